@@ -0,0 +1,18 @@
+//! Anchor-free building blocks shared by the ARS Core program and off-chain
+//! clients.
+//!
+//! The crate is `no_std` (it only needs `alloc`) so it compiles to
+//! `wasm32-unknown-unknown`, letting web clients build signing payloads and
+//! compute voting power byte-for-byte the same way the program does.
+
+#![no_std]
+
+extern crate alloc;
+
+pub mod message;
+pub mod program_ids;
+pub mod voting;
+
+pub use message::*;
+pub use program_ids::*;
+pub use voting::*;
@@ -0,0 +1,82 @@
+use alloc::vec::Vec;
+
+/// Domain prefix of proposal creation messages
+pub const PROPOSAL_MESSAGE_PREFIX: &[u8] = b"ARS_CREATE_PROPOSAL";
+
+/// Domain prefix of vote messages
+pub const VOTE_MESSAGE_PREFIX: &[u8] = b"ARS_VOTE";
+
+/// Construct message for proposal creation
+///
+/// Layout: prefix | agent (32) | policy_type (1) | policy_params | timestamp (i64 LE) | nonce (u64 LE)
+pub fn construct_proposal_message(
+    agent_pubkey: &[u8; 32],
+    policy_type: u8,
+    policy_params: &[u8],
+    timestamp: i64,
+    nonce: u64,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(PROPOSAL_MESSAGE_PREFIX.len() + 32 + 1 + policy_params.len() + 16);
+    message.extend_from_slice(PROPOSAL_MESSAGE_PREFIX);
+    message.extend_from_slice(agent_pubkey);
+    message.push(policy_type);
+    message.extend_from_slice(policy_params);
+    message.extend_from_slice(&timestamp.to_le_bytes());
+    message.extend_from_slice(&nonce.to_le_bytes());
+    message
+}
+
+/// Construct message for voting
+///
+/// Layout: prefix | agent (32) | proposal_id (u64 LE) | prediction (1) | stake_amount (u64 LE) | timestamp (i64 LE) | nonce (u64 LE)
+pub fn construct_vote_message(
+    agent_pubkey: &[u8; 32],
+    proposal_id: u64,
+    prediction: bool,
+    stake_amount: u64,
+    timestamp: i64,
+    nonce: u64,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(VOTE_MESSAGE_PREFIX.len() + 32 + 8 + 1 + 24);
+    message.extend_from_slice(VOTE_MESSAGE_PREFIX);
+    message.extend_from_slice(agent_pubkey);
+    message.extend_from_slice(&proposal_id.to_le_bytes());
+    message.push(if prediction { 1 } else { 0 });
+    message.extend_from_slice(&stake_amount.to_le_bytes());
+    message.extend_from_slice(&timestamp.to_le_bytes());
+    message.extend_from_slice(&nonce.to_le_bytes());
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_proposal_message_layout() {
+        let agent = [7u8; 32];
+        let message = construct_proposal_message(&agent, 2, &[1, 2, 3], 1_234_567_890, 42);
+        
+        let prefix_len = PROPOSAL_MESSAGE_PREFIX.len();
+        assert!(message.starts_with(PROPOSAL_MESSAGE_PREFIX));
+        assert_eq!(&message[prefix_len..prefix_len + 32], &agent);
+        assert_eq!(message[prefix_len + 32], 2);
+        assert_eq!(&message[prefix_len + 33..prefix_len + 36], &[1, 2, 3]);
+        assert_eq!(&message[prefix_len + 36..prefix_len + 44], &1_234_567_890i64.to_le_bytes());
+        assert_eq!(&message[prefix_len + 44..], &42u64.to_le_bytes());
+    }
+    
+    #[test]
+    fn test_vote_message_layout() {
+        let agent = [9u8; 32];
+        let message = construct_vote_message(&agent, 5, true, 1_000, 1_234_567_890, 42);
+        
+        let prefix_len = VOTE_MESSAGE_PREFIX.len();
+        assert!(message.starts_with(VOTE_MESSAGE_PREFIX));
+        assert_eq!(&message[prefix_len..prefix_len + 32], &agent);
+        assert_eq!(&message[prefix_len + 32..prefix_len + 40], &5u64.to_le_bytes());
+        assert_eq!(message[prefix_len + 40], 1);
+        assert_eq!(&message[prefix_len + 41..prefix_len + 49], &1_000u64.to_le_bytes());
+        assert_eq!(message.len(), prefix_len + 32 + 8 + 1 + 8 + 8 + 8);
+    }
+}
@@ -0,0 +1,15 @@
+//! Program ids of the ARS programs, for the programs that check each other's
+//! accounts. Kept as raw bytes so the crate stays free of Solana types; each
+//! program's tests check its own entry against `declare_id!`.
+
+/// ars-core program (`EpzmAas4F7XAWeHht7Yp3wTDcTciKLmXkhqaR5JhfCHE`)
+pub const ARS_CORE_PROGRAM_ID: [u8; 32] = [
+    205, 115, 138, 7, 28, 243, 11, 29, 22, 130, 137, 148, 233, 55, 168, 250,
+    88, 205, 235, 255, 76, 228, 206, 94, 93, 192, 236, 98, 248, 56, 254, 73,
+];
+
+/// ars-reserve program (`yiUCxoup6Jh7pcUsyZ8zR93kA13ecQX6EDdSEkGapQx`)
+pub const ARS_RESERVE_PROGRAM_ID: [u8; 32] = [
+    14, 135, 106, 31, 54, 24, 64, 171, 89, 164, 54, 186, 91, 51, 230, 238,
+    115, 224, 79, 65, 8, 235, 136, 182, 97, 84, 155, 181, 3, 203, 99, 193,
+];
@@ -0,0 +1,114 @@
+/// Errors from the voting power math
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VotingMathError {
+    /// Stake must be greater than zero
+    ZeroStake,
+    /// An intermediate value overflowed
+    Overflow,
+}
+
+/// Calculate square root using fixed-point arithmetic
+/// Uses Babylonian method (Newton's method) for sqrt calculation
+///
+/// Returns the floor of the square root
+pub fn sqrt_fixed(x: u64) -> Result<u64, VotingMathError> {
+    if x == 0 {
+        return Ok(0);
+    }
+    
+    // For small numbers, use direct calculation
+    if x < 4 {
+        return Ok(1);
+    }
+    
+    // Initial guess: the power of two at or above sqrt(x), at most 2^32.
+    // Starting from above, Newton's method decreases monotonically to the
+    // floor, and since y <= 2^32 and x / y < 2^33 the sum below cannot come
+    // near u64::MAX. (An x / 2 guess needed ~30 halving steps at the top of
+    // the range and stopped short of the root.)
+    let bits = u64::BITS - x.leading_zeros();
+    let mut y = 1u64 << bits.div_ceil(2);
+    
+    // Converges in about six steps; the bound is only a safety net
+    for _ in 0..64 {
+        // Newton's method: z = (x/y + y) / 2
+        let x_div_y = x.checked_div(y)
+            .ok_or(VotingMathError::Overflow)?;
+        
+        let z = x_div_y.checked_add(y)
+            .ok_or(VotingMathError::Overflow)?
+            / 2;
+        
+        if z >= y {
+            break;
+        }
+        y = z;
+    }
+    
+    Ok(y)
+}
+
+/// Calculate voting power using quadratic staking
+/// voting_power = sqrt(stake_amount), with a minimum of 1
+pub fn calculate_voting_power(stake_amount: u64) -> Result<u64, VotingMathError> {
+    if stake_amount == 0 {
+        return Err(VotingMathError::ZeroStake);
+    }
+    
+    Ok(sqrt_fixed(stake_amount)?.max(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_sqrt_is_floor() {
+        for x in [0u64, 1, 2, 3, 4, 10, 50, 99, 100, 10_000, 999_999, 1_000_000] {
+            let root = sqrt_fixed(x).unwrap();
+            assert!(root * root <= x);
+            assert!((root + 1) * (root + 1) > x);
+        }
+    }
+    
+    fn assert_floor_root(x: u64) {
+        let root = sqrt_fixed(x).unwrap() as u128;
+        assert!(root * root <= x as u128, "sqrt({}) = {} too large", x, root);
+        assert!((root + 1) * (root + 1) > x as u128, "sqrt({}) = {} too small", x, root);
+    }
+    
+    #[test]
+    fn test_sqrt_top_of_range() {
+        assert_eq!(sqrt_fixed(u64::MAX), Ok(u32::MAX as u64));
+        assert_eq!(sqrt_fixed(u64::MAX - 1), Ok(u32::MAX as u64));
+        assert_eq!(calculate_voting_power(u64::MAX), Ok(u32::MAX as u64));
+        
+        // Perfect squares near the top and their neighbours
+        let max_root = u32::MAX as u64;
+        assert_eq!(sqrt_fixed(max_root * max_root), Ok(max_root));
+        assert_eq!(sqrt_fixed(max_root * max_root - 1), Ok(max_root - 1));
+        
+        for shift in 32..64 {
+            let power = 1u64 << shift;
+            assert_floor_root(power - 1);
+            assert_floor_root(power);
+            assert_floor_root(power + 1);
+        }
+        assert_eq!(sqrt_fixed(1 << 62), Ok(1 << 31));
+    }
+    
+    #[test]
+    fn test_sqrt_small_values() {
+        for x in 0..100_000u64 {
+            assert_floor_root(x);
+        }
+    }
+    
+    #[test]
+    fn test_voting_power() {
+        assert_eq!(calculate_voting_power(0), Err(VotingMathError::ZeroStake));
+        assert_eq!(calculate_voting_power(1), Ok(1));
+        assert_eq!(calculate_voting_power(10_000), Ok(100));
+        assert_eq!(calculate_voting_power(1_000_000), Ok(1_000));
+    }
+}
@@ -13,6 +13,18 @@ pub const VOTE_SEED: &[u8] = b"vote";
 /// Seed for agent registry PDA
 pub const AGENT_SEED: &[u8] = b"agent";
 
+/// Seed for agent state (nonce / cooldown) PDA
+pub const AGENT_STATE_SEED: &[u8] = b"agent_state";
+
+/// Seed for oracle submitter schedule PDA
+pub const ORACLE_SUBMITTER_SEED: &[u8] = b"oracle_submitter";
+
+/// Seed for proposal outcome history PDA
+pub const PROPOSAL_HISTORY_SEED: &[u8] = b"proposal_history";
+
+/// Seed for staged parameter changes PDA
+pub const PENDING_PARAMETERS_SEED: &[u8] = b"pending_parameters";
+
 /// Basis points denominator (10000 = 100%)
 pub const BPS_DENOMINATOR: u16 = 10000;
 
@@ -35,12 +47,121 @@ pub const SLASHING_PENALTY_BPS: u16 = 1000;
 pub const EXECUTION_DELAY: i64 = 86400;
 
 // FIX #6: Oracle validation limits
-pub const MAX_ILI_VALUE: u64 = 1_000_000_000_000; // 1 trillion (scaled by 1e6)
+pub const MAX_ILI_VALUE: u64 = 1_000_000_000_000; // 1 trillion (raw, 1M at DEFAULT_ILI_DECIMALS)
 pub const MAX_YIELD_BPS: u32 = 100_000; // 1000% max APY
 pub const MAX_VOLATILITY_BPS: u32 = 100_000; // 1000% max volatility
 
 // FIX #7: Circuit breaker timelock (24 hours)
 pub const CIRCUIT_BREAKER_DELAY: i64 = 86400;
 
-// FIX #9: Minimum slot buffer for clock manipulation protection
+// FIX #9: Minimum slot buffer for clock manipulation protection; also the
+// hard cap of one ILI write per this many slots, regardless of interval settings
 pub const MIN_SLOT_BUFFER: u64 = 100; // ~40 seconds at 400ms/slot
+
+// Maximum ILI move between consecutive data points (20%)
+pub const MAX_ILI_DEVIATION_BPS: u64 = 2_000;
+
+// Oracle degraded mode: wider deviation (50%) for a recovering feed
+pub const DEGRADED_MAX_ILI_DEVIATION_BPS: u64 = 5_000;
+
+// Maximum ILI age before consumers treat it as stale (15 minutes)
+pub const MAX_ILI_STALENESS: i64 = 900;
+
+// Oracle degraded mode: consumers fail closed sooner (6 minutes)
+pub const DEGRADED_MAX_ILI_STALENESS: i64 = 360;
+
+// Oracle degraded mode: every update needs the authority plus a cosigner
+pub const DEGRADED_MIN_ILI_SIGNERS: usize = 2;
+
+// Maximum number of snapshots accepted by a single update_ili_batch; also the
+// ceiling for the governance-tunable GlobalState::max_batch_size
+pub const MAX_ILI_BATCH_SIZE: usize = 16;
+
+// Default minimum time between an agent's consecutive proposals (5 minutes)
+pub const DEFAULT_AGENT_ACTION_COOLDOWN: i64 = 300;
+
+// Maximum proposals tracked in GlobalState::active_proposals
+pub const MAX_ACTIVE_PROPOSALS: usize = 32;
+
+// Default maximum ILI age when executing MintICU/BurnICU proposals (15 minutes)
+pub const DEFAULT_EXECUTION_MAX_ORACLE_AGE: i64 = 900;
+
+// Default wait after end_time so in-flight votes land before finalization
+pub const DEFAULT_FINALIZATION_GRACE_SECS: i64 = 60;
+
+// Default minimum yes + no voting power for a proposal to pass (disabled)
+pub const DEFAULT_MIN_QUORUM_STAKE: u64 = 0;
+
+// Maximum total extension of a proposal's voting period (3 days)
+pub const MAX_PROPOSAL_EXTENSION: i64 = 259200;
+
+// Bounds for vhr_threshold: at least 110% safety margin, at most 300%
+pub const MIN_VHR_THRESHOLD: u16 = 11000;
+pub const MAX_VHR_THRESHOLD: u16 = 30000;
+pub const DEFAULT_VHR_THRESHOLD: u16 = 15000;
+
+// Maximum keepers in GlobalState::executor_allowlist
+pub const MAX_EXECUTORS: usize = 8;
+
+// Proposal ids start at 1; 0 is reserved as "no proposal" (e.g. for depends_on)
+pub const NO_PROPOSAL_ID: u64 = 0;
+pub const FIRST_PROPOSAL_ID: u64 = 1;
+
+// Signed timestamps may lag the cluster clock by up to 5 minutes but may only
+// lead it by 15 seconds, so messages cannot be pre-signed for later use
+pub const MAX_SIGNATURE_AGE: i64 = 300;
+pub const MAX_SIGNATURE_CLOCK_DRIFT: i64 = 15;
+
+// Yes share of voting power a proposal must exceed to pass (50%)
+pub const PASS_THRESHOLD_BPS: u16 = 5000;
+
+// Default minimum distinct voters for a proposal to pass (disabled)
+pub const DEFAULT_MIN_UNIQUE_VOTERS: u32 = 0;
+
+// Fixed-point decimals of ILI values; 6 matches the historical 1e6 scaling
+pub const DEFAULT_ILI_DECIMALS: u8 = 6;
+pub const MAX_ILI_DECIMALS: u8 = 12;
+
+// Default time after end_time before an unfinalized proposal can be force-expired (7 days)
+pub const DEFAULT_MAX_PROPOSAL_STALE_SECS: i64 = 604800;
+
+// Votes in the first 25% of the voting period count as early for the contrarian bonus
+pub const EARLY_VOTING_WINDOW_BPS: u16 = 2500;
+
+// Default extra reward for early contrarian winners (disabled); at most doubles the reward
+pub const DEFAULT_CONTRARIAN_BONUS_BPS: u16 = 0;
+pub const MAX_CONTRARIAN_BONUS_BPS: u16 = 10000;
+
+// ILI moves above 10% need a second submitter by default
+pub const DEFAULT_LARGE_ILI_MOVE_BPS: u16 = 1_000;
+pub const DEFAULT_LARGE_MOVE_QUORUM: u8 = 2;
+
+// Most outcomes query_proposal_history returns; keeps the result within return data limits
+pub const MAX_PROPOSAL_HISTORY_QUERY: usize = 32;
+
+// Default smallest reward share paid out; smaller shares are retained as dust (disabled)
+pub const DEFAULT_MIN_REWARD_PAYOUT: u64 = 0;
+
+// Default lowest ILI an update may report; 1 keeps the original "greater than zero" check
+pub const DEFAULT_MIN_ILI_VALUE: u64 = 1;
+
+// Default time after deactivating the circuit breaker before it can be requested again (1 hour)
+pub const DEFAULT_BREAKER_REREQUEST_COOLDOWN: i64 = 3600;
+
+// Compute unit ceiling of a single transaction, the most a crank can have left
+pub const MAX_TRANSACTION_COMPUTE_UNITS: u64 = 1_400_000;
+
+// Weight of the newest proposal in the turnout moving average (20%)
+pub const TURNOUT_EMA_ALPHA_BPS: u16 = 2000;
+
+// ars-reserve program, owner of the reserve vault read by try_auto_deactivate
+pub const ARS_RESERVE_PROGRAM_ID: anchor_lang::prelude::Pubkey =
+    anchor_lang::prelude::Pubkey::new_from_array(ars_common::ARS_RESERVE_PROGRAM_ID);
+
+// Delay between staging parameter changes and applying them (24 hours)
+pub const PARAMETER_TIMELOCK: i64 = 86400;
+
+// Default and hard ceiling on registered oracle submitters, bounding the cost
+// of iterating the submitter set
+pub const DEFAULT_MAX_SUBMITTERS: u16 = 8;
+pub const MAX_ORACLE_SUBMITTERS: u16 = 32;
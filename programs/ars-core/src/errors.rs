@@ -121,4 +121,180 @@ pub enum ICBError {
     
     #[msg("Signature expired")]
     SignatureExpired,
+    
+    // ILI history and batch updates
+    #[msg("ILI deviation exceeds maximum allowed")]
+    ILIDeviationTooLarge,
+    
+    #[msg("Invalid ILI batch size")]
+    InvalidBatchSize,
+    
+    #[msg("ILI timestamps must be strictly increasing")]
+    NonMonotonicTimestamp,
+    
+    // Agent throttling and configuration
+    #[msg("Agent action cooldown has not elapsed")]
+    ActionCooldownActive,
+    
+    #[msg("Invalid parameter value")]
+    InvalidParameter,
+    
+    #[msg("Account already migrated to the current version")]
+    AlreadyMigrated,
+    
+    #[msg("Invalid ILI history capacity")]
+    InvalidHistoryCapacity,
+    
+    #[msg("ILI update timestamp must be later than the last update")]
+    DuplicateILITimestamp,
+    
+    #[msg("Too many active proposals")]
+    TooManyActiveProposals,
+    
+    #[msg("ILI update rate limit exceeded")]
+    ILIUpdateRateLimited,
+    
+    #[msg("Agent type is not permitted to propose this policy type")]
+    PolicyNotPermittedForAgent,
+    
+    #[msg("Oracle data is stale")]
+    OracleStale,
+    
+    // Proposal execution state machine
+    #[msg("Proposal has already been executed")]
+    ProposalAlreadyExecuted,
+    
+    #[msg("Proposal failed and cannot be executed")]
+    ProposalFailedCannotExecute,
+    
+    #[msg("Proposal was cancelled")]
+    ProposalCancelled,
+    
+    #[msg("Proposal has already reached quorum")]
+    QuorumAlreadyMet,
+    
+    #[msg("Vote record has not been claimed")]
+    VoteNotClaimed,
+    
+    #[msg("Proposal is not in a terminal state")]
+    ProposalNotTerminal,
+    
+    // Executor allowlist
+    #[msg("Executor is already allowlisted")]
+    ExecutorAlreadyAllowed,
+    
+    #[msg("Executor allowlist is full")]
+    ExecutorAllowlistFull,
+    
+    #[msg("Executor is not allowlisted")]
+    ExecutorNotFound,
+    
+    #[msg("Invalid proposal id")]
+    InvalidProposalId,
+    
+    #[msg("Account is not owned by the expected program")]
+    InvalidAccountOwner,
+    
+    #[msg("Reserve vault has not been set")]
+    ReserveVaultNotSet,
+    
+    #[msg("Division by zero")]
+    DivisionByZero,
+    
+    #[msg("Only Active or Passed proposals can be vetoed")]
+    ProposalNotVetoable,
+    
+    #[msg("Proposal cannot depend on itself")]
+    SelfDependency,
+    
+    #[msg("Proposal can only depend on an earlier proposal")]
+    InvalidDependency,
+    
+    #[msg("Invalid ILI decimals")]
+    InvalidILIDecimals,
+    
+    #[msg("Batch exceeds the configured maximum size")]
+    BatchTooLarge,
+    
+    #[msg("Oracle last update slot is ahead of the current slot")]
+    LastUpdateSlotInFuture,
+    
+    #[msg("Proposal has not been stale long enough to force-expire")]
+    ProposalNotStale,
+    
+    #[msg("Rewards already claimed for this vote")]
+    RewardAlreadyClaimed,
+    
+    #[msg("Agent signature was already used on a previous vote")]
+    SignatureReused,
+    
+    #[msg("Large ILI move requires an additional oracle cosigner")]
+    LargeILIMoveNeedsCosigner,
+    
+    #[msg("Invalid policy parameters")]
+    InvalidPolicyParams,
+    
+    #[msg("Reserve balance cannot fund the rebalance swaps")]
+    InsufficientRebalanceLiquidity,
+    
+    #[msg("Unknown policy type")]
+    InvalidPolicyType,
+    
+    #[msg("Circuit breaker was deactivated too recently to request again")]
+    CircuitBreakerCooldownActive,
+    
+    #[msg("Proposal counter can only be advanced")]
+    ProposalCounterNotAdvanced,
+    
+    #[msg("Stake token account is not for the ICU mint")]
+    InvalidStakeMint,
+    
+    #[msg("Circuit breaker auto-recovery is disabled")]
+    AutoRecoverDisabled,
+    
+    #[msg("Circuit breaker is not active")]
+    CircuitBreakerNotActive,
+    
+    #[msg("Circuit breaker trigger condition still holds")]
+    BreakerConditionPersists,
+    
+    #[msg("Proposal deposit requires the proposer and escrow token accounts")]
+    DepositAccountsMissing,
+    
+    #[msg("No unsettled proposal deposit")]
+    NoDepositToSettle,
+    
+    #[msg("No parameter changes are pending")]
+    NoPendingParameters,
+    
+    #[msg("Parameter change timelock has not passed")]
+    ParameterTimelockNotMet,
+    
+    #[msg("ILI updates in degraded mode require an oracle cosigner")]
+    DegradedUpdateNeedsCosigner,
+    
+    #[msg("Oracle submitter limit reached")]
+    OracleSubmitterLimitReached,
+    
+    #[msg("ILI oracle account does not match global state")]
+    OracleMismatch,
+    
+    #[msg("Stake escrow does not match the proposal")]
+    InvalidStakeEscrow,
+    
+    #[msg("Escrowed and tally-only votes cannot be mixed on a proposal")]
+    EscrowModeMismatch,
+    
+    #[msg("Escrowed proposal requires the stake escrow accounts")]
+    EscrowAccountsMissing,
+    
+    #[msg("Registered oracle submitters must sign with their schedule account")]
+    OracleSubmitterRequired,
+}
+
+/// `error` carrying the mismatched keys, logged as Left (actual) / Right
+/// (expected), for key comparisons in `constraint = ...` that Anchor
+/// cannot annotate itself
+pub fn key_mismatch(error: ICBError, actual: Pubkey, expected: Pubkey) -> anchor_lang::error::Error {
+    anchor_lang::error::Error::from(error).with_pubkeys((actual, expected))
 }
@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::utils::{construct_proposal_message, construct_vote_message};
+
+/// Agent action whose Ed25519 message should be built
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum SigningAction {
+    CreateProposal {
+        policy_type: PolicyType,
+        policy_params: Vec<u8>,
+    },
+    Vote {
+        proposal_id: u64,
+        prediction: bool,
+        stake_amount: u64,
+    },
+}
+
+impl SigningAction {
+    /// Canonical message bytes the agent must sign for this action
+    pub fn message(&self, agent: &Pubkey, timestamp: i64, nonce: u64) -> Vec<u8> {
+        match self {
+            SigningAction::CreateProposal { policy_type, policy_params } => {
+                construct_proposal_message(
+                    agent,
+                    policy_type.clone() as u8,
+                    policy_params,
+                    timestamp,
+                    nonce,
+                )
+            }
+            SigningAction::Vote { proposal_id, prediction, stake_amount } => {
+                construct_vote_message(
+                    agent,
+                    *proposal_id,
+                    *prediction,
+                    *stake_amount,
+                    timestamp,
+                    nonce,
+                )
+            }
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct BuildSigningPayload<'info> {
+    /// CHECK: Only the key is used to build the message
+    pub agent: UncheckedAccount<'info>,
+    
+    /// Absent until the agent's first action, in which case the nonce is 0
+    #[account(
+        seeds = [AGENT_STATE_SEED, agent.key().as_ref()],
+        bump = agent_state.bump
+    )]
+    pub agent_state: Option<Account<'info, AgentState>>,
+}
+
+pub fn handler(
+    ctx: Context<BuildSigningPayload>,
+    action: SigningAction,
+    timestamp: i64,
+) -> Result<Vec<u8>> {
+    let nonce = ctx.accounts.agent_state
+        .as_ref()
+        .map(|agent_state| agent_state.nonce)
+        .unwrap_or(0);
+    
+    let message = action.message(&ctx.accounts.agent.key(), timestamp, nonce);
+    
+    msg!("Signing payload for agent: {:?}", ctx.accounts.agent.key());
+    msg!("Nonce: {}", nonce);
+    msg!("Message length: {}", message.len());
+    
+    Ok(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_proposal_payload_matches_reconstruction() {
+        let agent = Pubkey::new_unique();
+        let action = SigningAction::CreateProposal {
+            policy_type: PolicyType::BurnICU,
+            policy_params: vec![1, 2, 3],
+        };
+        
+        assert_eq!(
+            action.message(&agent, 1_700_000_000, 7),
+            construct_proposal_message(&agent, 1, &[1, 2, 3], 1_700_000_000, 7)
+        );
+    }
+    
+    #[test]
+    fn test_vote_payload_matches_reconstruction() {
+        let agent = Pubkey::new_unique();
+        let action = SigningAction::Vote {
+            proposal_id: 3,
+            prediction: true,
+            stake_amount: 5_000,
+        };
+        
+        assert_eq!(
+            action.message(&agent, 1_700_000_000, 7),
+            construct_vote_message(&agent, 3, true, 5_000, 1_700_000_000, 7)
+        );
+        
+        // The nonce is part of the signed bytes
+        assert_ne!(action.message(&agent, 1_700_000_000, 7), action.message(&agent, 1_700_000_000, 8));
+    }
+}
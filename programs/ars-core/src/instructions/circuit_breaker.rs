@@ -11,7 +11,7 @@ pub struct RequestCircuitBreaker<'info> {
         mut,
         seeds = [GLOBAL_STATE_SEED],
         bump = global_state.bump,
-        constraint = global_state.authority == authority.key() @ ICBError::Unauthorized
+        has_one = authority @ ICBError::Unauthorized
     )]
     pub global_state: Account<'info, GlobalState>,
     
@@ -30,6 +30,9 @@ pub fn request_circuit_breaker(ctx: Context<RequestCircuitBreaker>) -> Result<()
     let global_state = &mut ctx.accounts.global_state;
     let clock = Clock::get()?;
     
+    // Give the market time to settle after a deactivation
+    global_state.check_breaker_rerequest(clock.unix_timestamp)?;
+    
     // Check if VHR is below threshold (if reserve vault provided)
     let vhr_triggered = false;
     if let Some(_reserve_vault_info) = &ctx.accounts.reserve_vault {
@@ -70,7 +73,7 @@ pub struct ActivateCircuitBreaker<'info> {
         mut,
         seeds = [GLOBAL_STATE_SEED],
         bump = global_state.bump,
-        constraint = global_state.authority == authority.key() @ ICBError::Unauthorized
+        has_one = authority @ ICBError::Unauthorized
     )]
     pub global_state: Account<'info, GlobalState>,
     
@@ -102,7 +105,7 @@ pub struct DeactivateCircuitBreaker<'info> {
         mut,
         seeds = [GLOBAL_STATE_SEED],
         bump = global_state.bump,
-        constraint = global_state.authority == authority.key() @ ICBError::Unauthorized
+        has_one = authority @ ICBError::Unauthorized
     )]
     pub global_state: Account<'info, GlobalState>,
     
@@ -111,13 +114,105 @@ pub struct DeactivateCircuitBreaker<'info> {
 
 pub fn deactivate_circuit_breaker(ctx: Context<DeactivateCircuitBreaker>) -> Result<()> {
     let global_state = &mut ctx.accounts.global_state;
+    let clock = Clock::get()?;
     
     // Deactivation can be immediate (emergency recovery)
-    global_state.circuit_breaker_active = false;
-    global_state.circuit_breaker_requested_at = 0; // Reset request
+    global_state.deactivate_breaker(clock.unix_timestamp);
     
     msg!("Circuit breaker DEACTIVATED");
+    msg!("Can be requested again after: {}", clock.unix_timestamp + global_state.breaker_rerequest_cooldown);
     
     Ok(())
 }
 
+// Byte offsets into an ars-reserve ReserveVault: discriminator, four pubkeys
+// and total_value_usd precede liabilities_usd, which precedes vhr
+const RESERVE_LIABILITIES_OFFSET: usize = 8 + 32 * 4 + 8;
+const RESERVE_VHR_OFFSET: usize = RESERVE_LIABILITIES_OFFSET + 8;
+
+/// Read the VHR from raw ars-reserve ReserveVault data. A vault with no
+/// liabilities is fully backed and reads as u16::MAX.
+pub fn read_reserve_vhr(data: &[u8]) -> Result<u16> {
+    let discriminator = anchor_lang::solana_program::hash::hash(b"account:ReserveVault");
+    require!(
+        data.len() >= RESERVE_VHR_OFFSET + 2 && data[..8] == discriminator.to_bytes()[..8],
+        ICBError::InvalidReserveVault
+    );
+    
+    let liabilities = u64::from_le_bytes(
+        data[RESERVE_LIABILITIES_OFFSET..RESERVE_VHR_OFFSET].try_into().unwrap()
+    );
+    if liabilities == 0 {
+        return Ok(u16::MAX);
+    }
+    Ok(u16::from_le_bytes(data[RESERVE_VHR_OFFSET..RESERVE_VHR_OFFSET + 2].try_into().unwrap()))
+}
+
+#[derive(Accounts)]
+pub struct TryAutoDeactivate<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        seeds = [ILI_ORACLE_SEED],
+        bump = ili_oracle.bump
+    )]
+    pub ili_oracle: Account<'info, ILIOracle>,
+    
+    /// CHECK: ars-reserve ReserveVault, parsed by read_reserve_vhr
+    #[account(owner = ARS_RESERVE_PROGRAM_ID @ ICBError::InvalidReserveVault)]
+    pub reserve_vault: UncheckedAccount<'info>,
+    
+    pub caller: Signer<'info>,
+}
+
+pub fn try_auto_deactivate(ctx: Context<TryAutoDeactivate>) -> Result<()> {
+    let clock = Clock::get()?;
+    let reserve_vhr = read_reserve_vhr(&ctx.accounts.reserve_vault.try_borrow_data()?)?;
+    let oracle_stale = ctx.accounts.ili_oracle.is_stale(clock.unix_timestamp);
+    
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.check_breaker_recovered(reserve_vhr, oracle_stale)?;
+    global_state.deactivate_breaker(clock.unix_timestamp);
+    
+    msg!("Circuit breaker auto-DEACTIVATED by {}", ctx.accounts.caller.key());
+    msg!("Reserve VHR: {} bps (threshold {})", reserve_vhr, global_state.vhr_threshold.bps());
+    msg!("ILI last update: {}", ctx.accounts.ili_oracle.last_update);
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn reserve_data(liabilities: u64, vhr: u16) -> Vec<u8> {
+        let mut data = vec![0u8; RESERVE_VHR_OFFSET + 2 + 64];
+        let discriminator = anchor_lang::solana_program::hash::hash(b"account:ReserveVault");
+        data[..8].copy_from_slice(&discriminator.to_bytes()[..8]);
+        data[RESERVE_LIABILITIES_OFFSET..RESERVE_VHR_OFFSET].copy_from_slice(&liabilities.to_le_bytes());
+        data[RESERVE_VHR_OFFSET..RESERVE_VHR_OFFSET + 2].copy_from_slice(&vhr.to_le_bytes());
+        data
+    }
+    
+    #[test]
+    fn test_shared_program_id_matches_declared() {
+        assert_eq!(Pubkey::new_from_array(ars_common::ARS_CORE_PROGRAM_ID), crate::ID);
+    }
+    
+    #[test]
+    fn test_read_reserve_vhr() {
+        assert_eq!(read_reserve_vhr(&reserve_data(1_000, 14_000)).unwrap(), 14_000);
+        assert_eq!(read_reserve_vhr(&reserve_data(0, 0)).unwrap(), u16::MAX);
+        
+        // Some other account type, or a truncated one
+        let mut other = reserve_data(1_000, 20_000);
+        other[0] ^= 1;
+        assert_eq!(read_reserve_vhr(&other).unwrap_err(), ICBError::InvalidReserveVault.into());
+        assert!(read_reserve_vhr(&reserve_data(1_000, 20_000)[..RESERVE_VHR_OFFSET]).is_err());
+    }
+}
@@ -0,0 +1,330 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::{key_mismatch, ICBError};
+use crate::math::{mul_div_ceil, mul_div_floor};
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, PolicyProposal>,
+    
+    #[account(
+        mut,
+        seeds = [VOTE_SEED, proposal.key().as_ref(), agent.key().as_ref()],
+        bump = vote_record.bump,
+        constraint = !vote_record.claimed @ ICBError::RewardAlreadyClaimed
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+    
+    pub agent: Signer<'info>,
+    
+    /// Holds the stakes of a proposal voted through stake_and_vote,
+    /// required when the proposal has one
+    #[account(
+        mut,
+        address = proposal.stake_escrow @ ICBError::InvalidStakeEscrow
+    )]
+    pub stake_escrow: Option<Account<'info, TokenAccount>>,
+    
+    /// Receives the payout from the stake escrow
+    #[account(
+        mut,
+        constraint = agent_token_account.owner == agent.key()
+            @ key_mismatch(ICBError::InvalidAccountOwner, agent_token_account.owner, agent.key()),
+        constraint = agent_token_account.mint == global_state.icu_mint @ ICBError::InvalidStakeMint
+    )]
+    pub agent_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// Receives what the payouts leave in the escrow (reward dust) once
+    /// the last vote is settled
+    #[account(
+        mut,
+        address = global_state.reserve_vault @ ICBError::InvalidReserveVault
+    )]
+    pub reserve_vault: Option<Account<'info, TokenAccount>>,
+    
+    pub token_program: Option<Program<'info, Token>>,
+}
+
+pub fn handler(ctx: Context<ClaimRewards>) -> Result<u64> {
+    let proposal = &mut ctx.accounts.proposal;
+    let vote_record = &mut ctx.accounts.vote_record;
+    
+    require!(proposal.status.is_terminal(), ICBError::ProposalNotTerminal);
+    
+    let global_state = &ctx.accounts.global_state;
+    let payout = settle_vote(
+        proposal,
+        vote_record.stake_amount,
+        vote_record.prediction,
+        vote_record.contrarian,
+        global_state.contrarian_bonus_bps,
+        global_state.min_reward_payout,
+    )?;
+    
+    vote_record.claimed = true;
+    proposal.record_settlement()?;
+    
+    // Tally-only proposals hold no tokens; escrowed ones pay out here
+    if proposal.is_escrowed() {
+        let (Some(escrow), Some(agent_token_account), Some(reserve_vault), Some(token_program)) = (
+            ctx.accounts.stake_escrow.as_mut(),
+            &ctx.accounts.agent_token_account,
+            &ctx.accounts.reserve_vault,
+            &ctx.accounts.token_program,
+        ) else {
+            return err!(ICBError::EscrowAccountsMissing);
+        };
+        
+        if payout > 0 {
+            pay_from_escrow(proposal, escrow, agent_token_account.to_account_info(), token_program, payout)?;
+        }
+        
+        // Once every vote is settled the escrow is drained, so sweeping
+        // the proposal leaves no tokens behind
+        if proposal.settled_vote_count >= proposal.unique_voter_count {
+            escrow.reload()?;
+            let remainder = escrow.amount;
+            if remainder > 0 {
+                pay_from_escrow(proposal, escrow, reserve_vault.to_account_info(), token_program, remainder)?;
+                msg!("Escrow remainder of {} retained by the reserve", remainder);
+            }
+        }
+    }
+    
+    emit!(RewardClaimed {
+        proposal_id: proposal.id,
+        agent: ctx.accounts.agent.key(),
+        stake_amount: vote_record.stake_amount,
+        payout,
+        contrarian: vote_record.contrarian,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Vote on proposal {} settled", proposal.id);
+    msg!("Stake: {}, payout: {}", vote_record.stake_amount, payout);
+    
+    Ok(payout)
+}
+
+/// Transfer `amount` out of the stake escrow, signed by the proposal PDA
+/// that owns it
+fn pay_from_escrow<'info>(
+    proposal: &Account<'info, PolicyProposal>,
+    escrow: &Account<'info, TokenAccount>,
+    to: AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let id_bytes = proposal.id.to_le_bytes();
+    let seeds = &[PROPOSAL_SEED, &id_bytes, &[proposal.bump]];
+    let signer = &[&seeds[..]];
+    
+    let cpi_accounts = Transfer {
+        from: escrow.to_account_info(),
+        to,
+        authority: proposal.to_account_info(),
+    };
+    token::transfer(
+        CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer),
+        amount,
+    )
+}
+
+/// Amount returned to a voter for `stake_amount`. Incorrect predictions
+/// lose SLASHING_PENALTY_BPS of their stake, rounded up so the losers always
+/// cover the losing pool. Correct ones get their stake back plus a share of
+/// the losing pool pro rata to their reward weight (stake, raised by
+/// `contrarian_bonus_bps` for early minority-side votes), rounded down.
+/// Shares below `min_payout` return the stake only. Without a winning side
+/// the stake is refunded.
+pub fn settle_vote(
+    proposal: &mut PolicyProposal,
+    stake_amount: u64,
+    prediction: bool,
+    contrarian: bool,
+    contrarian_bonus_bps: u16,
+    min_payout: u64,
+) -> Result<u64> {
+    let Some(winning_side) = proposal.winning_side() else {
+        return Ok(stake_amount);
+    };
+    
+    if prediction != winning_side {
+        let slashed = mul_div_ceil(stake_amount, SLASHING_PENALTY_BPS as u64, BPS_DENOMINATOR as u64)?;
+        return Ok(stake_amount - slashed);
+    }
+    
+    let weight = reward_weight(stake_amount, contrarian, contrarian_bonus_bps)?;
+    let share = mul_div_floor(
+        proposal.losing_pool()?,
+        weight,
+        proposal.winning_weight(contrarian_bonus_bps)?,
+    )?;
+    let reward = proposal.pay_reward(share, min_payout)?;
+    
+    stake_amount
+        .checked_add(reward)
+        .ok_or(error!(ICBError::ArithmeticOverflow))
+}
+
+/// A winning vote's claim on the losing pool; mirrors
+/// `PolicyProposal::winning_weight` for a single vote
+pub fn reward_weight(stake_amount: u64, contrarian: bool, contrarian_bonus_bps: u16) -> Result<u64> {
+    if !contrarian {
+        return Ok(stake_amount);
+    }
+    let bonus = mul_div_floor(stake_amount, contrarian_bonus_bps as u64, BPS_DENOMINATOR as u64)?;
+    stake_amount
+        .checked_add(bonus)
+        .ok_or(error!(ICBError::ArithmeticOverflow))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const STAKE: u64 = 1_000_000;
+    
+    /// (stake, prediction, contrarian)
+    type Vote = (u64, bool, bool);
+    
+    fn proposal(status: ProposalStatus, votes: &[Vote]) -> PolicyProposal {
+        let mut proposal = PolicyProposal {
+            id: 1,
+            proposer: Pubkey::new_unique(),
+            end_time: MIN_VOTING_PERIOD,
+            status,
+            unique_voter_count: votes.len() as u32,
+            bump: 255,
+            ..Default::default()
+        };
+        for &(stake, prediction, contrarian) in votes {
+            proposal.add_stake(prediction, stake, contrarian).unwrap();
+        }
+        proposal
+    }
+    
+    /// Settle every vote in order, returning the winners' total reward
+    fn settle_all(proposal: &mut PolicyProposal, votes: &[Vote], bonus_bps: u16, min_payout: u64) -> u64 {
+        let mut rewards = 0;
+        for &(stake, prediction, contrarian) in votes {
+            let payout = settle_vote(proposal, stake, prediction, contrarian, bonus_bps, min_payout).unwrap();
+            if payout > stake {
+                rewards += payout - stake;
+            }
+            proposal.record_settlement().unwrap();
+        }
+        rewards
+    }
+    
+    #[test]
+    fn test_payouts_plus_dust_equal_losing_pool() {
+        // Stakes chosen so no pro-rata share divides evenly
+        let votes = [
+            (333_333, true, false),
+            (1_000_001, false, false),
+            (333_334, true, true),
+            (7, true, false),
+            (999_999, false, false),
+            (333_333, true, false),
+            (3, false, false),
+        ];
+        
+        for bonus_bps in [0, 5000] {
+            let mut proposal = proposal(ProposalStatus::Executed, &votes);
+            let pool = proposal.losing_pool().unwrap();
+            assert_eq!(pool, 200_000);
+            
+            let rewards = settle_all(&mut proposal, &votes, bonus_bps, 0);
+            
+            assert!(proposal.reward_dust > 0);
+            assert_eq!(proposal.rewards_paid, rewards);
+            assert_eq!(rewards + proposal.reward_dust, pool);
+        }
+    }
+    
+    #[test]
+    fn test_small_shares_retained_as_dust() {
+        let votes = [(STAKE, false, false), (1_000, false, false), (STAKE, true, false)];
+        let mut proposal = proposal(ProposalStatus::Failed, &votes);
+        let pool = proposal.losing_pool().unwrap();
+        
+        // The small winner's share is below min_payout: stake returned only
+        assert_eq!(settle_vote(&mut proposal, 1_000, false, false, 0, 100).unwrap(), 1_000);
+        assert!(proposal.reward_dust > 0);
+        
+        let big = settle_vote(&mut proposal, STAKE, false, false, 0, 100).unwrap();
+        assert!(big > STAKE);
+        
+        // Losers are slashed regardless of min_payout
+        assert_eq!(settle_vote(&mut proposal, STAKE, true, false, 0, 100).unwrap(), 900_000);
+        for _ in 0..votes.len() {
+            proposal.record_settlement().unwrap();
+        }
+        
+        assert_eq!(proposal.rewards_paid, big - STAKE);
+        assert_eq!(proposal.rewards_paid + proposal.reward_dust, pool);
+    }
+    
+    #[test]
+    fn test_contrarian_winner_earns_more_than_bandwagon() {
+        let bonus_bps = 5000; // 1.5x weight
+        let votes = [(STAKE, true, false), (STAKE, true, true), (STAKE, false, false)];
+        let mut proposal = proposal(ProposalStatus::Executed, &votes);
+        
+        let bandwagon = settle_vote(&mut proposal, STAKE, true, false, bonus_bps, 0).unwrap();
+        let contrarian = settle_vote(&mut proposal, STAKE, true, true, bonus_bps, 0).unwrap();
+        
+        // 100_000 pool split 1 : 1.5
+        assert_eq!(bandwagon, 1_040_000);
+        assert_eq!(contrarian, 1_060_000);
+    }
+    
+    #[test]
+    fn test_bonus_disabled_by_default() {
+        let votes = [(STAKE, false, true), (STAKE, false, false), (STAKE, true, false)];
+        let mut proposal = proposal(ProposalStatus::Failed, &votes);
+        
+        assert_eq!(
+            settle_vote(&mut proposal, STAKE, false, true, DEFAULT_CONTRARIAN_BONUS_BPS, 0).unwrap(),
+            settle_vote(&mut proposal, STAKE, false, false, DEFAULT_CONTRARIAN_BONUS_BPS, 0).unwrap()
+        );
+    }
+    
+    #[test]
+    fn test_losers_slashed_regardless_of_contrarian() {
+        // Being early and in the minority does not help a wrong prediction
+        let votes = [(STAKE, true, false), (STAKE, true, true), (STAKE, false, false)];
+        let mut proposal = proposal(ProposalStatus::Failed, &votes);
+        for contrarian in [false, true] {
+            assert_eq!(settle_vote(&mut proposal, STAKE, true, contrarian, 5000, 0).unwrap(), 900_000);
+        }
+        
+        // Rounded up, so a loser never forfeits less than its part of the pool
+        assert_eq!(settle_vote(&mut proposal, 15, true, false, 0, 0).unwrap(), 13);
+    }
+    
+    #[test]
+    fn test_cancelled_proposal_refunds() {
+        let votes = [(STAKE, true, true), (STAKE, false, false)];
+        let mut proposal = proposal(ProposalStatus::Cancelled, &votes);
+        
+        assert_eq!(settle_vote(&mut proposal, STAKE, true, true, 5000, 0).unwrap(), STAKE);
+        assert_eq!(settle_vote(&mut proposal, STAKE, false, false, 5000, 0).unwrap(), STAKE);
+        assert_eq!(settle_all(&mut proposal, &votes, 5000, 0), 0);
+        assert_eq!(proposal.reward_dust, 0);
+    }
+}
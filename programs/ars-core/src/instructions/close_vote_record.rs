@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct CloseVoteRecord<'info> {
+    #[account(
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, PolicyProposal>,
+    
+    #[account(
+        mut,
+        close = agent,
+        seeds = [VOTE_SEED, proposal.key().as_ref(), agent.key().as_ref()],
+        bump = vote_record.bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+    
+    /// Receives the reclaimed rent
+    #[account(mut)]
+    pub agent: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CloseVoteRecord>) -> Result<()> {
+    ctx.accounts.vote_record.check_closable(&ctx.accounts.proposal.status)?;
+    
+    msg!("Vote record closed for proposal: {}", ctx.accounts.proposal.id);
+    msg!("Rent returned to: {}", ctx.accounts.agent.key());
+    
+    Ok(())
+}
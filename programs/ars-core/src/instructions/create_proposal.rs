@@ -1,21 +1,24 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use crate::state::*;
 use crate::constants::*;
-use crate::errors::ICBError;
+use crate::errors::{key_mismatch, ICBError};
 
 #[derive(Accounts)]
-#[instruction(policy_type: PolicyType, policy_params: Vec<u8>, duration: i64)]
+#[instruction(policy_type: PolicyType, policy_params: Vec<u8>, duration: i64, depends_on: Option<u64>)]
 pub struct CreateProposal<'info> {
     #[account(
         mut, // FIX #1: Need mut to update proposal_counter
         seeds = [GLOBAL_STATE_SEED],
         bump = global_state.bump,
-        constraint = !global_state.circuit_breaker_active @ ICBError::CircuitBreakerActive
+        constraint = global_state.can_propose(&policy_type) @ ICBError::CircuitBreakerActive,
+        constraint = global_state.is_reserve_ready_for(&policy_type) @ ICBError::ReserveVaultNotSet
     )]
     pub global_state: Account<'info, GlobalState>,
     
+    /// Created here, or already allocated (zeroed) by preallocate_proposal
     #[account(
-        init,
+        init_if_needed,
         payer = proposer,
         space = PolicyProposal::LEN,
         seeds = [PROPOSAL_SEED, &global_state.proposal_counter.to_le_bytes()], // FIX #4: Use counter from global_state
@@ -23,6 +26,24 @@ pub struct CreateProposal<'info> {
     )]
     pub proposal: Account<'info, PolicyProposal>,
     
+    #[account(
+        init_if_needed,
+        payer = proposer,
+        space = AgentState::LEN,
+        seeds = [AGENT_STATE_SEED, proposer.key().as_ref()],
+        bump
+    )]
+    pub agent_state: Account<'info, AgentState>,
+    
+    #[account(
+        mut,
+        seeds = [AGENT_SEED, proposer.key().as_ref()],
+        bump = agent_registry.bump,
+        constraint = agent_registry.agent_type.can_propose(&policy_type)
+            @ ICBError::PolicyNotPermittedForAgent
+    )]
+    pub agent_registry: Account<'info, AgentRegistry>,
+    
     #[account(mut)]
     pub proposer: Signer<'info>,
     
@@ -31,6 +52,26 @@ pub struct CreateProposal<'info> {
     pub instructions_sysvar: AccountInfo<'info>,
     
     pub system_program: Program<'info, System>,
+    
+    /// Source of the proposal deposit, required while proposal_deposit > 0
+    #[account(
+        mut,
+        constraint = proposer_token_account.owner == proposer.key()
+            @ key_mismatch(ICBError::InvalidAccountOwner, proposer_token_account.owner, proposer.key()),
+        constraint = proposer_token_account.mint == global_state.icu_mint @ ICBError::InvalidStakeMint
+    )]
+    pub proposer_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// Holds the deposit until settle_deposit
+    #[account(
+        mut,
+        constraint = deposit_escrow.owner == proposal.key()
+            @ key_mismatch(ICBError::InvalidAccountOwner, deposit_escrow.owner, proposal.key()),
+        constraint = deposit_escrow.mint == global_state.icu_mint @ ICBError::InvalidStakeMint
+    )]
+    pub deposit_escrow: Option<Account<'info, TokenAccount>>,
+    
+    pub token_program: Option<Program<'info, Token>>,
 }
 
 pub fn handler(
@@ -38,6 +79,7 @@ pub fn handler(
     policy_type: PolicyType,
     policy_params: Vec<u8>,
     duration: i64,
+    depends_on: Option<u64>,
 ) -> Result<()> {
     // ARS-SA-2026-001: Validate agent authentication
     crate::validate_agent_auth(
@@ -57,27 +99,84 @@ pub fn handler(
     
     let global_state = &mut ctx.accounts.global_state;
     let proposal = &mut ctx.accounts.proposal;
+    let agent_state = &mut ctx.accounts.agent_state;
     let clock = Clock::get()?;
     
+    // A preallocated account may be filled once, never overwritten
+    check_unfilled(proposal)?;
+    
+    // Throttle rapid-fire proposals from the same agent
+    agent_state.check_cooldown(clock.unix_timestamp, global_state.agent_action_cooldown)?;
+    agent_state.agent_pubkey = ctx.accounts.proposer.key();
+    agent_state.last_action_timestamp = clock.unix_timestamp;
+    agent_state.bump = ctx.bumps.agent_state;
+    ctx.accounts.agent_registry.record_activity(0, clock.unix_timestamp)?;
+    
     // FIX #1: Use monotonic counter instead of timestamp
-    let proposal_id = global_state.proposal_counter;
-    global_state.proposal_counter = proposal_id
-        .checked_add(1)
-        .ok_or(ICBError::CounterOverflow)?;
+    let proposal_id = global_state.allocate_proposal_id()?;
+    global_state.track_active_proposal(proposal_id)?;
+    validate_dependency(depends_on, proposal_id)?;
     
     proposal.id = proposal_id;
     proposal.proposer = ctx.accounts.proposer.key();
     proposal.policy_type = policy_type.clone();
     proposal.policy_params = policy_params.clone();
-    proposal.start_time = clock.unix_timestamp;
-    proposal.end_time = clock.unix_timestamp + duration;
+    proposal.stamp_created(&clock);
+    proposal.end_time = compute_end_time(clock.unix_timestamp, duration)?;
     proposal.yes_stake = 0;
     proposal.no_stake = 0;
     proposal.status = ProposalStatus::Active;
     proposal.execution_tx = None;
     proposal.passed_at = 0; // FIX #3: Initialize passed_at
+    proposal.unique_voter_count = 0;
+    proposal.depends_on = depends_on;
+    proposal.settled_vote_count = 0;
+    proposal.yes_staked = 0;
+    proposal.no_staked = 0;
+    proposal.yes_contrarian_staked = 0;
+    proposal.no_contrarian_staked = 0;
+    proposal.rewards_paid = 0;
+    proposal.reward_dust = 0;
+    proposal.deposit_amount = 0;
+    proposal.deposit_escrow = Pubkey::default();
+    proposal.deposit_settled = false;
+    proposal.stake_escrow = Pubkey::default(); // Bound by the first stake_and_vote
+    proposal.snapshot_rules(global_state);
+    proposal.record_rent_payer(ctx.accounts.proposer.key());
+    proposal.locked = false;
     proposal.bump = ctx.bumps.proposal;
     
+    // Escrow the anti-spam deposit, returned by settle_deposit on quorum
+    let deposit = global_state.proposal_deposit;
+    if deposit > 0 {
+        let (Some(from), Some(escrow), Some(token_program)) = (
+            &ctx.accounts.proposer_token_account,
+            &ctx.accounts.deposit_escrow,
+            &ctx.accounts.token_program,
+        ) else {
+            return err!(ICBError::DepositAccountsMissing);
+        };
+        
+        let cpi_accounts = Transfer {
+            from: from.to_account_info(),
+            to: escrow.to_account_info(),
+            authority: ctx.accounts.proposer.to_account_info(),
+        };
+        token::transfer(CpiContext::new(token_program.to_account_info(), cpi_accounts), deposit)?;
+        
+        proposal.deposit_amount = deposit;
+        proposal.deposit_escrow = escrow.key();
+        msg!("Deposit escrowed: {}", deposit);
+    }
+    
+    emit!(ProposalCreated {
+        proposal_id,
+        proposer: proposal.proposer,
+        policy_type: policy_type.clone(),
+        created_slot: proposal.created_slot,
+        timestamp: clock.unix_timestamp,
+    });
+    
     msg!("Proposal created: {}", proposal_id);
     msg!("Policy type: {:?}", policy_type);
     msg!("Duration: {} seconds", duration);
@@ -85,3 +184,75 @@ pub fn handler(
     
     Ok(())
 }
+
+/// Require a proposal account that has not been filled yet. Preallocated
+/// accounts are zeroed, so they still carry `NO_PROPOSAL_ID`.
+pub fn check_unfilled(proposal: &PolicyProposal) -> Result<()> {
+    require!(proposal.id == NO_PROPOSAL_ID, ICBError::ProposalAlreadyExists);
+    Ok(())
+}
+
+/// Compute the voting end time, rejecting i64 overflow
+pub fn compute_end_time(start_time: i64, duration: i64) -> Result<i64> {
+    start_time
+        .checked_add(duration)
+        .ok_or(error!(ICBError::ArithmeticOverflow))
+}
+
+/// A proposal may only depend on an earlier one. Ids are allocated in
+/// increasing order, so this also rules out self-references and cycles.
+pub fn validate_dependency(depends_on: Option<u64>, proposal_id: u64) -> Result<()> {
+    if let Some(dependency) = depends_on {
+        require!(dependency != proposal_id, ICBError::SelfDependency);
+        require!(
+            dependency >= FIRST_PROPOSAL_ID && dependency < proposal_id,
+            ICBError::InvalidDependency
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_compute_end_time() {
+        assert_eq!(compute_end_time(1_000, MIN_VOTING_PERIOD).unwrap(), 1_000 + MIN_VOTING_PERIOD);
+        assert_eq!(compute_end_time(i64::MAX - MAX_VOTING_PERIOD, MAX_VOTING_PERIOD).unwrap(), i64::MAX);
+    }
+    
+    #[test]
+    fn test_compute_end_time_overflow() {
+        assert_eq!(
+            compute_end_time(i64::MAX - MAX_VOTING_PERIOD + 1, MAX_VOTING_PERIOD).unwrap_err(),
+            ICBError::ArithmeticOverflow.into()
+        );
+        assert!(compute_end_time(i64::MAX, MIN_VOTING_PERIOD).is_err());
+    }
+    
+    #[test]
+    fn test_dependency_must_be_earlier() {
+        assert!(validate_dependency(None, 5).is_ok());
+        assert!(validate_dependency(Some(4), 5).is_ok());
+        assert!(validate_dependency(Some(FIRST_PROPOSAL_ID), 5).is_ok());
+        
+        // Self-reference
+        assert_eq!(
+            validate_dependency(Some(5), 5).unwrap_err(),
+            ICBError::SelfDependency.into()
+        );
+        
+        // Forward references could form a cycle
+        assert_eq!(
+            validate_dependency(Some(6), 5).unwrap_err(),
+            ICBError::InvalidDependency.into()
+        );
+        
+        // 0 is never a proposal id
+        assert_eq!(
+            validate_dependency(Some(NO_PROPOSAL_ID), 5).unwrap_err(),
+            ICBError::InvalidDependency.into()
+        );
+    }
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+use crate::utils::{diagnose_agent_auth_window, load_preceding_instructions, AuthDiagnosis, MAX_ED25519_LOOKBACK};
+
+#[derive(Accounts)]
+pub struct DebugVerifyAuth<'info> {
+    /// CHECK: Instructions sysvar for agent verification (ARS-SA-2026-001)
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+}
+
+pub fn handler(
+    ctx: Context<DebugVerifyAuth>,
+    expected_agent: Pubkey,
+    expected_message: Option<Vec<u8>>,
+) -> Result<AuthDiagnosis> {
+    let preceding = load_preceding_instructions(&ctx.accounts.instructions_sysvar, MAX_ED25519_LOOKBACK)?;
+    let diagnosis = diagnose_agent_auth_window(&preceding, &expected_agent, expected_message.as_deref());
+    
+    msg!("Agent {} authentication: {:?}", expected_agent, diagnosis);
+    
+    Ok(diagnosis)
+}
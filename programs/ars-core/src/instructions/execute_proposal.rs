@@ -1,11 +1,15 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
 use crate::state::*;
 use crate::constants::*;
 use crate::errors::ICBError;
+use crate::math::mul_div_floor;
+use crate::utils::{check_executable_status, execution_ready_at, is_executable};
 
 #[derive(Accounts)]
 pub struct ExecuteProposal<'info> {
     #[account(
+        mut,
         seeds = [GLOBAL_STATE_SEED],
         bump = global_state.bump
     )]
@@ -14,16 +18,33 @@ pub struct ExecuteProposal<'info> {
     #[account(
         mut,
         seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
-        bump = proposal.bump,
-        constraint = proposal.status == ProposalStatus::Active || proposal.status == ProposalStatus::Passed
-            @ ICBError::ProposalNotActive
+        bump = proposal.bump
     )]
     pub proposal: Account<'info, PolicyProposal>,
     
     #[account(
-        constraint = global_state.authority == executor.key() @ ICBError::Unauthorized // FIX #3: Require authority
+        seeds = [ILI_ORACLE_SEED],
+        bump = ili_oracle.bump
+    )]
+    pub ili_oracle: Account<'info, ILIOracle>,
+    
+    #[account(
+        mut,
+        seeds = [PROPOSAL_HISTORY_SEED],
+        bump = proposal_history.bump
+    )]
+    pub proposal_history: Account<'info, ProposalHistory>,
+    
+    /// The proposal `proposal` depends on, required when it declares one.
+    /// When it ended Failed or Cancelled the dependent is cancelled instead
+    /// of finalized.
+    #[account(
+        seeds = [PROPOSAL_SEED, &dependency.id.to_le_bytes()],
+        bump = dependency.bump
     )]
-    pub executor: Signer<'info>,
+    pub dependency: Option<Account<'info, PolicyProposal>>,
+    
+    pub executor: Signer<'info>, // FIX #3: Authority or allowlisted keeper, checked in the handler
     
     /// CHECK: Instructions sysvar for agent verification (ARS-SA-2026-001)
     #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
@@ -31,56 +52,88 @@ pub struct ExecuteProposal<'info> {
 }
 
 pub fn handler(ctx: Context<ExecuteProposal>) -> Result<()> {
+    ctx.accounts.global_state.check_executor(&ctx.accounts.executor.key())?;
+    
     // ARS-SA-2026-001: Validate agent authentication
     crate::validate_agent_auth(
         &ctx.accounts.instructions_sysvar,
         &ctx.accounts.executor.key(),
     )?;
     
+    let global_state = &mut ctx.accounts.global_state;
     let proposal = &mut ctx.accounts.proposal;
+    let proposal_history = &mut ctx.accounts.proposal_history;
     let clock = Clock::get()?;
     
+    check_executable_status(&proposal.status)?;
+    
+    // Votes on a proposal whose dependency failed can never count; refund them
+    if proposal.cancel_for_failed_dependency(ctx.accounts.dependency.as_deref())? {
+        global_state.untrack_active_proposal(proposal.id);
+        proposal_history.record(ProposalOutcome::from(&**proposal));
+        
+        msg!("Proposal {} CANCELLED: dependency {:?} did not pass", proposal.id, proposal.depends_on);
+        
+        return Ok(());
+    }
+    
     // If proposal is Active, check voting and mark as Passed/Failed
     if proposal.status == ProposalStatus::Active {
-        // Check if voting period has ended
-        require!(
-            clock.unix_timestamp >= proposal.end_time,
-            ICBError::ProposalStillActive
-        );
+        // Check if voting period has ended, plus a grace for in-flight votes
+        check_finalization_window(
+            proposal.end_time,
+            global_state.finalization_grace_secs,
+            clock.unix_timestamp,
+        )?;
         
-        // Calculate total stake and consensus
-        let total_stake = proposal.yes_stake
-            .checked_add(proposal.no_stake)
-            .ok_or(ICBError::ArithmeticOverflow)?;
+        // Voting is over either way, drop it from the keeper work list
+        global_state.untrack_active_proposal(proposal.id);
         
-        require!(total_stake > 0, ICBError::InsufficientStake);
-        
-        // FIX #8: Safe percentage calculation with overflow protection
-        require!(
-            (proposal.yes_stake as u128) <= u128::MAX / 10000,
-            ICBError::ArithmeticOverflow
-        );
+        let tally = evaluate_tally(proposal)?;
+        let yes_percentage = tally.yes_bps;
+        global_state.record_turnout(tally.total_stake)?;
         
-        let yes_percentage = (proposal.yes_stake as u128)
-            .checked_mul(10000)
-            .ok_or(ICBError::ArithmeticOverflow)?
-            .checked_div(total_stake as u128)
-            .ok_or(ICBError::ArithmeticOverflow)? as u16;
+        match tally.outcome {
+            TallyOutcome::NoVotes => return err!(ICBError::InsufficientStake),
+            // Not enough participation to decide either way
+            TallyOutcome::BelowQuorum => {
+                proposal.transition(ProposalStatus::Active, ProposalStatus::Failed)?;
+                proposal_history.record(ProposalOutcome::from(&**proposal));
+                
+                msg!("Proposal {} FAILED: quorum not met", proposal.id);
+                msg!("Total stake: {} (quorum {})", tally.total_stake, proposal.min_quorum_stake);
+                
+                return Ok(());
+            }
+            // Enough stake, but from too few distinct agents
+            TallyOutcome::TooFewVoters => {
+                proposal.transition(ProposalStatus::Active, ProposalStatus::Failed)?;
+                proposal_history.record(ProposalOutcome::from(&**proposal));
+                
+                msg!("Proposal {} FAILED: too few voters", proposal.id);
+                msg!("Voters: {} (minimum {})", proposal.unique_voter_count, proposal.min_unique_voters);
+                
+                return Ok(());
+            }
+            TallyOutcome::Passed | TallyOutcome::Rejected => {}
+        }
         
-        if yes_percentage > 5000 {
+        if tally.outcome == TallyOutcome::Passed {
             // Proposal passed - set passed_at for execution delay
-            proposal.status = ProposalStatus::Passed;
+            proposal.transition(ProposalStatus::Active, ProposalStatus::Passed)?;
             proposal.passed_at = clock.unix_timestamp; // FIX #3: Record when passed
+            proposal_history.record(ProposalOutcome::from(&**proposal));
             
             msg!("Proposal {} PASSED", proposal.id);
             msg!("YES: {} ({} bps)", proposal.yes_stake, yes_percentage);
             msg!("NO: {}", proposal.no_stake);
-            msg!("Can be executed after: {}", clock.unix_timestamp + EXECUTION_DELAY);
+            msg!("Can be executed after: {}", execution_ready_at(proposal.passed_at)?);
             
             return Ok(());
         } else {
             // Proposal failed
-            proposal.status = ProposalStatus::Failed;
+            proposal.transition(ProposalStatus::Active, ProposalStatus::Failed)?;
+            proposal_history.record(ProposalOutcome::from(&**proposal));
             
             msg!("Proposal {} FAILED", proposal.id);
             msg!("YES: {} ({} bps)", proposal.yes_stake, yes_percentage);
@@ -89,12 +142,13 @@ pub fn handler(ctx: Context<ExecuteProposal>) -> Result<()> {
             // Slashing logic for failed predictions
             // Voters who predicted incorrectly (YES voters in this case) lose 10% of their stake
             // This incentivizes accurate predictions and discourages spam proposals
-            let slashing_percentage = 1000; // 10% in basis points
-            let yes_slashed = (proposal.yes_stake as u128)
-                .checked_mul(slashing_percentage as u128)
-                .ok_or(ICBError::ArithmeticOverflow)?
-                .checked_div(10000)
-                .ok_or(ICBError::ArithmeticOverflow)? as u64;
+            // Rounded down: slashed stake is paid out to NO voters, so it must
+            // never exceed what was actually taken
+            let yes_slashed = mul_div_floor(
+                proposal.yes_stake,
+                SLASHING_PENALTY_BPS as u64,
+                BPS_DENOMINATOR as u64,
+            )?;
             
             msg!("Slashing {} from YES voters (10%)", yes_slashed);
             msg!("Slashed funds will be distributed to NO voters");
@@ -110,18 +164,43 @@ pub fn handler(ctx: Context<ExecuteProposal>) -> Result<()> {
     if proposal.status == ProposalStatus::Passed {
         // FIX #3: Enforce execution delay
         require!(
-            clock.unix_timestamp >= proposal.passed_at + EXECUTION_DELAY,
+            is_executable(proposal, clock.unix_timestamp)?,
             ICBError::ExecutionDelayNotMet
         );
         
+        // Defer while the breaker is up; the proposal stays Passed and can be
+        // executed once it is deactivated
+        check_circuit_breaker(global_state, &proposal.policy_type)?;
+        
+        // Monetary policies must not execute against a stale ILI
+        check_oracle_freshness(
+            &proposal.policy_type,
+            ctx.accounts.ili_oracle.last_update,
+            clock.unix_timestamp,
+            ctx.accounts.ili_oracle.execution_max_age(global_state.execution_max_oracle_age),
+        )?;
+        check_oracle_initialized(
+            &proposal.policy_type,
+            ctx.accounts.ili_oracle.current_ili,
+            ctx.accounts.ili_oracle.snapshot_count,
+        )?;
+        
+        // Fail before any swap if the reserve cannot fund every leg. Source
+        // token accounts are passed in remaining_accounts.
+        if proposal.policy_type == PolicyType::RebalanceVault {
+            let params = RebalanceParams::try_from_slice(&proposal.policy_params)
+                .map_err(|_| error!(ICBError::InvalidPolicyParams))?;
+            let balances = token_balances(ctx.remaining_accounts)?;
+            check_rebalance_liquidity(&params, &balances)?;
+        }
+        
         msg!("Executing proposal {}", proposal.id);
         msg!("Policy type: {:?}", proposal.policy_type);
         
-        // TODO: Execute policy based on policy_type
-        // This would involve calling other programs (ARU token, reserve, etc.)
-        // For now, just mark as executed
+        execute_policy(&proposal.policy_type)?;
         
-        proposal.status = ProposalStatus::Executed;
+        proposal.transition(ProposalStatus::Passed, ProposalStatus::Executed)?;
+        proposal_history.record(ProposalOutcome::from(&**proposal));
         msg!("Proposal executed successfully");
         
         return Ok(());
@@ -130,3 +209,341 @@ pub fn handler(ctx: Context<ExecuteProposal>) -> Result<()> {
     Err(ICBError::ProposalNotReadyForExecution.into())
 }
 
+/// Apply a passed policy. Every PolicyType is matched explicitly, with no
+/// wildcard arm, so adding a variant fails to compile until it is handled
+/// here rather than silently executing as a no-op.
+pub fn execute_policy(policy_type: &PolicyType) -> Result<()> {
+    // TODO: CPI into the ICU token and reserve programs for each policy
+    match policy_type {
+        PolicyType::MintICU => msg!("Minting ICU"),
+        PolicyType::BurnICU => msg!("Burning ICU"),
+        PolicyType::UpdateICR => msg!("Updating ICR"),
+        PolicyType::RebalanceVault => msg!("Rebalancing reserve vault"),
+    }
+    Ok(())
+}
+
+/// How a proposal's votes resolve if it is finalized with its current tally
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TallyOutcome {
+    NoVotes,
+    BelowQuorum,
+    TooFewVoters,
+    Rejected,
+    Passed,
+}
+
+/// Vote totals and the resulting outcome, shared by execute_proposal and
+/// query_proposal_status so the two can never disagree
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Tally {
+    pub total_stake: u64,
+    pub yes_bps: u16,
+    pub quorum_met: bool,
+    pub outcome: TallyOutcome,
+}
+
+/// Resolve a proposal's tally. Checks run in order: any votes, quorum,
+/// distinct voters, then the yes share must exceed PASS_THRESHOLD_BPS.
+/// Quorum and voter minimum are the ones snapshotted at creation.
+pub fn evaluate_tally(proposal: &PolicyProposal) -> Result<Tally> {
+    let total_stake = proposal.yes_stake
+        .checked_add(proposal.no_stake)
+        .ok_or(ICBError::ArithmeticOverflow)?;
+    let quorum_met = proposal.meets_quorum(proposal.min_quorum_stake);
+    
+    if total_stake == 0 {
+        return Ok(Tally { total_stake, yes_bps: 0, quorum_met, outcome: TallyOutcome::NoVotes });
+    }
+    
+    // FIX #8: Safe percentage calculation with overflow protection.
+    // Rounded down so a proposal never passes on a rounding artifact.
+    let yes_bps = mul_div_floor(proposal.yes_stake, BPS_DENOMINATOR as u64, total_stake)? as u16;
+    
+    let outcome = if !quorum_met {
+        TallyOutcome::BelowQuorum
+    } else if !proposal.meets_voter_minimum(proposal.min_unique_voters) {
+        TallyOutcome::TooFewVoters
+    } else if yes_bps > PASS_THRESHOLD_BPS {
+        TallyOutcome::Passed
+    } else {
+        TallyOutcome::Rejected
+    };
+    
+    Ok(Tally { total_stake, yes_bps, quorum_met, outcome })
+}
+
+/// Require `grace_secs` to have passed since `end_time` before finalizing
+pub fn check_finalization_window(end_time: i64, grace_secs: i64, now: i64) -> Result<()> {
+    let ready_at = end_time
+        .checked_add(grace_secs)
+        .ok_or(ICBError::ArithmeticOverflow)?;
+    require!(now >= ready_at, ICBError::ProposalStillActive);
+    Ok(())
+}
+
+/// Reject execution of a policy type frozen by the active circuit breaker
+pub fn check_circuit_breaker(global_state: &GlobalState, policy_type: &PolicyType) -> Result<()> {
+    require!(
+        !global_state.is_blocked_by_breaker(policy_type),
+        ICBError::CircuitBreakerActive
+    );
+    Ok(())
+}
+
+/// Reject execution of a price-sensitive policy when the ILI is older than `max_age`
+pub fn check_oracle_freshness(
+    policy_type: &PolicyType,
+    last_update: i64,
+    now: i64,
+    max_age: i64,
+) -> Result<()> {
+    if policy_type.is_price_sensitive() {
+        require!(
+            now.saturating_sub(last_update) <= max_age,
+            ICBError::OracleStale
+        );
+    }
+    Ok(())
+}
+
+/// Reject execution of a price-sensitive policy before the ILI has ever been
+/// updated, so monetary actions are never priced off an empty feed
+pub fn check_oracle_initialized(
+    policy_type: &PolicyType,
+    current_ili: u64,
+    snapshot_count: u16,
+) -> Result<()> {
+    if policy_type.is_price_sensitive() {
+        require!(
+            current_ili > 0 && snapshot_count > 0,
+            ICBError::InvalidILIValue
+        );
+    }
+    Ok(())
+}
+
+/// (key, amount) of each SPL token account in `accounts`
+pub fn token_balances(accounts: &[AccountInfo]) -> Result<Vec<(Pubkey, u64)>> {
+    accounts
+        .iter()
+        .map(|account| {
+            require_keys_eq!(*account.owner, anchor_spl::token::ID, ICBError::InvalidAccountOwner);
+            let token_account = TokenAccount::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+            Ok((account.key(), token_account.amount))
+        })
+        .collect()
+}
+
+/// Check that every swap of a rebalance can be funded from `balances`.
+/// Swaps drawing on the same source are summed, and a source missing from
+/// `balances` counts as empty.
+pub fn check_rebalance_liquidity(params: &RebalanceParams, balances: &[(Pubkey, u64)]) -> Result<()> {
+    require!(!params.swaps.is_empty(), ICBError::InvalidPolicyParams);
+    
+    for (i, swap) in params.swaps.iter().enumerate() {
+        require!(swap.amount_in > 0, ICBError::InvalidPolicyParams);
+        
+        // Check each source once, against the total of all its legs
+        if params.swaps[..i].iter().any(|s| s.source == swap.source) {
+            continue;
+        }
+        let required = params.swaps[i..]
+            .iter()
+            .filter(|s| s.source == swap.source)
+            .try_fold(0u64, |total, s| total.checked_add(s.amount_in))
+            .ok_or(ICBError::ArithmeticOverflow)?;
+        let available = balances
+            .iter()
+            .find(|(key, _)| *key == swap.source)
+            .map_or(0, |(_, amount)| *amount);
+        
+        require!(available >= required, ICBError::InsufficientRebalanceLiquidity);
+    }
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn swap(source: Pubkey, amount_in: u64) -> RebalanceSwap {
+        RebalanceSwap { source, amount_in }
+    }
+    
+    #[test]
+    fn test_feasible_rebalance_accepted() {
+        let (usdc, sol) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let balances = [(usdc, 1_000_000), (sol, 5_000)];
+        let params = RebalanceParams { swaps: vec![swap(usdc, 400_000), swap(sol, 5_000), swap(usdc, 600_000)] };
+        
+        assert!(check_rebalance_liquidity(&params, &balances).is_ok());
+        
+        // policy_params round-trip as stored on the proposal
+        let encoded = params.try_to_vec().unwrap();
+        assert_eq!(RebalanceParams::try_from_slice(&encoded).unwrap(), params);
+    }
+    
+    #[test]
+    fn test_infeasible_rebalance_rejected_before_any_swap() {
+        let (usdc, sol) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let balances = [(usdc, 1_000_000), (sol, 5_000)];
+        
+        // Each leg fits on its own, but together they overdraw USDC
+        let overdrawn = RebalanceParams { swaps: vec![swap(usdc, 600_000), swap(sol, 1_000), swap(usdc, 600_000)] };
+        assert_eq!(
+            check_rebalance_liquidity(&overdrawn, &balances).unwrap_err(),
+            ICBError::InsufficientRebalanceLiquidity.into()
+        );
+        
+        // A source that was not supplied has no liquidity
+        let missing = RebalanceParams { swaps: vec![swap(Pubkey::new_unique(), 1)] };
+        assert_eq!(
+            check_rebalance_liquidity(&missing, &balances).unwrap_err(),
+            ICBError::InsufficientRebalanceLiquidity.into()
+        );
+    }
+    
+    #[test]
+    fn test_malformed_rebalance_params_rejected() {
+        let source = Pubkey::new_unique();
+        let balances = [(source, 1_000)];
+        
+        for params in [
+            RebalanceParams { swaps: vec![] },
+            RebalanceParams { swaps: vec![swap(source, 0)] },
+        ] {
+            assert_eq!(
+                check_rebalance_liquidity(&params, &balances).unwrap_err(),
+                ICBError::InvalidPolicyParams.into()
+            );
+        }
+        
+        let overflow = RebalanceParams { swaps: vec![swap(source, u64::MAX), swap(source, 1)] };
+        assert!(check_rebalance_liquidity(&overflow, &balances).is_err());
+    }
+    
+    #[test]
+    fn test_unknown_policy_type_rejected() {
+        for policy_type in [
+            PolicyType::MintICU,
+            PolicyType::BurnICU,
+            PolicyType::UpdateICR,
+            PolicyType::RebalanceVault,
+        ] {
+            // Every known type decodes from its own discriminant and executes
+            let discriminant = policy_type.try_to_vec().unwrap()[0];
+            assert_eq!(PolicyType::try_from(discriminant).unwrap(), policy_type);
+            assert!(execute_policy(&policy_type).is_ok());
+        }
+        
+        // Out-of-range discriminants fail loudly instead of falling through
+        for discriminant in [4, 5, u8::MAX] {
+            assert_eq!(
+                PolicyType::try_from(discriminant).unwrap_err(),
+                ICBError::InvalidPolicyType.into()
+            );
+        }
+    }
+    
+    #[test]
+    fn test_oracle_freshness_for_monetary_policies() {
+        let now = 1_700_000_000;
+        let max_age = DEFAULT_EXECUTION_MAX_ORACLE_AGE;
+        
+        // Fresh oracle
+        assert!(check_oracle_freshness(&PolicyType::MintICU, now - max_age, now, max_age).is_ok());
+        assert!(check_oracle_freshness(&PolicyType::BurnICU, now - 10, now, max_age).is_ok());
+        
+        // Stale oracle
+        assert_eq!(
+            check_oracle_freshness(&PolicyType::MintICU, now - max_age - 1, now, max_age).unwrap_err(),
+            ICBError::OracleStale.into()
+        );
+        assert!(check_oracle_freshness(&PolicyType::BurnICU, now - max_age - 1, now, max_age).is_err());
+        
+        // Non-price policies are exempt
+        assert!(check_oracle_freshness(&PolicyType::UpdateICR, 0, now, max_age).is_ok());
+        assert!(check_oracle_freshness(&PolicyType::RebalanceVault, 0, now, max_age).is_ok());
+    }
+    
+    #[test]
+    fn test_degraded_oracle_blocks_monetary_execution() {
+        let now = 1_700_000_000;
+        // Within the execution age, but past the degraded staleness limit
+        let last_update = now - DEGRADED_MAX_ILI_STALENESS - 40;
+        let normal = ILIOracle { last_update, ..Default::default() };
+        let degraded = ILIOracle { last_update, degraded: true, ..Default::default() };
+        
+        let max_age = normal.execution_max_age(DEFAULT_EXECUTION_MAX_ORACLE_AGE);
+        assert!(check_oracle_freshness(&PolicyType::MintICU, last_update, now, max_age).is_ok());
+        
+        let max_age = degraded.execution_max_age(DEFAULT_EXECUTION_MAX_ORACLE_AGE);
+        assert_eq!(max_age, DEGRADED_MAX_ILI_STALENESS);
+        assert_eq!(
+            check_oracle_freshness(&PolicyType::MintICU, last_update, now, max_age).unwrap_err(),
+            ICBError::OracleStale.into()
+        );
+        
+        // A tighter governance age still applies
+        assert_eq!(degraded.execution_max_age(60), 60);
+    }
+    
+    #[test]
+    fn test_minting_rejected_before_first_ili_update() {
+        // Freshly initialized oracle: no value, no snapshots
+        assert_eq!(
+            check_oracle_initialized(&PolicyType::MintICU, 0, 0).unwrap_err(),
+            ICBError::InvalidILIValue.into()
+        );
+        assert!(check_oracle_initialized(&PolicyType::BurnICU, 0, 0).is_err());
+        assert!(check_oracle_initialized(&PolicyType::MintICU, 5_000_000, 0).is_err());
+        assert!(check_oracle_initialized(&PolicyType::MintICU, 0, 1).is_err());
+        
+        // Allowed once the first update has landed
+        assert!(check_oracle_initialized(&PolicyType::MintICU, 5_000_000, 1).is_ok());
+        
+        // Non-price policies do not depend on the feed
+        assert!(check_oracle_initialized(&PolicyType::UpdateICR, 0, 0).is_ok());
+    }
+    
+    #[test]
+    fn test_execution_deferred_by_circuit_breaker() {
+        let mut global_state = GlobalState {
+            circuit_breaker_active: true,
+            breaker_exempt_policies: PolicyType::mask(&[PolicyType::BurnICU]),
+            ..Default::default()
+        };
+        
+        // Blocked while the breaker is active, unless exempt
+        assert_eq!(
+            check_circuit_breaker(&global_state, &PolicyType::MintICU).unwrap_err(),
+            ICBError::CircuitBreakerActive.into()
+        );
+        assert!(check_circuit_breaker(&global_state, &PolicyType::BurnICU).is_ok());
+        
+        // Executable again after deactivation
+        global_state.circuit_breaker_active = false;
+        assert!(check_circuit_breaker(&global_state, &PolicyType::MintICU).is_ok());
+    }
+    
+    #[test]
+    fn test_finalization_grace_window() {
+        let end_time = 1_700_000_000;
+        let grace = DEFAULT_FINALIZATION_GRACE_SECS;
+        
+        // Rejected at end_time and anywhere inside the grace window
+        for now in [end_time, end_time + grace - 1] {
+            assert_eq!(
+                check_finalization_window(end_time, grace, now).unwrap_err(),
+                ICBError::ProposalStillActive.into()
+            );
+        }
+        assert!(check_finalization_window(end_time, grace, end_time + grace).is_ok());
+        
+        // Without a grace period finalization opens at end_time
+        assert!(check_finalization_window(end_time, 0, end_time).is_ok());
+        assert!(check_finalization_window(i64::MAX, 1, i64::MAX).is_err());
+    }
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::ICBError;
+
+#[derive(Accounts)]
+pub struct ManageExecutors<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ICBError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn add_executor(ctx: Context<ManageExecutors>, executor: Pubkey) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.add_executor(executor)?;
+    
+    msg!("Executor added: {}", executor);
+    msg!("Allowlisted executors: {}", global_state.executor_allowlist.len());
+    
+    Ok(())
+}
+
+pub fn remove_executor(ctx: Context<ManageExecutors>, executor: Pubkey) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.remove_executor(&executor)?;
+    
+    msg!("Executor removed: {}", executor);
+    msg!("Allowlisted executors: {}", global_state.executor_allowlist.len());
+    
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::ICBError;
+
+#[derive(Accounts)]
+pub struct ExtendProposal<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ICBError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, PolicyProposal>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ExtendProposal>, additional_secs: i64) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let clock = Clock::get()?;
+    
+    let min_quorum_stake = proposal.min_quorum_stake;
+    let new_end_time = proposal.extend_voting(additional_secs, min_quorum_stake, clock.unix_timestamp)?;
+    
+    emit!(ProposalExtended {
+        proposal_id: proposal.id,
+        additional_secs,
+        new_end_time,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Proposal {} extended by {} seconds", proposal.id, additional_secs);
+    msg!("New end time: {}", new_end_time);
+    
+    Ok(())
+}
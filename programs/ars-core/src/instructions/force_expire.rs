@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::ICBError;
+
+#[derive(Accounts)]
+pub struct ForceExpire<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ICBError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, PolicyProposal>,
+    
+    #[account(
+        mut,
+        seeds = [PROPOSAL_HISTORY_SEED],
+        bump = proposal_history.bump
+    )]
+    pub proposal_history: Account<'info, ProposalHistory>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ForceExpire>) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let proposal = &mut ctx.accounts.proposal;
+    let clock = Clock::get()?;
+    
+    proposal.force_expire(global_state.max_stale_secs, clock.unix_timestamp)?;
+    
+    // Free the active index slot the stuck proposal was holding
+    global_state.untrack_active_proposal(proposal.id);
+    ctx.accounts.proposal_history.record(ProposalOutcome::from(&**proposal));
+    
+    emit!(ProposalForceExpired {
+        proposal_id: proposal.id,
+        end_time: proposal.end_time,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Proposal {} force-expired", proposal.id);
+    
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::ICBError;
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ICBError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Option<Pubkey>) -> Result<()> {
+    ctx.accounts.global_state.set_guardian(guardian)?;
+    
+    match guardian {
+        Some(guardian) => msg!("Guardian set: {}", guardian),
+        None => msg!("Guardian cleared"),
+    }
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct VetoProposal<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, PolicyProposal>,
+    
+    #[account(
+        mut,
+        seeds = [PROPOSAL_HISTORY_SEED],
+        bump = proposal_history.bump
+    )]
+    pub proposal_history: Account<'info, ProposalHistory>,
+    
+    /// With no guardian set the default key is expected, which cannot sign
+    #[account(address = global_state.guardian.unwrap_or_default() @ ICBError::Unauthorized)]
+    pub guardian: Signer<'info>,
+}
+
+pub fn veto_proposal(ctx: Context<VetoProposal>) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let proposal = &mut ctx.accounts.proposal;
+    
+    proposal.veto()?;
+    
+    // A vetoed proposal no longer needs a keeper
+    global_state.untrack_active_proposal(proposal.id);
+    ctx.accounts.proposal_history.record(ProposalOutcome::from(&**proposal));
+    
+    emit!(ProposalVetoed {
+        proposal_id: proposal.id,
+        guardian: ctx.accounts.guardian.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Proposal {} vetoed by guardian", proposal.id);
+    
+    Ok(())
+}
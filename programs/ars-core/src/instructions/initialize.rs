@@ -30,50 +30,179 @@ pub struct Initialize<'info> {
     pub system_program: Program<'info, System>,
 }
 
+/// Protocol settings chosen at initialization
+pub struct InitConfig {
+    pub epoch_duration: i64,
+    pub mint_burn_cap_bps: u16,
+    pub stability_fee_bps: u16,
+    pub vhr_threshold: u16,
+    pub ili_decimals: u8,
+}
+
+impl InitConfig {
+    /// Validate the settings and write a freshly initialized GlobalState and
+    /// ILIOracle owned by `authority`. Bumps are left to the caller.
+    pub fn apply(
+        &self,
+        global_state: &mut GlobalState,
+        ili_oracle: &mut ILIOracle,
+        authority: Pubkey,
+        ili_oracle_key: Pubkey,
+        slot: u64,
+    ) -> Result<()> {
+        require!(self.epoch_duration > 0, ICBError::InvalidEpochDuration);
+        require!(self.mint_burn_cap_bps <= BPS_DENOMINATOR, ICBError::InvalidMintBurnCap);
+        let vhr_threshold = ICRatio::new(self.vhr_threshold)?;
+        ili_scale(self.ili_decimals)?;
+        
+        // Initialize global state
+        global_state.authority = authority;
+        global_state.ili_oracle = ili_oracle_key;
+        global_state.reserve_vault = Pubkey::default(); // Set by initialize_full or later via set_reserve_vault
+        global_state.icu_mint = Pubkey::default(); // Set by initialize_full or later via set_reserve_vault
+        global_state.epoch_duration = self.epoch_duration;
+        global_state.mint_burn_cap_bps = self.mint_burn_cap_bps;
+        global_state.stability_fee_bps = self.stability_fee_bps;
+        global_state.vhr_threshold = vhr_threshold;
+        global_state.circuit_breaker_active = false;
+        global_state.proposal_counter = FIRST_PROPOSAL_ID; // FIX #1: Initialize counter (0 is reserved)
+        global_state.circuit_breaker_requested_at = 0; // FIX #7: Initialize timelock
+        global_state.last_update_slot = slot; // FIX #9: Initialize slot
+        global_state.agent_action_cooldown = DEFAULT_AGENT_ACTION_COOLDOWN;
+        global_state.version = GlobalState::CURRENT_VERSION;
+        global_state.breaker_exempt_policies = 0;
+        global_state.active_proposals = Vec::new();
+        global_state.execution_max_oracle_age = DEFAULT_EXECUTION_MAX_ORACLE_AGE;
+        global_state.finalization_grace_secs = DEFAULT_FINALIZATION_GRACE_SECS;
+        global_state.min_quorum_stake = DEFAULT_MIN_QUORUM_STAKE;
+        global_state.executor_allowlist = Vec::new();
+        global_state.oracle_layout_version = ILIOracle::LAYOUT_VERSION;
+        global_state.min_unique_voters = DEFAULT_MIN_UNIQUE_VOTERS;
+        global_state.max_active_proposals = MAX_ACTIVE_PROPOSALS as u8;
+        global_state.guardian = None;
+        global_state.max_batch_size = MAX_ILI_BATCH_SIZE as u8;
+        global_state.max_stale_secs = DEFAULT_MAX_PROPOSAL_STALE_SECS;
+        global_state.contrarian_bonus_bps = DEFAULT_CONTRARIAN_BONUS_BPS;
+        global_state.large_ili_move_bps = DEFAULT_LARGE_ILI_MOVE_BPS;
+        global_state.large_move_quorum = DEFAULT_LARGE_MOVE_QUORUM;
+        global_state.min_reward_payout = DEFAULT_MIN_REWARD_PAYOUT;
+        global_state.last_deactivated_at = 0;
+        global_state.breaker_rerequest_cooldown = DEFAULT_BREAKER_REREQUEST_COOLDOWN;
+        global_state.max_signature_age = MAX_SIGNATURE_AGE;
+        global_state.turnout_ema = 0;
+        global_state.turnout_samples = 0;
+        global_state.auto_recover = false;
+        global_state.proposal_deposit = 0;
+        global_state.oracle_submitter_count = 0;
+        global_state.max_submitters = DEFAULT_MAX_SUBMITTERS;
+        
+        // Initialize ILI oracle
+        ili_oracle.authority = authority;
+        ili_oracle.current_ili = 0;
+        ili_oracle.ili_decimals = self.ili_decimals;
+        ili_oracle.min_ili_value = DEFAULT_MIN_ILI_VALUE;
+        ili_oracle.last_update = 0;
+        ili_oracle.update_interval = DEFAULT_ILI_UPDATE_INTERVAL;
+        ili_oracle.snapshot_count = 0;
+        ili_oracle.last_update_slot = slot; // FIX #9: Initialize slot
+        ili_oracle.degraded = false;
+        ili_oracle.history_capacity = ILIOracle::DEFAULT_HISTORY_CAPACITY as u16;
+        ili_oracle.history_head = 0;
+        ili_oracle.history = Vec::new();
+        
+        Ok(())
+    }
+}
+
 pub fn handler(
     ctx: Context<Initialize>,
     epoch_duration: i64,
     mint_burn_cap_bps: u16,
     stability_fee_bps: u16,
     vhr_threshold: u16,
+    ili_decimals: u8,
 ) -> Result<()> {
-    require!(epoch_duration > 0, ICBError::InvalidEpochDuration);
-    require!(mint_burn_cap_bps <= BPS_DENOMINATOR, ICBError::InvalidMintBurnCap);
-    require!(vhr_threshold >= 10000, ICBError::InvalidVHRThreshold); // At least 100%
-    
+    let config = InitConfig { epoch_duration, mint_burn_cap_bps, stability_fee_bps, vhr_threshold, ili_decimals };
+    let authority = ctx.accounts.authority.key();
+    let ili_oracle_key = ctx.accounts.ili_oracle.key();
     let global_state = &mut ctx.accounts.global_state;
     let ili_oracle = &mut ctx.accounts.ili_oracle;
     let clock = Clock::get()?;
     
-    // Initialize global state
-    global_state.authority = ctx.accounts.authority.key();
-    global_state.ili_oracle = ili_oracle.key();
-    global_state.reserve_vault = Pubkey::default(); // Set later via set_reserve_vault
-    global_state.icu_mint = Pubkey::default(); // Set later via set_reserve_vault
-    global_state.epoch_duration = epoch_duration;
-    global_state.mint_burn_cap_bps = mint_burn_cap_bps;
-    global_state.stability_fee_bps = stability_fee_bps;
-    global_state.vhr_threshold = vhr_threshold;
-    global_state.circuit_breaker_active = false;
-    global_state.proposal_counter = 0; // FIX #1: Initialize counter
-    global_state.circuit_breaker_requested_at = 0; // FIX #7: Initialize timelock
-    global_state.last_update_slot = clock.slot; // FIX #9: Initialize slot
+    config.apply(global_state, ili_oracle, authority, ili_oracle_key, clock.slot)?;
     global_state.bump = ctx.bumps.global_state;
-    
-    // Initialize ILI oracle
-    ili_oracle.authority = ctx.accounts.authority.key();
-    ili_oracle.current_ili = 0;
-    ili_oracle.last_update = 0;
-    ili_oracle.update_interval = DEFAULT_ILI_UPDATE_INTERVAL;
-    ili_oracle.snapshot_count = 0;
-    ili_oracle.last_update_slot = clock.slot; // FIX #9: Initialize slot
     ili_oracle.bump = ctx.bumps.ili_oracle;
     
     msg!("ARS Protocol initialized");
     msg!("Authority: {}", global_state.authority);
     msg!("Epoch duration: {} seconds", epoch_duration);
     msg!("Mint/burn cap: {} bps", mint_burn_cap_bps);
-    msg!("VHR threshold: {} bps", vhr_threshold);
+    msg!("VHR threshold: {} bps", global_state.vhr_threshold.bps());
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeFull<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = GlobalState::LEN,
+        seeds = [GLOBAL_STATE_SEED],
+        bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = ILIOracle::LEN,
+        seeds = [ILI_ORACLE_SEED],
+        bump
+    )]
+    pub ili_oracle: Account<'info, ILIOracle>,
+    
+    #[account(
+        constraint = reserve_vault.owner == anchor_spl::token::ID @ ICBError::InvalidReserveVault,
+        constraint = reserve_vault.mint == icu_mint.key() @ ICBError::InvalidICUMint
+    )]
+    pub reserve_vault: Account<'info, TokenAccount>,
+    
+    pub icu_mint: Account<'info, Mint>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// initialize followed by set_reserve_vault in one instruction, so the
+/// protocol is never observable half-configured
+pub fn initialize_full(
+    ctx: Context<InitializeFull>,
+    epoch_duration: i64,
+    mint_burn_cap_bps: u16,
+    stability_fee_bps: u16,
+    vhr_threshold: u16,
+    ili_decimals: u8,
+) -> Result<()> {
+    let config = InitConfig { epoch_duration, mint_burn_cap_bps, stability_fee_bps, vhr_threshold, ili_decimals };
+    let authority = ctx.accounts.authority.key();
+    let ili_oracle_key = ctx.accounts.ili_oracle.key();
+    let global_state = &mut ctx.accounts.global_state;
+    let ili_oracle = &mut ctx.accounts.ili_oracle;
+    let clock = Clock::get()?;
+    
+    config.apply(global_state, ili_oracle, authority, ili_oracle_key, clock.slot)?;
+    global_state.bump = ctx.bumps.global_state;
+    ili_oracle.bump = ctx.bumps.ili_oracle;
+    
+    assign_reserve_vault(global_state, ctx.accounts.reserve_vault.key(), ctx.accounts.icu_mint.key())?;
+    
+    msg!("ARS Protocol initialized");
+    msg!("Authority: {}", global_state.authority);
+    msg!("Reserve vault: {}", global_state.reserve_vault);
+    msg!("ICU mint: {}", global_state.icu_mint);
     
     Ok(())
 }
@@ -96,26 +225,131 @@ pub struct SetReserveVault<'info> {
     
     pub icu_mint: Account<'info, Mint>,
     
-    #[account(
-        constraint = global_state.authority == authority.key() @ ICBError::Unauthorized
-    )]
+    #[account(address = global_state.authority @ ICBError::Unauthorized)]
     pub authority: Signer<'info>,
 }
 
 pub fn set_reserve_vault(ctx: Context<SetReserveVault>) -> Result<()> {
-    let global_state = &mut ctx.accounts.global_state;
+    assign_reserve_vault(
+        &mut ctx.accounts.global_state,
+        ctx.accounts.reserve_vault.key(),
+        ctx.accounts.icu_mint.key(),
+    )?;
+    
+    msg!("Reserve vault set: {}", ctx.accounts.reserve_vault.key());
+    msg!("ARU mint set: {}", ctx.accounts.icu_mint.key());
     
+    Ok(())
+}
+
+/// Record the reserve vault and ICU mint. The token-level checks (vault
+/// owned by the token program, holding `icu_mint`) are account constraints
+/// shared by SetReserveVault and InitializeFull.
+pub fn assign_reserve_vault(global_state: &mut GlobalState, reserve_vault: Pubkey, icu_mint: Pubkey) -> Result<()> {
     // FIX #10: Ensure vault can only be set once
     require!(
         global_state.reserve_vault == Pubkey::default(),
         ICBError::InvalidReserveVault
     );
     
-    global_state.reserve_vault = ctx.accounts.reserve_vault.key();
-    global_state.icu_mint = ctx.accounts.icu_mint.key();
+    global_state.reserve_vault = reserve_vault;
+    global_state.icu_mint = icu_mint;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::Discriminator;
     
-    msg!("Reserve vault set: {}", ctx.accounts.reserve_vault.key());
-    msg!("ARU mint set: {}", ctx.accounts.icu_mint.key());
+    #[test]
+    fn test_vhr_threshold_bounds() {
+        assert!(ICRatio::new(MIN_VHR_THRESHOLD).is_ok());
+        assert!(ICRatio::new(15000).is_ok());
+        assert!(ICRatio::new(MAX_VHR_THRESHOLD).is_ok());
+        
+        // 100% leaves no safety margin
+        assert_eq!(
+            ICRatio::new(10000).unwrap_err(),
+            ICBError::InvalidVHRThreshold.into()
+        );
+        assert!(ICRatio::new(MIN_VHR_THRESHOLD - 1).is_err());
+        assert!(ICRatio::new(MAX_VHR_THRESHOLD + 1).is_err());
+    }
     
-    Ok(())
+    fn config() -> InitConfig {
+        InitConfig {
+            epoch_duration: 86_400,
+            mint_burn_cap_bps: 200,
+            stability_fee_bps: 10,
+            vhr_threshold: 15_000,
+            ili_decimals: 6,
+        }
+    }
+    
+    fn empty_oracle() -> ILIOracle {
+        let mut data = ILIOracle::DISCRIMINATOR.to_vec();
+        data.resize(ILIOracle::LEN, 0);
+        ILIOracle::try_deserialize(&mut &data[..]).unwrap()
+    }
+    
+    #[test]
+    fn test_full_init_leaves_no_default_keys() {
+        let (authority, oracle_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (reserve_vault, icu_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut global_state = GlobalState::default();
+        let mut ili_oracle = empty_oracle();
+        
+        config().apply(&mut global_state, &mut ili_oracle, authority, oracle_key, 1).unwrap();
+        
+        // The two-step path is half-configured at this point
+        assert_eq!(global_state.reserve_vault, Pubkey::default());
+        assert_eq!(global_state.icu_mint, Pubkey::default());
+        
+        assign_reserve_vault(&mut global_state, reserve_vault, icu_mint).unwrap();
+        
+        for key in [
+            global_state.authority,
+            global_state.ili_oracle,
+            global_state.reserve_vault,
+            global_state.icu_mint,
+            ili_oracle.authority,
+        ] {
+            assert_ne!(key, Pubkey::default());
+        }
+        assert_eq!(global_state.reserve_vault, reserve_vault);
+        assert_eq!(global_state.icu_mint, icu_mint);
+        assert_eq!(global_state.version, GlobalState::CURRENT_VERSION);
+    }
+    
+    #[test]
+    fn test_reserve_vault_assigned_once() {
+        let mut global_state = GlobalState::default();
+        assign_reserve_vault(&mut global_state, Pubkey::new_unique(), Pubkey::new_unique()).unwrap();
+        
+        // set_reserve_vault after initialize_full is rejected like a second set
+        assert_eq!(
+            assign_reserve_vault(&mut global_state, Pubkey::new_unique(), Pubkey::new_unique()).unwrap_err(),
+            ICBError::InvalidReserveVault.into()
+        );
+    }
+    
+    #[test]
+    fn test_init_config_validated() {
+        let mut global_state = GlobalState::default();
+        let mut ili_oracle = empty_oracle();
+        let authority = Pubkey::new_unique();
+        
+        let zero_epoch = InitConfig { epoch_duration: 0, ..config() };
+        assert_eq!(
+            zero_epoch.apply(&mut global_state, &mut ili_oracle, authority, Pubkey::new_unique(), 1).unwrap_err(),
+            ICBError::InvalidEpochDuration.into()
+        );
+        
+        let low_vhr = InitConfig { vhr_threshold: 10_000, ..config() };
+        assert_eq!(
+            low_vhr.apply(&mut global_state, &mut ili_oracle, authority, Pubkey::new_unique(), 1).unwrap_err(),
+            ICBError::InvalidVHRThreshold.into()
+        );
+    }
 }
@@ -0,0 +1,351 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::ICBError;
+
+#[derive(Accounts)]
+pub struct MigrateGlobalState<'info> {
+    /// CHECK: Loaded manually - an old-layout account is too small to
+    /// deserialize as the current GlobalState until it has been reallocated
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump,
+        owner = crate::ID
+    )]
+    pub global_state: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateGlobalState>) -> Result<()> {
+    let global_state_info = ctx.accounts.global_state.to_account_info();
+    let new_len = GlobalState::LEN;
+    
+    // Grow the account to the current layout, charging the extra rent to the authority
+    if global_state_info.data_len() < new_len {
+        let rent = Rent::get()?;
+        let lamports_needed = rent
+            .minimum_balance(new_len)
+            .saturating_sub(global_state_info.lamports());
+        
+        if lamports_needed > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: global_state_info.clone(),
+                    },
+                ),
+                lamports_needed,
+            )?;
+        }
+        
+        // New bytes are zeroed so appended fields start from a known state
+        global_state_info.realloc(new_len, true)?;
+    }
+    
+    let mut data = global_state_info.try_borrow_mut_data()?;
+    let from_version = migrate_account_data(&mut data[..], &ctx.accounts.authority.key())?;
+    
+    msg!("GlobalState migrated from version {} to {}", from_version, GlobalState::CURRENT_VERSION);
+    msg!("Account size: {} bytes", new_len);
+    
+    Ok(())
+}
+
+/// Offset of `GlobalState::version`: the original 176-byte layout plus
+/// agent_action_cooldown, both fixed-size
+const VERSION_OFFSET: usize = 184;
+
+/// Upgrade serialized GlobalState data (already sized to GlobalState::LEN,
+/// with any appended bytes zeroed) to the current layout version.
+/// Returns the version the account was migrated from.
+pub fn migrate_account_data(data: &mut [u8], authority: &Pubkey) -> Result<u8> {
+    let from_version = stored_version(data);
+    let mut global_state = deserialize_version(data, from_version)?;
+    
+    require_keys_eq!(global_state.authority, *authority, ICBError::Unauthorized);
+    
+    // Each version can only be migrated once
+    require!(
+        from_version < GlobalState::CURRENT_VERSION,
+        ICBError::AlreadyMigrated
+    );
+    
+    global_state.migrate_from(from_version);
+    
+    // Borsh vecs are compact, so the stored payload may be followed by stale
+    // bytes (e.g. from a shrunk active_proposals); none may outlive the rewrite
+    data.fill(0);
+    let mut writer: &mut [u8] = data;
+    global_state.try_serialize(&mut writer)?;
+    
+    Ok(from_version)
+}
+
+/// Layout version of stored GlobalState data. The unversioned layout ends
+/// before the version byte, which then reads as 0 from the zeroed tail.
+pub fn stored_version(data: &[u8]) -> u8 {
+    data.get(VERSION_OFFSET).copied().unwrap_or(0)
+}
+
+/// Read GlobalState data stored in layout `version`. Only the fields that
+/// layout had are decoded; later ones stay at their defaults for
+/// `migrate_from`, rather than being read from whatever bytes follow the
+/// stored payload.
+pub fn deserialize_version(data: &[u8], version: u8) -> Result<GlobalState> {
+    require!(
+        data.len() >= 8 && data[..8] == GlobalState::DISCRIMINATOR,
+        ICBError::InvalidAccountOwner
+    );
+    
+    let mut stored = &data[8..];
+    let buf = &mut stored;
+    let mut global_state = GlobalState {
+        authority: read(buf)?,
+        ili_oracle: read(buf)?,
+        reserve_vault: read(buf)?,
+        icu_mint: read(buf)?,
+        epoch_duration: read(buf)?,
+        mint_burn_cap_bps: read(buf)?,
+        stability_fee_bps: read(buf)?,
+        vhr_threshold: read(buf)?,
+        circuit_breaker_active: read(buf)?,
+        proposal_counter: read(buf)?,
+        circuit_breaker_requested_at: read(buf)?,
+        last_update_slot: read(buf)?,
+        bump: read(buf)?,
+        ..Default::default()
+    };
+    
+    // Fields in the order they were appended, each from the version that added it
+    if version >= 1 {
+        global_state.agent_action_cooldown = read(buf)?;
+        global_state.version = read(buf)?;
+    }
+    if version >= 2 {
+        global_state.breaker_exempt_policies = read(buf)?;
+    }
+    if version >= 3 {
+        global_state.active_proposals = read(buf)?;
+    }
+    if version >= 4 {
+        global_state.execution_max_oracle_age = read(buf)?;
+    }
+    if version >= 5 {
+        global_state.finalization_grace_secs = read(buf)?;
+    }
+    if version >= 6 {
+        global_state.min_quorum_stake = read(buf)?;
+    }
+    if version >= 7 {
+        global_state.executor_allowlist = read(buf)?;
+    }
+    // Version 8 only moved proposal_counter off the reserved id
+    if version >= 9 {
+        global_state.oracle_layout_version = read(buf)?;
+    }
+    if version >= 10 {
+        global_state.min_unique_voters = read(buf)?;
+    }
+    if version >= 11 {
+        global_state.max_active_proposals = read(buf)?;
+    }
+    if version >= 12 {
+        global_state.guardian = read(buf)?;
+    }
+    if version >= 13 {
+        global_state.max_batch_size = read(buf)?;
+    }
+    if version >= 14 {
+        global_state.max_stale_secs = read(buf)?;
+    }
+    if version >= 15 {
+        global_state.contrarian_bonus_bps = read(buf)?;
+    }
+    if version >= 16 {
+        if version < 24 {
+            // Retired cosigner list; cosigners co-sign again once registered
+            // as oracle submitters
+            read::<Vec<Pubkey>>(buf)?;
+        }
+        global_state.large_ili_move_bps = read(buf)?;
+        global_state.large_move_quorum = read(buf)?;
+    }
+    if version >= 17 {
+        global_state.min_reward_payout = read(buf)?;
+    }
+    if version >= 18 {
+        global_state.last_deactivated_at = read(buf)?;
+        global_state.breaker_rerequest_cooldown = read(buf)?;
+    }
+    if version >= 19 {
+        global_state.max_signature_age = read(buf)?;
+    }
+    if version >= 20 {
+        global_state.turnout_ema = read(buf)?;
+        global_state.turnout_samples = read(buf)?;
+    }
+    if version >= 21 {
+        global_state.auto_recover = read(buf)?;
+    }
+    if version >= 22 {
+        global_state.proposal_deposit = read(buf)?;
+    }
+    if version >= 23 {
+        global_state.oracle_submitter_count = read(buf)?;
+        global_state.max_submitters = read(buf)?;
+    }
+    
+    Ok(global_state)
+}
+
+fn read<T: AnchorDeserialize>(buf: &mut &[u8]) -> Result<T> {
+    Ok(T::deserialize(buf)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    // Size of the original (unversioned) layout, before any appended fields
+    const LEGACY_LEN: usize = 176;
+    
+    fn legacy_account(authority: Pubkey) -> Vec<u8> {
+        let global_state = GlobalState {
+            authority,
+            epoch_duration: DEFAULT_EPOCH_DURATION,
+            mint_burn_cap_bps: 200,
+            vhr_threshold: ICRatio::new(15000).unwrap(),
+            proposal_counter: 7,
+            bump: 254,
+            ..Default::default()
+        };
+        
+        let mut data = Vec::new();
+        global_state.try_serialize(&mut data).unwrap();
+        
+        // Drop the appended fields, then realloc with a zeroed tail
+        data.truncate(LEGACY_LEN);
+        data.resize(GlobalState::LEN, 0);
+        data
+    }
+    
+    #[test]
+    fn test_migrate_legacy_account() {
+        let authority = Pubkey::new_unique();
+        let mut data = legacy_account(authority);
+        
+        assert_eq!(migrate_account_data(&mut data, &authority).unwrap(), 0);
+        
+        let migrated = GlobalState::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(migrated.version, GlobalState::CURRENT_VERSION);
+        assert_eq!(migrated.agent_action_cooldown, DEFAULT_AGENT_ACTION_COOLDOWN);
+        assert_eq!(migrated.breaker_exempt_policies, 0);
+        assert!(migrated.active_proposals.is_empty());
+        assert_eq!(migrated.execution_max_oracle_age, DEFAULT_EXECUTION_MAX_ORACLE_AGE);
+        assert_eq!(migrated.finalization_grace_secs, DEFAULT_FINALIZATION_GRACE_SECS);
+        assert_eq!(migrated.min_quorum_stake, DEFAULT_MIN_QUORUM_STAKE);
+        assert!(migrated.executor_allowlist.is_empty());
+        assert_eq!(migrated.oracle_layout_version, 0);
+        assert_eq!(migrated.min_unique_voters, DEFAULT_MIN_UNIQUE_VOTERS);
+        assert_eq!(migrated.max_active_proposals as usize, MAX_ACTIVE_PROPOSALS);
+        assert_eq!(migrated.guardian, None);
+        assert_eq!(migrated.max_batch_size as usize, MAX_ILI_BATCH_SIZE);
+        assert_eq!(migrated.max_stale_secs, DEFAULT_MAX_PROPOSAL_STALE_SECS);
+        assert_eq!(migrated.contrarian_bonus_bps, DEFAULT_CONTRARIAN_BONUS_BPS);
+        assert_eq!(migrated.large_ili_move_bps, DEFAULT_LARGE_ILI_MOVE_BPS);
+        assert_eq!(migrated.large_move_quorum, DEFAULT_LARGE_MOVE_QUORUM);
+        assert_eq!(migrated.min_reward_payout, DEFAULT_MIN_REWARD_PAYOUT);
+        assert_eq!(migrated.last_deactivated_at, 0);
+        assert_eq!(migrated.breaker_rerequest_cooldown, DEFAULT_BREAKER_REREQUEST_COOLDOWN);
+        assert_eq!(migrated.max_signature_age, MAX_SIGNATURE_AGE);
+        assert_eq!(migrated.turnout_ema, 0);
+        assert_eq!(migrated.turnout_samples, 0);
+        assert!(!migrated.auto_recover);
+        assert_eq!(migrated.proposal_deposit, 0);
+        assert_eq!(migrated.oracle_submitter_count, 0);
+        assert_eq!(migrated.max_submitters, DEFAULT_MAX_SUBMITTERS);
+        
+        // Existing fields survive the migration
+        assert_eq!(migrated.authority, authority);
+        assert_eq!(migrated.epoch_duration, DEFAULT_EPOCH_DURATION);
+        assert_eq!(migrated.mint_burn_cap_bps, 200);
+        assert_eq!(migrated.vhr_threshold.bps(), 15000);
+        assert_eq!(migrated.proposal_counter, 7);
+        assert_eq!(migrated.bump, 254);
+    }
+    
+    #[test]
+    fn test_migrate_ignores_bytes_left_by_shrunk_vec() {
+        let authority = Pubkey::new_unique();
+        let stored = |active_proposals: Vec<u64>| {
+            let count = active_proposals.len();
+            let global_state = GlobalState {
+                authority,
+                version: 3,
+                active_proposals,
+                execution_max_oracle_age: i64::MAX,
+                guardian: Some(Pubkey::new_unique()),
+                proposal_deposit: u64::MAX,
+                bump: 254,
+                ..Default::default()
+            };
+            let mut data = Vec::new();
+            global_state.try_serialize(&mut data).unwrap();
+            // v3 layout ends with active_proposals
+            data.truncate(8 + 168 + 8 + 1 + 1 + 4 + 8 * count);
+            data
+        };
+        
+        // Written with ten active proposals, then rewritten in place after
+        // eight were untracked, leaving the old payload's tail behind it
+        let mut data = vec![0u8; GlobalState::LEN];
+        let long = stored((1..=10).collect());
+        data[..long.len()].copy_from_slice(&long);
+        let short = stored(vec![1, 2]);
+        data[..short.len()].copy_from_slice(&short);
+        assert!(data[short.len()..long.len()].iter().any(|byte| *byte != 0));
+        
+        assert_eq!(migrate_account_data(&mut data, &authority).unwrap(), 3);
+        
+        let migrated = GlobalState::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(migrated.version, GlobalState::CURRENT_VERSION);
+        assert_eq!(migrated.active_proposals, vec![1, 2]);
+        assert_eq!(migrated.execution_max_oracle_age, DEFAULT_EXECUTION_MAX_ORACLE_AGE);
+        assert_eq!(migrated.guardian, None);
+        assert_eq!(migrated.proposal_deposit, 0);
+        assert_eq!(migrated.oracle_submitter_count, 0);
+        assert_eq!(migrated.max_submitters, DEFAULT_MAX_SUBMITTERS);
+        
+        // Nothing stale remains after the rewritten payload
+        let mut payload = Vec::new();
+        migrated.try_serialize(&mut payload).unwrap();
+        assert!(data[payload.len()..].iter().all(|byte| *byte == 0));
+    }
+    
+    #[test]
+    fn test_migrate_runs_once_per_version() {
+        let authority = Pubkey::new_unique();
+        let mut data = legacy_account(authority);
+        
+        migrate_account_data(&mut data, &authority).unwrap();
+        assert_eq!(
+            migrate_account_data(&mut data, &authority).unwrap_err(),
+            ICBError::AlreadyMigrated.into()
+        );
+    }
+    
+    #[test]
+    fn test_migrate_requires_authority() {
+        let mut data = legacy_account(Pubkey::new_unique());
+        assert!(migrate_account_data(&mut data, &Pubkey::new_unique()).is_err());
+    }
+}
@@ -0,0 +1,209 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::ICBError;
+
+#[derive(Accounts)]
+pub struct MigrateOracle<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ICBError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    /// CHECK: Loaded manually - an old-layout oracle cannot be deserialized
+    /// as the current ILIOracle
+    #[account(
+        mut,
+        seeds = [ILI_ORACLE_SEED],
+        bump,
+        owner = crate::ID
+    )]
+    pub ili_oracle: UncheckedAccount<'info>,
+    
+    /// Pays for any extra rent the new layout needs
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateOracle>) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let ili_oracle_info = ctx.accounts.ili_oracle.to_account_info();
+    
+    let from_version = global_state.oracle_layout_version;
+    let migrated = {
+        let data = ili_oracle_info.try_borrow_data()?;
+        migrate_oracle_data(&data[..], from_version)?
+    };
+    let new_len = ILIOracle::space(migrated.history_capacity as usize);
+    
+    // Top up rent for the new layout, charging the authority
+    let rent = Rent::get()?;
+    let lamports_needed = rent
+        .minimum_balance(new_len)
+        .saturating_sub(ili_oracle_info.lamports());
+    if lamports_needed > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: ili_oracle_info.clone(),
+                },
+            ),
+            lamports_needed,
+        )?;
+    }
+    
+    // Close out the old layout, then reinitialize the account in the new one
+    ili_oracle_info.realloc(new_len, true)?;
+    let mut data = ili_oracle_info.try_borrow_mut_data()?;
+    data.fill(0);
+    let mut writer: &mut [u8] = &mut data[..];
+    migrated.try_serialize(&mut writer)?;
+    
+    global_state.oracle_layout_version = ILIOracle::LAYOUT_VERSION;
+    
+    msg!("ILI oracle migrated from layout {} to {}", from_version, ILIOracle::LAYOUT_VERSION);
+    msg!("Snapshots carried over: {}", migrated.history.len());
+    
+    Ok(())
+}
+
+/// Read an oracle stored in layout `from_version` and return it in the
+/// current layout. Fails if the oracle is already current so the migration
+/// can only run once.
+pub fn migrate_oracle_data(data: &[u8], from_version: u8) -> Result<ILIOracle> {
+    require!(
+        from_version < ILIOracle::LAYOUT_VERSION,
+        ICBError::AlreadyMigrated
+    );
+    require!(
+        data.len() >= 8 && data[..8] == ILIOracle::DISCRIMINATOR,
+        ICBError::InvalidAccountOwner
+    );
+    
+    let mut stored = &data[8..];
+    Ok(match from_version {
+        0 | 1 => LegacyILIOracle::deserialize(&mut stored)?.into(),
+        _ => ILIOracleV2::deserialize(&mut stored)?.into(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn legacy_account() -> Vec<u8> {
+        let legacy = LegacyILIOracle {
+            authority: Pubkey::new_unique(),
+            current_ili: 5_250_000,
+            last_update: 1_700_000_600,
+            update_interval: DEFAULT_ILI_UPDATE_INTERVAL,
+            snapshot_count: 3,
+            last_update_slot: 42,
+            degraded: true,
+            history_capacity: 4,
+            history_head: 3,
+            history: (0..3)
+                .map(|i| ILISnapshot {
+                    timestamp: 1_700_000_000 + i * 300,
+                    ili_value: 5_000_000 + i as u64 * 125_000,
+                    avg_yield: 500,
+                    volatility: 200,
+                    tvl: 1_000_000,
+                })
+                .collect(),
+            bump: 253,
+        };
+        
+        let mut data = ILIOracle::DISCRIMINATOR.to_vec();
+        legacy.serialize(&mut data).unwrap();
+        data.resize(ILIOracle::space(4), 0);
+        data
+    }
+    
+    #[test]
+    fn test_migrate_oracle_preserves_history() {
+        let migrated = migrate_oracle_data(&legacy_account(), 0).unwrap();
+        
+        assert_eq!(migrated.current_ili, 5_250_000);
+        assert_eq!(migrated.last_update, 1_700_000_600);
+        assert_eq!(migrated.snapshot_count, 3);
+        assert!(migrated.degraded);
+        assert_eq!(migrated.ili_decimals, DEFAULT_ILI_DECIMALS);
+        assert_eq!(migrated.min_ili_value, DEFAULT_MIN_ILI_VALUE);
+        assert_eq!(migrated.history_capacity, 4);
+        assert_eq!(migrated.history_head, 3);
+        assert_eq!(migrated.bump, 253);
+        
+        let history: Vec<(i64, u64)> = migrated.history
+            .iter()
+            .map(|s| (s.timestamp, s.ili_value))
+            .collect();
+        assert_eq!(
+            history,
+            vec![
+                (1_700_000_000, 5_000_000),
+                (1_700_000_300, 5_125_000),
+                (1_700_000_600, 5_250_000),
+            ]
+        );
+        
+        // The result round-trips through the current layout
+        let mut data = Vec::new();
+        migrated.try_serialize(&mut data).unwrap();
+        assert!(data.len() <= ILIOracle::space(4));
+        let reloaded = ILIOracle::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(reloaded.current_ili, 5_250_000);
+        assert_eq!(reloaded.history.len(), 3);
+    }
+    
+    #[test]
+    fn test_migrate_oracle_from_layout_2() {
+        let v2 = ILIOracleV2 {
+            authority: Pubkey::new_unique(),
+            current_ili: 5_250,
+            ili_decimals: 3,
+            last_update: 1_700_000_600,
+            update_interval: DEFAULT_ILI_UPDATE_INTERVAL,
+            snapshot_count: 1,
+            last_update_slot: 42,
+            degraded: false,
+            history_capacity: 4,
+            history_head: 1,
+            history: Vec::new(),
+            bump: 253,
+        };
+        let mut data = ILIOracle::DISCRIMINATOR.to_vec();
+        v2.serialize(&mut data).unwrap();
+        
+        let migrated = migrate_oracle_data(&data, 2).unwrap();
+        assert_eq!(migrated.current_ili, 5_250);
+        assert_eq!(migrated.ili_decimals, 3);
+        assert_eq!(migrated.min_ili_value, DEFAULT_MIN_ILI_VALUE);
+        assert_eq!(migrated.history_head, 1);
+        assert_eq!(migrated.bump, 253);
+    }
+    
+    #[test]
+    fn test_migrate_oracle_runs_once() {
+        assert_eq!(
+            migrate_oracle_data(&legacy_account(), ILIOracle::LAYOUT_VERSION).err(),
+            Some(ICBError::AlreadyMigrated.into())
+        );
+    }
+    
+    #[test]
+    fn test_migrate_oracle_rejects_foreign_account() {
+        let mut data = legacy_account();
+        data[0] ^= 0xff;
+        assert!(migrate_oracle_data(&data, 0).is_err());
+    }
+}
@@ -1,15 +1,75 @@
 pub mod initialize;
 pub mod update_ili;
+pub mod update_ili_batch;
+pub mod resize_ili_history;
 pub mod query_ili;
+pub mod query_next_action;
+pub mod query_proposal_status;
+pub mod query_proposal_history;
+pub mod query_agent_summary;
+pub mod query_turnout;
+pub mod query_time_remaining;
 pub mod create_proposal;
+pub mod preallocate_proposal;
+pub mod reconcile_counter;
 pub mod vote_on_proposal;
+pub mod stake_and_vote;
+pub mod settle_deposit;
+pub mod query_pending_parameters;
+pub mod close_vote_record;
+pub mod claim_rewards;
+pub mod sweep_closed;
 pub mod execute_proposal;
+pub mod proposal_history;
+pub mod extend_proposal;
+pub mod executors;
+pub mod oracle_submitter;
+pub mod guardian;
+pub mod force_expire;
 pub mod circuit_breaker;
+pub mod set_oracle_degraded;
+pub mod set_min_ili_value;
+pub mod update_parameters;
+pub mod migrate_global_state;
+pub mod migrate_oracle;
+pub mod build_signing_payload;
+pub mod debug_verify_auth;
+pub mod register_agent;
 
 pub use initialize::*;
 pub use update_ili::*;
+pub use update_ili_batch::*;
+pub use resize_ili_history::*;
 pub use query_ili::*;
+pub use query_next_action::*;
+pub use query_proposal_status::*;
+pub use query_proposal_history::*;
+pub use query_agent_summary::*;
+pub use query_turnout::*;
+pub use query_time_remaining::*;
 pub use create_proposal::*;
+pub use preallocate_proposal::*;
+pub use reconcile_counter::*;
 pub use vote_on_proposal::*;
+pub use stake_and_vote::*;
+pub use settle_deposit::*;
+pub use query_pending_parameters::*;
+pub use close_vote_record::*;
+pub use claim_rewards::*;
+pub use sweep_closed::*;
 pub use execute_proposal::*;
+pub use proposal_history::*;
+pub use extend_proposal::*;
+pub use executors::*;
+pub use oracle_submitter::*;
+pub use guardian::*;
+pub use force_expire::*;
 pub use circuit_breaker::*;
+pub use set_oracle_degraded::*;
+pub use set_min_ili_value::*;
+pub use update_parameters::*;
+pub use migrate_global_state::*;
+pub use migrate_oracle::*;
+pub use build_signing_payload::*;
+pub use debug_verify_auth::*;
+pub use register_agent::*;
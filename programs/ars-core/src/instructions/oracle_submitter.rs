@@ -0,0 +1,141 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::ICBError;
+
+#[derive(Accounts)]
+#[instruction(submitter: Pubkey)]
+pub struct AddOracleSubmitter<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ICBError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        seeds = [ILI_ORACLE_SEED],
+        bump = ili_oracle.bump
+    )]
+    pub ili_oracle: Account<'info, ILIOracle>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = OracleSubmitter::LEN,
+        seeds = [ORACLE_SUBMITTER_SEED, submitter.as_ref()],
+        bump
+    )]
+    pub oracle_submitter: Account<'info, OracleSubmitter>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn add_oracle_submitter(
+    ctx: Context<AddOracleSubmitter>,
+    submitter: Pubkey,
+    phase_offset: i64,
+) -> Result<()> {
+    check_phase_offset(phase_offset, ctx.accounts.ili_oracle.update_interval)?;
+    
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.register_submitter()?;
+    
+    let oracle_submitter = &mut ctx.accounts.oracle_submitter;
+    oracle_submitter.submitter = submitter;
+    oracle_submitter.phase_offset = phase_offset;
+    oracle_submitter.last_submission = 0;
+    oracle_submitter.bump = ctx.bumps.oracle_submitter;
+    
+    msg!("Oracle submitter added: {}", submitter);
+    msg!("Oracle submitters: {} / {}", global_state.oracle_submitter_count, global_state.max_submitters);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RemoveOracleSubmitter<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ICBError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        mut,
+        close = authority,
+        seeds = [ORACLE_SUBMITTER_SEED, oracle_submitter.submitter.as_ref()],
+        bump = oracle_submitter.bump
+    )]
+    pub oracle_submitter: Account<'info, OracleSubmitter>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn remove_oracle_submitter(ctx: Context<RemoveOracleSubmitter>) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.unregister_submitter();
+    
+    msg!("Oracle submitter removed: {}", ctx.accounts.oracle_submitter.submitter);
+    msg!("Oracle submitters: {} / {}", global_state.oracle_submitter_count, global_state.max_submitters);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetSubmitterPhaseOffset<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ICBError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        seeds = [ILI_ORACLE_SEED],
+        bump = ili_oracle.bump
+    )]
+    pub ili_oracle: Account<'info, ILIOracle>,
+    
+    #[account(
+        mut,
+        seeds = [ORACLE_SUBMITTER_SEED, oracle_submitter.submitter.as_ref()],
+        bump = oracle_submitter.bump
+    )]
+    pub oracle_submitter: Account<'info, OracleSubmitter>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Move a registered submitter's update windows; new submitters go through
+/// add_oracle_submitter so they count against max_submitters
+pub fn set_submitter_phase_offset(
+    ctx: Context<SetSubmitterPhaseOffset>,
+    phase_offset: i64,
+) -> Result<()> {
+    check_phase_offset(phase_offset, ctx.accounts.ili_oracle.update_interval)?;
+    
+    // last_submission carries over when an existing schedule is changed
+    let oracle_submitter = &mut ctx.accounts.oracle_submitter;
+    oracle_submitter.phase_offset = phase_offset;
+    
+    msg!("Submitter {} phase offset: {} seconds", oracle_submitter.submitter, phase_offset);
+    
+    Ok(())
+}
+
+/// A phase offset must fall within one update interval
+pub fn check_phase_offset(phase_offset: i64, update_interval: i64) -> Result<()> {
+    require!(
+        phase_offset >= 0 && phase_offset < update_interval,
+        ICBError::InvalidParameter
+    );
+    Ok(())
+}
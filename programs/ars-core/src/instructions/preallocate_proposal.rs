@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct PreallocateProposal<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    /// The account create_proposal will fill next. Retrying is a no-op once
+    /// it exists.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = PolicyProposal::LEN,
+        seeds = [PROPOSAL_SEED, &global_state.proposal_counter.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, PolicyProposal>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<PreallocateProposal>) -> Result<()> {
+    // The preallocating payer, not the eventual proposer, is refunded on close
+    ctx.accounts.proposal.record_rent_payer(ctx.accounts.payer.key());
+    
+    msg!("Proposal account preallocated: {}", ctx.accounts.proposal.key());
+    msg!("Next proposal id: {}", ctx.accounts.global_state.proposal_counter);
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::Discriminator;
+    use crate::errors::ICBError;
+    use crate::instructions::create_proposal::check_unfilled;
+    
+    #[test]
+    fn test_two_phase_creation() {
+        // Phase 1: the account exists with only its discriminator written
+        let mut data = PolicyProposal::DISCRIMINATOR.to_vec();
+        data.resize(PolicyProposal::LEN, 0);
+        let mut proposal = PolicyProposal::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(proposal.id, NO_PROPOSAL_ID);
+        
+        // The keeper paying for it is recorded as the rent payer
+        let keeper = Pubkey::new_unique();
+        proposal.record_rent_payer(keeper);
+        
+        // Phase 2: create_proposal may fill it once
+        assert!(check_unfilled(&proposal).is_ok());
+        proposal.id = FIRST_PROPOSAL_ID;
+        let proposer = Pubkey::new_unique();
+        proposal.proposer = proposer;
+        proposal.record_rent_payer(proposer);
+        
+        // The keeper, not the proposer, is owed the rent on close
+        assert_eq!(proposal.rent_payer, keeper);
+        assert_eq!(proposal.rent_recipient(), keeper);
+        
+        // A second fill of the same account is rejected
+        assert_eq!(
+            check_unfilled(&proposal).unwrap_err(),
+            ICBError::ProposalAlreadyExists.into()
+        );
+    }
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::ICBError;
+
+#[derive(Accounts)]
+pub struct InitializeProposalHistory<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ICBError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = ProposalHistory::LEN,
+        seeds = [PROPOSAL_HISTORY_SEED],
+        bump
+    )]
+    pub proposal_history: Account<'info, ProposalHistory>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_proposal_history(ctx: Context<InitializeProposalHistory>) -> Result<()> {
+    let proposal_history = &mut ctx.accounts.proposal_history;
+    
+    proposal_history.head = 0;
+    proposal_history.total_recorded = 0;
+    proposal_history.outcomes = Vec::new();
+    proposal_history.bump = ctx.bumps.proposal_history;
+    
+    msg!("Proposal history initialized ({} outcomes)", ProposalHistory::CAPACITY);
+    
+    Ok(())
+}
@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::ICBError;
+
+/// One view of an agent's registry stats, nonce and open votes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct AgentSummary {
+    pub agent: Pubkey,
+    pub reputation_score: u32,
+    pub total_transactions: u64,
+    pub total_volume: u64,
+    pub last_active: i64,
+    pub nonce: u64,                 // 0 until the agent has an AgentState
+    pub has_agent_state: bool,
+    pub active_votes: u32,          // Given vote records not yet settled by claim_rewards
+}
+
+/// The agent's vote records to count are passed as remaining accounts.
+#[derive(Accounts)]
+pub struct QueryAgentSummary<'info> {
+    #[account(
+        seeds = [AGENT_SEED, agent_registry.agent_pubkey.as_ref()],
+        bump = agent_registry.bump
+    )]
+    pub agent_registry: Account<'info, AgentRegistry>,
+    
+    /// CHECK: Created on the agent's first proposal; may not exist yet
+    #[account(
+        seeds = [AGENT_STATE_SEED, agent_registry.agent_pubkey.as_ref()],
+        bump
+    )]
+    pub agent_state: UncheckedAccount<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, QueryAgentSummary<'info>>,
+) -> Result<AgentSummary> {
+    let agent_state_info = ctx.accounts.agent_state.to_account_info();
+    let agent_state = if agent_state_info.data_is_empty() {
+        None
+    } else {
+        require_keys_eq!(*agent_state_info.owner, crate::ID, ICBError::InvalidAccountOwner);
+        let data = agent_state_info.try_borrow_data()?;
+        Some(AgentState::try_deserialize(&mut &data[..])?)
+    };
+    
+    let mut vote_records = Vec::with_capacity(ctx.remaining_accounts.len());
+    for info in ctx.remaining_accounts.iter() {
+        require_keys_eq!(*info.owner, crate::ID, ICBError::Unauthorized);
+        let data = info.try_borrow_data()?;
+        vote_records.push(VoteRecord::try_deserialize(&mut &data[..])?);
+    }
+    
+    let summary = agent_summary(&ctx.accounts.agent_registry, agent_state.as_ref(), &vote_records);
+    
+    msg!("Agent {}", summary.agent);
+    msg!("Reputation: {}, transactions: {}", summary.reputation_score, summary.total_transactions);
+    msg!("Active votes: {}", summary.active_votes);
+    
+    Ok(summary)
+}
+
+/// Combine the registry with the agent's AgentState, if it has one, and
+/// count its unsettled votes. Records of other agents are ignored.
+pub fn agent_summary(
+    registry: &AgentRegistry,
+    agent_state: Option<&AgentState>,
+    vote_records: &[VoteRecord],
+) -> AgentSummary {
+    let last_action = agent_state.map_or(0, |state| state.last_action_timestamp);
+    let active_votes = vote_records
+        .iter()
+        .filter(|record| record.agent == registry.agent_pubkey && !record.claimed)
+        .count();
+    
+    AgentSummary {
+        agent: registry.agent_pubkey,
+        reputation_score: registry.reputation_score,
+        total_transactions: registry.total_transactions,
+        total_volume: registry.total_volume,
+        last_active: registry.last_active.max(last_action),
+        nonce: agent_state.map_or(0, |state| state.nonce),
+        has_agent_state: agent_state.is_some(),
+        active_votes: active_votes as u32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn registry(agent: Pubkey) -> AgentRegistry {
+        AgentRegistry {
+            agent_pubkey: agent,
+            agent_type: AgentType::PredictionAgent,
+            total_transactions: 12,
+            total_volume: 50_000,
+            reputation_score: 640,
+            registered_at: 1_000,
+            last_active: 9_000,
+            last_vote_signature: [0; 64],
+            bump: 255,
+        }
+    }
+    
+    fn vote_record(agent: Pubkey, claimed: bool) -> VoteRecord {
+        VoteRecord {
+            proposal: Pubkey::new_unique(),
+            agent,
+            stake_amount: 1_000,
+            prediction: true,
+            timestamp: 8_000,
+            claimed,
+            agent_signature: [1; 64],
+            contrarian: false,
+            voted_slot: 0,
+            bump: 255,
+        }
+    }
+    
+    #[test]
+    fn test_fully_populated_agent() {
+        let agent = Pubkey::new_unique();
+        let agent_state = AgentState {
+            agent_pubkey: agent,
+            nonce: 7,
+            last_action_timestamp: 9_500,
+            bump: 254,
+        };
+        let votes = [
+            vote_record(agent, false),
+            vote_record(agent, false),
+            vote_record(agent, true),
+            vote_record(Pubkey::new_unique(), false),
+        ];
+        
+        let summary = agent_summary(&registry(agent), Some(&agent_state), &votes);
+        assert_eq!(summary, AgentSummary {
+            agent,
+            reputation_score: 640,
+            total_transactions: 12,
+            total_volume: 50_000,
+            last_active: 9_500,
+            nonce: 7,
+            has_agent_state: true,
+            active_votes: 2,
+        });
+    }
+    
+    #[test]
+    fn test_agent_without_agent_state() {
+        let agent = Pubkey::new_unique();
+        
+        let summary = agent_summary(&registry(agent), None, &[]);
+        assert!(!summary.has_agent_state);
+        assert_eq!(summary.nonce, 0);
+        assert_eq!(summary.last_active, 9_000);
+        assert_eq!(summary.active_votes, 0);
+        assert_eq!(summary.reputation_score, 640);
+    }
+}
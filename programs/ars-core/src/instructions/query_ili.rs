@@ -14,8 +14,19 @@ pub struct QueryILI<'info> {
 pub fn handler(ctx: Context<QueryILI>) -> Result<u64> {
     let ili_oracle = &ctx.accounts.ili_oracle;
     
-    msg!("Current ILI: {}", ili_oracle.current_ili);
+    let (whole, fraction) = ili_oracle.to_display(ili_oracle.current_ili)?;
+    msg!(
+        "Current ILI: {} ({}.{:0width$})",
+        ili_oracle.current_ili,
+        whole,
+        fraction,
+        width = ili_oracle.ili_decimals as usize
+    );
     msg!("Last update: {}", ili_oracle.last_update);
     
+    if ili_oracle.is_stale(Clock::get()?.unix_timestamp) {
+        msg!("WARNING: ILI is stale (max age {} seconds)", ili_oracle.max_staleness());
+    }
+    
     Ok(ili_oracle.current_ili)
 }
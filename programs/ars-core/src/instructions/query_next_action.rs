@@ -0,0 +1,195 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::ICBError;
+use crate::utils::execution_ready_at;
+
+/// Kind of keeper action that is due
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum NextActionKind {
+    FinalizeProposal,
+    ExecuteProposal,
+    ActivateCircuitBreaker,
+    EpochRollover,
+}
+
+/// Earliest pending action. A timestamp in the past means it is due now.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct NextAction {
+    pub kind: NextActionKind,
+    pub timestamp: i64,
+    pub proposal_id: Option<u64>,
+}
+
+/// Proposals to consider are passed as remaining accounts, e.g. the ids in
+/// `global_state.active_proposals` plus any passed-but-unexecuted ones.
+#[derive(Accounts)]
+pub struct QueryNextAction<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, QueryNextAction<'info>>,
+    epoch_start_time: Option<i64>,
+) -> Result<Option<NextAction>> {
+    let mut proposals = Vec::with_capacity(ctx.remaining_accounts.len());
+    for info in ctx.remaining_accounts.iter() {
+        require_keys_eq!(*info.owner, crate::ID, ICBError::Unauthorized);
+        let data = info.try_borrow_data()?;
+        proposals.push(PolicyProposal::try_deserialize(&mut &data[..])?);
+    }
+    
+    let next = next_action(&ctx.accounts.global_state, &proposals, epoch_start_time)?;
+    
+    match &next {
+        Some(action) => msg!("Next action: {:?} at {}", action.kind, action.timestamp),
+        None => msg!("No pending actions"),
+    }
+    
+    Ok(next)
+}
+
+/// Find the earliest deadline among proposal finalization and execution,
+/// circuit breaker activation and (when `epoch_start_time` is known, e.g.
+/// from the token program's mint state) the next epoch rollover
+pub fn next_action(
+    global_state: &GlobalState,
+    proposals: &[PolicyProposal],
+    epoch_start_time: Option<i64>,
+) -> Result<Option<NextAction>> {
+    let mut candidates = Vec::new();
+    
+    for proposal in proposals {
+        match proposal.status {
+            ProposalStatus::Active => candidates.push(NextAction {
+                kind: NextActionKind::FinalizeProposal,
+                timestamp: proposal.end_time
+                    .checked_add(global_state.finalization_grace_secs)
+                    .ok_or(ICBError::ArithmeticOverflow)?,
+                proposal_id: Some(proposal.id),
+            }),
+            ProposalStatus::Passed => candidates.push(NextAction {
+                kind: NextActionKind::ExecuteProposal,
+                timestamp: execution_ready_at(proposal.passed_at)?,
+                proposal_id: Some(proposal.id),
+            }),
+            _ => {}
+        }
+    }
+    
+    if !global_state.circuit_breaker_active && global_state.circuit_breaker_requested_at != 0 {
+        candidates.push(NextAction {
+            kind: NextActionKind::ActivateCircuitBreaker,
+            timestamp: global_state.circuit_breaker_requested_at
+                .checked_add(CIRCUIT_BREAKER_DELAY)
+                .ok_or(ICBError::ArithmeticOverflow)?,
+            proposal_id: None,
+        });
+    }
+    
+    if let Some(epoch_start_time) = epoch_start_time {
+        candidates.push(NextAction {
+            kind: NextActionKind::EpochRollover,
+            timestamp: epoch_start_time
+                .checked_add(global_state.epoch_duration)
+                .ok_or(ICBError::ArithmeticOverflow)?,
+            proposal_id: None,
+        });
+    }
+    
+    Ok(candidates.into_iter().min_by_key(|action| action.timestamp))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn proposal(id: u64, status: ProposalStatus, end_time: i64, passed_at: i64) -> PolicyProposal {
+        PolicyProposal {
+            id,
+            proposer: Pubkey::new_unique(),
+            end_time,
+            status,
+            passed_at,
+            bump: 255,
+            ..Default::default()
+        }
+    }
+    
+    fn global_state() -> GlobalState {
+        GlobalState {
+            epoch_duration: DEFAULT_EPOCH_DURATION,
+            finalization_grace_secs: 60,
+            ..Default::default()
+        }
+    }
+    
+    #[test]
+    fn test_earliest_deadline_wins() {
+        let mut global_state = global_state();
+        global_state.circuit_breaker_requested_at = 10_000;
+        
+        let proposals = [
+            proposal(1, ProposalStatus::Active, 50_000, 0),
+            proposal(2, ProposalStatus::Passed, 0, 20_000),
+            proposal(3, ProposalStatus::Active, 30_000, 0),
+            proposal(4, ProposalStatus::Executed, 1, 1),
+        ];
+        
+        // Proposal 3 finalizes at 30_060, before execution of 2 at 20_000 + delay
+        // and breaker activation at 10_000 + CIRCUIT_BREAKER_DELAY
+        let next = next_action(&global_state, &proposals, Some(0)).unwrap().unwrap();
+        assert_eq!(next, NextAction {
+            kind: NextActionKind::FinalizeProposal,
+            timestamp: 30_060,
+            proposal_id: Some(3),
+        });
+        
+        // Without proposals the epoch rollover comes before breaker activation
+        let next = next_action(&global_state, &[], Some(0)).unwrap().unwrap();
+        assert_eq!(next.kind, NextActionKind::EpochRollover);
+        assert_eq!(next.timestamp, DEFAULT_EPOCH_DURATION);
+        
+        let next = next_action(&global_state, &[], None).unwrap().unwrap();
+        assert_eq!(next.kind, NextActionKind::ActivateCircuitBreaker);
+        assert_eq!(next.timestamp, 10_000 + CIRCUIT_BREAKER_DELAY);
+    }
+    
+    #[test]
+    fn test_passed_proposal_execution_deadline() {
+        let proposals = [proposal(7, ProposalStatus::Passed, 0, 5_000)];
+        let next = next_action(&global_state(), &proposals, None).unwrap().unwrap();
+        
+        assert_eq!(next.kind, NextActionKind::ExecuteProposal);
+        assert_eq!(next.timestamp, 5_000 + EXECUTION_DELAY);
+        assert_eq!(next.proposal_id, Some(7));
+    }
+    
+    #[test]
+    fn test_epoch_rollover_overflow() {
+        let global_state = global_state();
+        
+        let last_start = i64::MAX - DEFAULT_EPOCH_DURATION;
+        let next = next_action(&global_state, &[], Some(last_start)).unwrap().unwrap();
+        assert_eq!(next.timestamp, i64::MAX);
+        
+        assert_eq!(
+            next_action(&global_state, &[], Some(last_start + 1)).unwrap_err(),
+            ICBError::ArithmeticOverflow.into()
+        );
+    }
+    
+    #[test]
+    fn test_nothing_pending() {
+        let mut global_state = global_state();
+        global_state.circuit_breaker_active = true;
+        global_state.circuit_breaker_requested_at = 10_000;
+        
+        let proposals = [proposal(1, ProposalStatus::Failed, 100, 0)];
+        assert_eq!(next_action(&global_state, &proposals, None).unwrap(), None);
+    }
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+/// Parameter changes in flight, as returned by query_pending_parameters
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PendingParametersView {
+    pub params: ParameterUpdate,
+    pub apply_after: i64,
+    pub applicable: bool,           // Timelock has passed; apply_staged_parameters will succeed
+}
+
+#[derive(Accounts)]
+pub struct QueryPendingParameters<'info> {
+    /// Absent until parameters are first staged
+    #[account(
+        seeds = [PENDING_PARAMETERS_SEED],
+        bump = pending_parameters.bump
+    )]
+    pub pending_parameters: Option<Account<'info, PendingParameters>>,
+}
+
+/// The staged changes, or None when nothing is pending
+pub fn pending_view(pending: Option<&PendingParameters>, now: i64) -> Option<PendingParametersView> {
+    pending.filter(|p| p.is_pending()).map(|p| PendingParametersView {
+        params: p.params.clone(),
+        apply_after: p.apply_after,
+        applicable: now >= p.apply_after,
+    })
+}
+
+pub fn handler(ctx: Context<QueryPendingParameters>) -> Result<Option<PendingParametersView>> {
+    let clock = Clock::get()?;
+    let view = pending_view(ctx.accounts.pending_parameters.as_deref(), clock.unix_timestamp);
+    
+    match &view {
+        Some(view) => {
+            msg!("Parameter changes pending, applicable after {}", view.apply_after);
+            msg!("Applicable now: {}", view.applicable);
+        }
+        None => msg!("No parameter changes pending"),
+    }
+    
+    Ok(view)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn pending(params: ParameterUpdate, apply_after: i64) -> PendingParameters {
+        PendingParameters { params, apply_after, staged_by: Pubkey::new_unique(), bump: 255 }
+    }
+    
+    #[test]
+    fn test_nothing_pending() {
+        assert!(pending_view(None, 1_000).is_none());
+        
+        // Account exists but its last staged change was already applied
+        let applied = pending(ParameterUpdate::default(), 0);
+        assert!(pending_view(Some(&applied), 1_000).is_none());
+    }
+    
+    #[test]
+    fn test_pending_changes_reported() {
+        let params = ParameterUpdate {
+            max_signature_age: Some(600),
+            vhr_threshold: Some(16_000),
+            ..Default::default()
+        };
+        let staged = pending(params, 5_000);
+        
+        let view = pending_view(Some(&staged), 4_999).unwrap();
+        assert_eq!(view.apply_after, 5_000);
+        assert!(!view.applicable);
+        assert_eq!(view.params.max_signature_age, Some(600));
+        assert_eq!(view.params.vhr_threshold, Some(16_000));
+        assert_eq!(view.params.min_quorum_stake, None);
+        
+        assert!(pending_view(Some(&staged), 5_000).unwrap().applicable);
+    }
+}
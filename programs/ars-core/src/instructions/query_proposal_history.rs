@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct QueryProposalHistory<'info> {
+    #[account(
+        seeds = [PROPOSAL_HISTORY_SEED],
+        bump = proposal_history.bump
+    )]
+    pub proposal_history: Account<'info, ProposalHistory>,
+}
+
+pub fn handler(ctx: Context<QueryProposalHistory>, start_id: u64, count: u16) -> Result<Vec<ProposalOutcome>> {
+    let outcomes = ctx.accounts.proposal_history.range(start_id, count)?;
+    
+    msg!("Proposal history from id {}: {} of {} ids retained", start_id, outcomes.len(), count);
+    
+    Ok(outcomes)
+}
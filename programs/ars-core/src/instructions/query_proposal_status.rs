@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::instructions::execute_proposal::{evaluate_tally, TallyOutcome};
+
+/// Live readout of where a proposal's vote stands
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct ProposalStatusView {
+    pub yes_stake: u64,
+    pub no_stake: u64,
+    pub yes_bps: u16,
+    pub quorum_met: bool,
+    pub min_quorum_stake: u64,
+    pub pass_threshold_bps: u16,
+    pub unique_voter_count: u32,
+    pub min_unique_voters: u32,
+    pub projected_outcome: TallyOutcome, // Outcome if finalized now
+}
+
+#[derive(Accounts)]
+pub struct QueryProposalStatus<'info> {
+    #[account(
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, PolicyProposal>,
+}
+
+pub fn handler(ctx: Context<QueryProposalStatus>) -> Result<ProposalStatusView> {
+    let view = proposal_status(&ctx.accounts.proposal)?;
+    
+    msg!("Proposal {}", ctx.accounts.proposal.id);
+    msg!("YES: {} ({} bps), NO: {}", view.yes_stake, view.yes_bps, view.no_stake);
+    msg!("Projected outcome: {:?}", view.projected_outcome);
+    
+    Ok(view)
+}
+
+/// Build the status view using the same tally as execute_proposal, judged
+/// by the rules the proposal was created under
+pub fn proposal_status(proposal: &PolicyProposal) -> Result<ProposalStatusView> {
+    let tally = evaluate_tally(proposal)?;
+    
+    Ok(ProposalStatusView {
+        yes_stake: proposal.yes_stake,
+        no_stake: proposal.no_stake,
+        yes_bps: tally.yes_bps,
+        quorum_met: tally.quorum_met,
+        min_quorum_stake: proposal.min_quorum_stake,
+        pass_threshold_bps: PASS_THRESHOLD_BPS,
+        unique_voter_count: proposal.unique_voter_count,
+        min_unique_voters: proposal.min_unique_voters,
+        projected_outcome: tally.outcome,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn proposal(yes_stake: u64, no_stake: u64, unique_voter_count: u32) -> PolicyProposal {
+        let mut proposal = PolicyProposal {
+            id: 1,
+            proposer: Pubkey::new_unique(),
+            end_time: MIN_VOTING_PERIOD,
+            yes_stake,
+            no_stake,
+            status: ProposalStatus::Active,
+            unique_voter_count,
+            bump: 255,
+            ..Default::default()
+        };
+        proposal.snapshot_rules(&global_state());
+        proposal
+    }
+    
+    fn global_state() -> GlobalState {
+        GlobalState {
+            min_quorum_stake: 1_000,
+            min_unique_voters: 2,
+            ..Default::default()
+        }
+    }
+    
+    #[test]
+    fn test_status_below_quorum() {
+        let view = proposal_status(&proposal(600, 300, 3)).unwrap();
+        
+        assert!(!view.quorum_met);
+        assert_eq!(view.yes_bps, 6666);
+        assert_eq!(view.projected_outcome, TallyOutcome::BelowQuorum);
+        
+        let view = proposal_status(&proposal(0, 0, 0)).unwrap();
+        assert_eq!(view.projected_outcome, TallyOutcome::NoVotes);
+    }
+    
+    #[test]
+    fn test_status_failing() {
+        // Exactly 50% does not pass
+        let view = proposal_status(&proposal(1_000, 1_000, 4)).unwrap();
+        assert!(view.quorum_met);
+        assert_eq!(view.yes_bps, PASS_THRESHOLD_BPS);
+        assert_eq!(view.projected_outcome, TallyOutcome::Rejected);
+        
+        // A majority from a single voter is held back by the voter minimum
+        let view = proposal_status(&proposal(5_000, 0, 1)).unwrap();
+        assert_eq!(view.projected_outcome, TallyOutcome::TooFewVoters);
+    }
+    
+    #[test]
+    fn test_status_passing() {
+        let view = proposal_status(&proposal(2_001, 1_999, 5)).unwrap();
+        
+        assert!(view.quorum_met);
+        assert_eq!(view.yes_bps, 5002);
+        assert_eq!(view.pass_threshold_bps, PASS_THRESHOLD_BPS);
+        assert_eq!(view.min_quorum_stake, 1_000);
+        assert_eq!(view.projected_outcome, TallyOutcome::Passed);
+    }
+    
+    #[test]
+    fn test_parameter_change_keeps_live_thresholds() {
+        let live = proposal(2_001, 1_999, 5);
+        
+        // Governance raises the bar while the vote is still open
+        let mut global_state = global_state();
+        ParameterUpdate {
+            min_quorum_stake: Some(10_000),
+            min_unique_voters: Some(10),
+            ..Default::default()
+        }
+        .apply(&mut global_state)
+        .unwrap();
+        assert_eq!(global_state.min_quorum_stake, 10_000);
+        
+        // The live proposal is still judged by the rules it was created under
+        let view = proposal_status(&live).unwrap();
+        assert_eq!(view.min_quorum_stake, 1_000);
+        assert_eq!(view.min_unique_voters, 2);
+        assert_eq!(view.projected_outcome, TallyOutcome::Passed);
+        
+        // A proposal created after the change picks up the new rules
+        let mut fresh = live.clone();
+        fresh.snapshot_rules(&global_state);
+        let view = proposal_status(&fresh).unwrap();
+        assert_eq!(view.projected_outcome, TallyOutcome::BelowQuorum);
+    }
+}
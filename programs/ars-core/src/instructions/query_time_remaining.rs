@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+/// Countdown for a proposal's voting period
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct TimeRemainingView {
+    pub seconds_remaining: u64, // Zero once voting has closed
+    pub voting_open: bool,
+}
+
+#[derive(Accounts)]
+pub struct QueryTimeRemaining<'info> {
+    #[account(
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, PolicyProposal>,
+}
+
+pub fn handler(ctx: Context<QueryTimeRemaining>) -> Result<TimeRemainingView> {
+    let clock = Clock::get()?;
+    let view = time_remaining(&ctx.accounts.proposal, clock.unix_timestamp);
+    
+    msg!("Proposal {}", ctx.accounts.proposal.id);
+    msg!("Voting open: {}, seconds remaining: {}", view.voting_open, view.seconds_remaining);
+    
+    Ok(view)
+}
+
+/// Voting is open under the same rule vote_on_proposal enforces: the
+/// proposal is Active and `end_time` has not been reached
+pub fn time_remaining(proposal: &PolicyProposal, now: i64) -> TimeRemainingView {
+    let voting_open = proposal.status == ProposalStatus::Active && now < proposal.end_time;
+    let seconds_remaining = if voting_open {
+        proposal.end_time.saturating_sub(now) as u64
+    } else {
+        0
+    };
+    
+    TimeRemainingView {
+        seconds_remaining,
+        voting_open,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn proposal(status: ProposalStatus) -> PolicyProposal {
+        PolicyProposal {
+            id: 1,
+            proposer: Pubkey::new_unique(),
+            start_time: 1_000,
+            end_time: 1_000 + MIN_VOTING_PERIOD,
+            status,
+            bump: 255,
+            ..Default::default()
+        }
+    }
+    
+    #[test]
+    fn test_active_proposal_counts_down() {
+        let proposal = proposal(ProposalStatus::Active);
+        
+        let view = time_remaining(&proposal, proposal.start_time);
+        assert!(view.voting_open);
+        assert_eq!(view.seconds_remaining, MIN_VOTING_PERIOD as u64);
+        
+        let view = time_remaining(&proposal, proposal.end_time - 1);
+        assert!(view.voting_open);
+        assert_eq!(view.seconds_remaining, 1);
+    }
+    
+    #[test]
+    fn test_ended_proposal_reports_zero() {
+        let proposal = proposal(ProposalStatus::Active);
+        
+        // Voting closes at end_time itself
+        let view = time_remaining(&proposal, proposal.end_time);
+        assert!(!view.voting_open);
+        assert_eq!(view.seconds_remaining, 0);
+        
+        let view = time_remaining(&proposal, proposal.end_time + 3_600);
+        assert!(!view.voting_open);
+        assert_eq!(view.seconds_remaining, 0);
+    }
+    
+    #[test]
+    fn test_finalized_proposal_is_closed_before_end_time() {
+        let proposal = proposal(ProposalStatus::Passed);
+        
+        let view = time_remaining(&proposal, proposal.start_time);
+        assert!(!view.voting_open);
+        assert_eq!(view.seconds_remaining, 0);
+    }
+}
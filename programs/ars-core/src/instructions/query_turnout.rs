@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct QueryTurnout<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+pub fn handler(ctx: Context<QueryTurnout>) -> Result<u64> {
+    let global_state = &ctx.accounts.global_state;
+    
+    msg!("Turnout EMA: {}", global_state.turnout_ema);
+    msg!("Finalized proposals sampled: {}", global_state.turnout_samples);
+    msg!("Minimum quorum: {}", global_state.min_quorum_stake);
+    
+    Ok(global_state.turnout_ema)
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::ICBError;
+
+#[derive(Accounts)]
+#[instruction(expected_next: u64)]
+pub struct ReconcileCounter<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ICBError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    /// CHECK: Only inspected to make sure no proposal lives at `expected_next`
+    #[account(
+        seeds = [PROPOSAL_SEED, &expected_next.to_le_bytes()],
+        bump
+    )]
+    pub target_proposal: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ReconcileCounter>, expected_next: u64) -> Result<()> {
+    require!(
+        is_free_proposal_slot(&ctx.accounts.target_proposal.try_borrow_data()?),
+        ICBError::ProposalAlreadyExists
+    );
+    
+    let global_state = &mut ctx.accounts.global_state;
+    let previous = global_state.proposal_counter;
+    global_state.reconcile_counter(expected_next)?;
+    
+    msg!("Proposal counter advanced from {} to {}", previous, expected_next);
+    
+    Ok(())
+}
+
+/// Whether a proposal PDA can still be created at: either it does not
+/// exist, or preallocate_proposal reserved it but it was never filled
+pub fn is_free_proposal_slot(data: &[u8]) -> bool {
+    if data.is_empty() {
+        return true;
+    }
+    
+    data.len() >= 8
+        && data[..8] == PolicyProposal::DISCRIMINATOR
+        && PolicyProposal::try_deserialize(&mut &data[..])
+            .map(|proposal| proposal.id == NO_PROPOSAL_ID)
+            .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn global_state(proposal_counter: u64) -> GlobalState {
+        GlobalState { proposal_counter, ..Default::default() }
+    }
+    
+    #[test]
+    fn test_counter_cannot_decrease() {
+        let mut global_state = global_state(10);
+        
+        assert_eq!(
+            global_state.reconcile_counter(9).unwrap_err(),
+            ICBError::ProposalCounterNotAdvanced.into()
+        );
+        assert!(global_state.reconcile_counter(10).is_err());
+        assert_eq!(global_state.proposal_counter, 10);
+    }
+    
+    #[test]
+    fn test_counter_advances_past_gap() {
+        let mut global_state = global_state(5);
+        
+        // Ids 5..9 were taken outside the counter; skip them
+        global_state.reconcile_counter(9).unwrap();
+        assert_eq!(global_state.allocate_proposal_id().unwrap(), 9);
+        assert_eq!(global_state.proposal_counter, 10);
+    }
+    
+    #[test]
+    fn test_target_slot_must_be_free() {
+        // Never created
+        assert!(is_free_proposal_slot(&[]));
+        
+        // Preallocated but not filled
+        let mut data = PolicyProposal::DISCRIMINATOR.to_vec();
+        data.resize(PolicyProposal::LEN, 0);
+        assert!(is_free_proposal_slot(&data));
+        
+        // Filled: id is written right after the discriminator
+        data[8..16].copy_from_slice(&7u64.to_le_bytes());
+        assert!(!is_free_proposal_slot(&data));
+        
+        // Some other account
+        assert!(!is_free_proposal_slot(&[1u8; 16]));
+    }
+}
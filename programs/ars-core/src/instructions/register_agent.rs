@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::ICBError;
+
+#[derive(Accounts)]
+pub struct RegisterAgent<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ICBError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = AgentRegistry::LEN,
+        seeds = [AGENT_SEED, agent.key().as_ref()],
+        bump
+    )]
+    pub agent_registry: Account<'info, AgentRegistry>,
+    
+    /// CHECK: Only the key is recorded; the agent proves control when acting
+    pub agent: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RegisterAgent>, agent_type: AgentType) -> Result<()> {
+    let agent_registry = &mut ctx.accounts.agent_registry;
+    let clock = Clock::get()?;
+    
+    agent_registry.agent_pubkey = ctx.accounts.agent.key();
+    agent_registry.agent_type = agent_type;
+    agent_registry.total_transactions = 0;
+    agent_registry.total_volume = 0;
+    agent_registry.reputation_score = 0;
+    agent_registry.registered_at = clock.unix_timestamp;
+    agent_registry.last_active = clock.unix_timestamp;
+    agent_registry.last_vote_signature = [0; 64];
+    agent_registry.bump = ctx.bumps.agent_registry;
+    
+    msg!("Agent registered: {:?}", agent_registry.agent_pubkey);
+    
+    Ok(())
+}
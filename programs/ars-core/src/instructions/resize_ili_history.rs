@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::ICBError;
+
+#[derive(Accounts)]
+#[instruction(new_capacity: u16)]
+pub struct ResizeILIHistory<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ICBError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        mut,
+        seeds = [ILI_ORACLE_SEED],
+        bump = ili_oracle.bump,
+        realloc = ILIOracle::space(new_capacity as usize),
+        realloc::payer = authority,
+        realloc::zero = false
+    )]
+    pub ili_oracle: Account<'info, ILIOracle>,
+    
+    /// Pays for (or is refunded) the rent difference
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ResizeILIHistory>, new_capacity: u16) -> Result<()> {
+    let ili_oracle = &mut ctx.accounts.ili_oracle;
+    let old_capacity = ili_oracle.history_capacity;
+    
+    // A single instruction can only grow an account by MAX_PERMITTED_DATA_INCREASE
+    let growth = ILIOracle::space(new_capacity as usize)
+        .saturating_sub(ILIOracle::space(old_capacity as usize));
+    require!(
+        growth <= MAX_PERMITTED_DATA_INCREASE,
+        ICBError::InvalidHistoryCapacity
+    );
+    
+    ili_oracle.resize_history(new_capacity)?;
+    
+    msg!("ILI history resized from {} to {} snapshots", old_capacity, new_capacity);
+    msg!("Snapshots retained: {}", ili_oracle.history.len());
+    
+    Ok(())
+}
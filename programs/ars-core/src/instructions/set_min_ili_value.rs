@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::ICBError;
+
+#[derive(Accounts)]
+pub struct SetMinILIValue<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ICBError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        mut,
+        seeds = [ILI_ORACLE_SEED],
+        bump = ili_oracle.bump
+    )]
+    pub ili_oracle: Account<'info, ILIOracle>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetMinILIValue>, min_ili_value: u64) -> Result<()> {
+    require!(
+        min_ili_value > 0 && min_ili_value <= MAX_ILI_VALUE,
+        ICBError::InvalidILIValue
+    );
+    
+    let ili_oracle = &mut ctx.accounts.ili_oracle;
+    ili_oracle.min_ili_value = min_ili_value;
+    
+    msg!("Min ILI value set to {}", min_ili_value);
+    
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::ICBError;
+
+#[derive(Accounts)]
+pub struct SetOracleDegraded<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ICBError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        mut,
+        seeds = [ILI_ORACLE_SEED],
+        bump = ili_oracle.bump
+    )]
+    pub ili_oracle: Account<'info, ILIOracle>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetOracleDegraded>, degraded: bool) -> Result<()> {
+    let ili_oracle = &mut ctx.accounts.ili_oracle;
+    let clock = Clock::get()?;
+    
+    ili_oracle.degraded = degraded;
+    
+    emit!(OracleDegradedModeChanged {
+        degraded,
+        authority: ctx.accounts.authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Oracle degraded mode: {}", degraded);
+    msg!("Max deviation: {} bps", ili_oracle.max_deviation_bps());
+    msg!("Max staleness: {} seconds", ili_oracle.max_staleness());
+    
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::{key_mismatch, ICBError};
+
+#[derive(Accounts)]
+pub struct SettleDeposit<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, PolicyProposal>,
+    
+    #[account(
+        mut,
+        address = proposal.deposit_escrow @ ICBError::InvalidAccountOwner
+    )]
+    pub deposit_escrow: Account<'info, TokenAccount>,
+    
+    /// Receives the deposit back when the proposal met quorum
+    #[account(
+        mut,
+        constraint = proposer_token_account.owner == proposal.proposer
+            @ key_mismatch(ICBError::InvalidAccountOwner, proposer_token_account.owner, proposal.proposer),
+        constraint = proposer_token_account.mint == deposit_escrow.mint @ ICBError::InvalidStakeMint
+    )]
+    pub proposer_token_account: Account<'info, TokenAccount>,
+    
+    /// Receives a forfeited deposit
+    #[account(
+        mut,
+        address = global_state.reserve_vault @ ICBError::InvalidReserveVault
+    )]
+    pub reserve_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<SettleDeposit>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let amount = proposal.deposit_amount;
+    let min_quorum_stake = proposal.min_quorum_stake;
+    let refunded = proposal.settle_deposit(min_quorum_stake)?;
+    
+    let destination = if refunded {
+        ctx.accounts.proposer_token_account.to_account_info()
+    } else {
+        ctx.accounts.reserve_vault.to_account_info()
+    };
+    
+    // The escrow is owned by the proposal PDA
+    let id_bytes = proposal.id.to_le_bytes();
+    let seeds = &[PROPOSAL_SEED, &id_bytes, &[proposal.bump]];
+    let signer = &[&seeds[..]];
+    
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.deposit_escrow.to_account_info(),
+        to: destination,
+        authority: proposal.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    token::transfer(CpiContext::new_with_signer(cpi_program, cpi_accounts, signer), amount)?;
+    
+    if refunded {
+        msg!("Deposit of {} refunded for proposal {}", amount, proposal.id);
+    } else {
+        msg!("Deposit of {} forfeited to the reserve for proposal {}", amount, proposal.id);
+    }
+    
+    Ok(())
+}
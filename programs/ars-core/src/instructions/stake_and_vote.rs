@@ -0,0 +1,291 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::{key_mismatch, ICBError};
+use crate::constants::*;
+use crate::instructions::vote_on_proposal::{cast_vote, Ballot};
+
+#[derive(Accounts)]
+pub struct StakeAndVote<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
+        bump = proposal.bump,
+        constraint = proposal.status == ProposalStatus::Active @ ICBError::ProposalNotActive
+    )]
+    pub proposal: Account<'info, PolicyProposal>,
+    
+    #[account(
+        init_if_needed,
+        payer = agent,
+        space = VoteRecord::LEN,
+        seeds = [VOTE_SEED, proposal.key().as_ref(), agent.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+    
+    #[account(
+        mut,
+        seeds = [AGENT_SEED, agent.key().as_ref()],
+        bump = agent_registry.bump
+    )]
+    pub agent_registry: Account<'info, AgentRegistry>,
+    
+    #[account(
+        mut,
+        constraint = agent_token_account.owner == agent.key()
+            @ key_mismatch(ICBError::InvalidAccountOwner, agent_token_account.owner, agent.key()),
+        constraint = agent_token_account.mint == global_state.icu_mint @ ICBError::InvalidStakeMint
+    )]
+    pub agent_token_account: Account<'info, TokenAccount>,
+    
+    /// Holds the proposal's staked tokens until claim_rewards pays them out.
+    /// The first stake binds it to the proposal as `stake_escrow`.
+    #[account(
+        mut,
+        constraint = escrow_token_account.owner == proposal.key()
+            @ key_mismatch(ICBError::InvalidAccountOwner, escrow_token_account.owner, proposal.key()),
+        constraint = escrow_token_account.mint == global_state.icu_mint @ ICBError::InvalidStakeMint
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub agent: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar for agent verification (ARS-SA-2026-001)
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<StakeAndVote>,
+    prediction: bool,
+    stake_amount: u64,
+    agent_signature: [u8; 64],
+) -> Result<()> {
+    // ARS-SA-2026-001: Validate agent authentication
+    crate::validate_agent_auth(
+        &ctx.accounts.instructions_sysvar,
+        &ctx.accounts.agent.key(),
+    )?;
+    
+    let clock = Clock::get()?;
+    let ballot = Ballot { prediction, stake_amount, agent_signature };
+    
+    ctx.accounts.vote_record.proposal = ctx.accounts.proposal.key();
+    ctx.accounts.vote_record.bump = ctx.bumps.vote_record;
+    ctx.accounts.proposal.bind_stake_escrow(ctx.accounts.escrow_token_account.key())?;
+    
+    let proposal_info = ctx.accounts.proposal.to_account_info();
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.agent_token_account.to_account_info(),
+        to: ctx.accounts.escrow_token_account.to_account_info(),
+        authority: ctx.accounts.agent.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    
+    // The transfer only runs once the vote has passed validation
+    cast_vote(
+        &mut ctx.accounts.proposal,
+        &mut ctx.accounts.vote_record,
+        &mut ctx.accounts.agent_registry,
+        ctx.accounts.agent.key(),
+        &ballot,
+        &clock,
+        |locked, amount| {
+            // A re-entered instruction loads the proposal from its account,
+            // so the lock is written there before the transfer
+            locked.persist(&mut proposal_info.try_borrow_mut_data()?)?;
+            token::transfer(CpiContext::new(cpi_program, cpi_accounts), amount)
+        },
+    )?;
+    
+    msg!("Escrowed {} for proposal {}", stake_amount, ctx.accounts.proposal.id);
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn proposal(end_time: i64) -> PolicyProposal {
+        PolicyProposal {
+            id: 4,
+            proposer: Pubkey::new_unique(),
+            start_time: 1_000,
+            end_time,
+            status: ProposalStatus::Active,
+            bump: 255,
+            ..Default::default()
+        }
+    }
+    
+    fn vote_record() -> VoteRecord {
+        VoteRecord {
+            proposal: Pubkey::default(),
+            agent: Pubkey::default(),
+            stake_amount: 0,
+            prediction: false,
+            timestamp: 0,
+            claimed: false,
+            agent_signature: [0; 64],
+            contrarian: false,
+            voted_slot: 0,
+            bump: 0,
+        }
+    }
+    
+    fn registry(agent: Pubkey) -> AgentRegistry {
+        AgentRegistry {
+            agent_pubkey: agent,
+            agent_type: AgentType::PredictionAgent,
+            total_transactions: 0,
+            total_volume: 0,
+            reputation_score: 0,
+            registered_at: 1_000,
+            last_active: 1_000,
+            last_vote_signature: [0; 64],
+            bump: 255,
+        }
+    }
+    
+    fn clock(unix_timestamp: i64) -> Clock {
+        Clock { slot: 77, unix_timestamp, ..Clock::default() }
+    }
+    
+    #[test]
+    fn test_failed_vote_leaves_no_escrow_debit() {
+        let agent = Pubkey::new_unique();
+        let ballot = Ballot { prediction: true, stake_amount: 10_000, agent_signature: [7; 64] };
+        let mut agent_balance = 50_000u64;
+        let mut escrowed = 0u64;
+        
+        // Voting period over
+        let mut proposal = proposal(2_000);
+        let mut record = vote_record();
+        let result = cast_vote(
+            &mut proposal,
+            &mut record,
+            &mut registry(agent),
+            agent,
+            &ballot,
+            &clock(2_000),
+            |_, amount| {
+                agent_balance -= amount;
+                escrowed += amount;
+                Ok(())
+            },
+        );
+        assert_eq!(result.unwrap_err(), ICBError::ProposalNotActive.into());
+        assert_eq!((agent_balance, escrowed), (50_000, 0));
+        assert_eq!((proposal.yes_stake, proposal.yes_staked, proposal.unique_voter_count), (0, 0, 0));
+        assert!(!record.is_recorded());
+        
+        // Re-vote by an agent already recorded
+        let mut record = VoteRecord { agent, ..vote_record() };
+        let result = cast_vote(
+            &mut proposal,
+            &mut record,
+            &mut registry(agent),
+            agent,
+            &ballot,
+            &clock(1_500),
+            |_, amount| {
+                agent_balance -= amount;
+                Ok(())
+            },
+        );
+        assert_eq!(result.unwrap_err(), ICBError::AlreadyVoted.into());
+        assert_eq!(agent_balance, 50_000);
+        
+        // A failing transfer leaves the tally untouched and the lock released
+        let mut record = vote_record();
+        let result = cast_vote(
+            &mut proposal,
+            &mut record,
+            &mut registry(agent),
+            agent,
+            &ballot,
+            &clock(1_500),
+            |_, _| err!(ICBError::InsufficientStake),
+        );
+        assert!(result.is_err());
+        assert_eq!(proposal.yes_stake, 0);
+        assert!(!proposal.locked);
+    }
+    
+    #[test]
+    fn test_stake_escrowed_with_vote() {
+        let agent = Pubkey::new_unique();
+        let ballot = Ballot { prediction: false, stake_amount: 10_000, agent_signature: [7; 64] };
+        let mut proposal = proposal(2_000);
+        let mut record = vote_record();
+        let mut registry = registry(agent);
+        let mut escrowed = 0u64;
+        
+        let voting_power = cast_vote(
+            &mut proposal,
+            &mut record,
+            &mut registry,
+            agent,
+            &ballot,
+            &clock(1_500),
+            |_, amount| {
+                escrowed += amount;
+                Ok(())
+            },
+        )
+        .unwrap();
+        
+        assert_eq!(escrowed, 10_000);
+        assert_eq!(proposal.no_staked, 10_000);
+        assert_eq!(proposal.no_stake, voting_power);
+        assert_eq!(proposal.unique_voter_count, 1);
+        assert_eq!((record.agent, record.stake_amount, record.voted_slot), (agent, 10_000, 77));
+        assert_eq!(registry.total_volume, 10_000);
+    }
+    
+    #[test]
+    fn test_lock_persisted_before_escrow_transfer() {
+        let agent = Pubkey::new_unique();
+        let ballot = Ballot { prediction: true, stake_amount: 10_000, agent_signature: [7; 64] };
+        let mut proposal = proposal(2_000);
+        let mut data = vec![0u8; PolicyProposal::LEN];
+        
+        cast_vote(
+            &mut proposal,
+            &mut vote_record(),
+            &mut registry(agent),
+            agent,
+            &ballot,
+            &clock(1_500),
+            |locked, _| {
+                locked.persist(&mut data)?;
+                
+                // What a re-entered stake_and_vote would load and lock
+                let mut reentered = PolicyProposal::try_deserialize(&mut &data[..])?;
+                assert!(reentered.locked);
+                assert_eq!(
+                    reentered.with_lock(|_| Ok(())).unwrap_err(),
+                    ICBError::ReentrancyDetected.into()
+                );
+                Ok(())
+            },
+        )
+        .unwrap();
+        
+        assert!(!proposal.locked);
+        assert_eq!(proposal.yes_staked, 10_000);
+    }
+}
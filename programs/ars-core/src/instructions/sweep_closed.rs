@@ -0,0 +1,205 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use crate::state::*;
+use crate::errors::ICBError;
+
+#[derive(Accounts)]
+pub struct SweepClosed<'info> {
+    /// Anyone may crank the sweep. The cranker only pays the transaction
+    /// fee; rent always goes to the original payers, never to the cranker.
+    pub cranker: Signer<'info>,
+}
+
+/// `remaining_accounts` holds (account, rent recipient) pairs. Each account
+/// that is eligible and paired with its original payer is closed; anything
+/// else is skipped so one bad entry does not revert the batch.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, SweepClosed<'info>>) -> Result<()> {
+    require!(
+        ctx.remaining_accounts.chunks_exact(2).remainder().is_empty(),
+        ICBError::InvalidParameter
+    );
+    
+    let mut closed: u32 = 0;
+    let mut skipped: u32 = 0;
+    
+    for pair in ctx.remaining_accounts.chunks(2) {
+        let (target, recipient) = (&pair[0], &pair[1]);
+        
+        let eligible = target.owner == ctx.program_id
+            && target.is_writable
+            && recipient.is_writable
+            && sweep_recipient(&target.try_borrow_data()?) == Some(recipient.key());
+        
+        if eligible {
+            close_account(target, recipient)?;
+            closed += 1;
+        } else {
+            msg!("Skipped {}", target.key());
+            skipped += 1;
+        }
+    }
+    
+    msg!("Swept {} accounts, skipped {}", closed, skipped);
+    
+    #[cfg(feature = "compute-report")]
+    crate::utils::report_compute_units("sweep_closed", closed + skipped);
+    
+    Ok(())
+}
+
+/// Close a program-owned account: move its lamports to `recipient` and hand
+/// it back to the system program with no data, so it cannot be revived
+fn close_account<'info>(target: &AccountInfo<'info>, recipient: &AccountInfo<'info>) -> Result<()> {
+    let lamports = recipient.lamports()
+        .checked_add(target.lamports())
+        .ok_or(ICBError::ArithmeticOverflow)?;
+    **recipient.try_borrow_mut_lamports()? = lamports;
+    **target.try_borrow_mut_lamports()? = 0;
+    
+    target.assign(&anchor_lang::system_program::ID);
+    target.realloc(0, false)?;
+    Ok(())
+}
+
+/// Who is owed the rent of a closable account: the rent payer of a terminal
+/// proposal whose votes are all settled, or the agent of a claimed vote
+/// record. None for anything else, including unknown account types.
+pub fn sweep_recipient(data: &[u8]) -> Option<Pubkey> {
+    if data.len() < 8 {
+        return None;
+    }
+    
+    if data[..8] == PolicyProposal::DISCRIMINATOR {
+        let proposal = PolicyProposal::try_deserialize(&mut &data[..]).ok()?;
+        return proposal.is_sweepable().then_some(proposal.rent_recipient());
+    }
+    
+    // claim_rewards only settles votes on terminal proposals
+    if data[..8] == VoteRecord::DISCRIMINATOR {
+        let vote_record = VoteRecord::try_deserialize(&mut &data[..]).ok()?;
+        return vote_record.claimed.then_some(vote_record.agent);
+    }
+    
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::*;
+    
+    fn proposal(status: ProposalStatus, unique_voter_count: u32, settled_vote_count: u32) -> PolicyProposal {
+        PolicyProposal {
+            id: 1,
+            proposer: Pubkey::new_unique(),
+            start_time: 1_000,
+            end_time: 1_000 + MIN_VOTING_PERIOD,
+            status,
+            unique_voter_count,
+            settled_vote_count,
+            bump: 255,
+            ..Default::default()
+        }
+    }
+    
+    fn vote_record(claimed: bool) -> VoteRecord {
+        VoteRecord {
+            proposal: Pubkey::new_unique(),
+            agent: Pubkey::new_unique(),
+            stake_amount: 1_000,
+            prediction: true,
+            timestamp: 1_000,
+            claimed,
+            agent_signature: [1; 64],
+            contrarian: false,
+            voted_slot: 0,
+            bump: 255,
+        }
+    }
+    
+    fn data<T: AccountSerialize>(account: &T) -> Vec<u8> {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        data
+    }
+    
+    #[test]
+    fn test_mixed_eligible_and_ineligible_accounts() {
+        let settled = proposal(ProposalStatus::Executed, 2, 2);
+        let unsettled = proposal(ProposalStatus::Failed, 2, 1);
+        let active = proposal(ProposalStatus::Active, 0, 0);
+        let claimed = vote_record(true);
+        let unclaimed = vote_record(false);
+        
+        let accounts = [
+            data(&settled),
+            data(&unsettled),
+            data(&active),
+            data(&claimed),
+            data(&unclaimed),
+        ];
+        let recipients: Vec<Option<Pubkey>> = accounts.iter().map(|d| sweep_recipient(d)).collect();
+        
+        // Rent goes to whoever paid for the account
+        assert_eq!(
+            recipients,
+            vec![Some(settled.proposer), None, None, Some(claimed.agent), None]
+        );
+    }
+    
+    #[test]
+    fn test_unsettled_deposit_blocks_sweep() {
+        let with_deposit = |deposit_settled| PolicyProposal {
+            deposit_amount: 5_000,
+            deposit_escrow: Pubkey::new_unique(),
+            deposit_settled,
+            ..proposal(ProposalStatus::Failed, 2, 2)
+        };
+        
+        // settle_deposit still has to load the proposal to release the escrow
+        let unsettled = with_deposit(false);
+        assert!(!unsettled.is_sweepable());
+        assert_eq!(sweep_recipient(&data(&unsettled)), None);
+        
+        let settled = with_deposit(true);
+        assert_eq!(sweep_recipient(&data(&settled)), Some(settled.proposer));
+    }
+    
+    #[test]
+    fn test_rent_returns_to_original_payer() {
+        // Created directly: the proposer paid and is refunded
+        let mut created = proposal(ProposalStatus::Executed, 0, 0);
+        created.record_rent_payer(created.proposer);
+        assert_eq!(sweep_recipient(&data(&created)), Some(created.proposer));
+        
+        // Preallocated by a keeper: the keeper is refunded, not the proposer
+        let keeper = Pubkey::new_unique();
+        let mut preallocated = proposal(ProposalStatus::Failed, 0, 0);
+        preallocated.record_rent_payer(keeper);
+        preallocated.record_rent_payer(preallocated.proposer);
+        assert_eq!(sweep_recipient(&data(&preallocated)), Some(keeper));
+        assert_ne!(sweep_recipient(&data(&preallocated)), Some(preallocated.proposer));
+    }
+    
+    #[test]
+    fn test_every_terminal_status_sweepable_once_settled() {
+        for status in [ProposalStatus::Executed, ProposalStatus::Failed, ProposalStatus::Cancelled] {
+            assert!(proposal(status, 3, 3).is_sweepable());
+        }
+        assert!(!proposal(ProposalStatus::Passed, 0, 0).is_sweepable());
+    }
+    
+    #[test]
+    fn test_unknown_accounts_skipped() {
+        assert_eq!(sweep_recipient(&[]), None);
+        assert_eq!(sweep_recipient(&[0; 64]), None);
+        
+        // A recognised discriminator with a truncated body
+        let mut truncated = data(&vote_record(true));
+        truncated.truncate(40);
+        assert_eq!(sweep_recipient(&truncated), None);
+        
+        let global_state = GlobalState::default();
+        assert_eq!(sweep_recipient(&data(&global_state)), None);
+    }
+}
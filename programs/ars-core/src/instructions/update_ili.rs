@@ -14,12 +14,22 @@ pub struct UpdateILI<'info> {
     #[account(
         mut,
         seeds = [ILI_ORACLE_SEED],
-        bump = ili_oracle.bump,
-        constraint = ili_oracle.authority == authority.key() @ ICBError::Unauthorized
+        bump = ili_oracle.bump
     )]
     pub ili_oracle: Account<'info, ILIOracle>,
     
-    pub authority: Signer<'info>,
+    /// A registered submitter, or the oracle authority while none are
+    /// registered; see check_submitter
+    pub submitter: Signer<'info>,
+    
+    /// The submitter's registration and staggered update schedule,
+    /// required once any submitter is registered
+    #[account(
+        mut,
+        seeds = [ORACLE_SUBMITTER_SEED, submitter.key().as_ref()],
+        bump = oracle_submitter.bump
+    )]
+    pub oracle_submitter: Option<Account<'info, OracleSubmitter>>,
     
     /// CHECK: Instructions sysvar for agent verification (ARS-SA-2026-001)
     #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
@@ -36,51 +46,425 @@ pub fn handler(
     // ARS-SA-2026-001: Validate agent authentication
     crate::validate_agent_auth(
         &ctx.accounts.instructions_sysvar,
-        &ctx.accounts.authority.key(),
+        &ctx.accounts.submitter.key(),
+    )?;
+    
+    check_oracle_account(&ctx.accounts.global_state, &ctx.accounts.ili_oracle.key())?;
+    check_submitter(
+        &ctx.accounts.global_state,
+        &ctx.accounts.ili_oracle.authority,
+        &ctx.accounts.submitter.key(),
+        ctx.accounts.oracle_submitter.as_deref(),
     )?;
     
     let ili_oracle = &mut ctx.accounts.ili_oracle;
     let clock = Clock::get()?;
     
+    // FIX #9: Bound write amplification even if the authority misbehaves
+    check_slot_rate_limit(ili_oracle.last_update_slot, clock.slot)?;
+    
+    // Two snapshots sharing a timestamp would get zero TWAP weight
+    validate_timestamp(ili_oracle.last_update, clock.unix_timestamp)?;
+    
     // FIX #9: Combine timestamp AND slot checks for clock manipulation protection
-    let time_delta = clock.unix_timestamp - ili_oracle.last_update;
-    let slot_delta = clock.slot - ili_oracle.last_update_slot;
+    check_submission_window(
+        ctx.accounts.oracle_submitter.as_deref(),
+        ili_oracle.last_update,
+        clock.unix_timestamp,
+        ili_oracle.update_interval,
+    )?;
     
-    require!(
-        time_delta >= ili_oracle.update_interval && slot_delta >= MIN_SLOT_BUFFER,
-        ICBError::ILIUpdateTooSoon
-    );
+    let snapshot = ILISnapshot {
+        timestamp: clock.unix_timestamp,
+        ili_value,
+        avg_yield,
+        volatility,
+        tvl,
+    };
     
     // FIX #6: Validate all oracle inputs
+    validate_snapshot(
+        ili_oracle.current_ili,
+        &snapshot,
+        ili_oracle.max_deviation_bps(),
+        ili_oracle.min_ili_value,
+    )?;
+    
+    // Large moves, and any move while degraded, need other registered
+    // submitters to co-sign via remaining_accounts
+    let signer_count = OracleSubmitter::signer_count(
+        &ctx.accounts.submitter.key(),
+        &cosigner_keys(ctx.remaining_accounts),
+        &registered_submitters(ctx.remaining_accounts, ctx.program_id),
+    );
+    ctx.accounts.global_state.check_ili_move_quorum(
+        deviation_bps(ili_oracle.current_ili, ili_value)?,
+        signer_count,
+        ili_oracle.degraded,
+    )?;
+    
+    // Update ILI oracle
+    ili_oracle.current_ili = ili_value;
+    ili_oracle.last_update = clock.unix_timestamp;
+    ili_oracle.last_update_slot = clock.slot; // FIX #9: Update slot
+    ili_oracle.push_snapshot(snapshot);
+    if let Some(submitter) = ctx.accounts.oracle_submitter.as_mut() {
+        submitter.last_submission = clock.unix_timestamp;
+    }
+    
+    msg!("ILI updated to: {}", ili_value);
+    msg!("Avg yield: {} bps", avg_yield);
+    msg!("Volatility: {} bps", volatility);
+    msg!("TVL: ${}", tvl);
+    msg!("Timestamp: {}", clock.unix_timestamp);
+    msg!("Slot: {}", clock.slot);
+    
+    Ok(())
+}
+
+/// Authorize the signer of an ILI write. A registered submitter signs with
+/// its schedule account; the oracle authority may sign without one only
+/// while no submitters are registered, so nobody can skip their own window
+/// by leaving the account out.
+pub fn check_submitter(
+    global_state: &GlobalState,
+    oracle_authority: &Pubkey,
+    signer: &Pubkey,
+    oracle_submitter: Option<&OracleSubmitter>,
+) -> Result<()> {
+    match oracle_submitter {
+        Some(schedule) => require_keys_eq!(schedule.submitter, *signer, ICBError::Unauthorized),
+        None => {
+            require_keys_eq!(*signer, *oracle_authority, ICBError::Unauthorized);
+            require!(
+                global_state.oracle_submitter_count == 0,
+                ICBError::OracleSubmitterRequired
+            );
+        }
+    }
+    Ok(())
+}
+
+/// A scheduled submitter writes once per its own phase-shifted window;
+/// without submitters the authority writes once per `update_interval`
+pub fn check_submission_window(
+    oracle_submitter: Option<&OracleSubmitter>,
+    last_update: i64,
+    now: i64,
+    update_interval: i64,
+) -> Result<()> {
+    let interval_elapsed = match oracle_submitter {
+        Some(schedule) => schedule.in_new_window(now, update_interval),
+        None => now.saturating_sub(last_update) >= update_interval,
+    };
+    require!(interval_elapsed, ICBError::ILIUpdateTooSoon);
+    Ok(())
+}
+
+/// Slots elapsed since the last ILI write. A stored slot ahead of the
+/// current one (e.g. a badly bootstrapped oracle) is an error, not an
+/// underflow.
+pub fn slots_since_update(last_update_slot: u64, current_slot: u64) -> Result<u64> {
+    current_slot
+        .checked_sub(last_update_slot)
+        .ok_or(error!(ICBError::LastUpdateSlotInFuture))
+}
+
+/// Allow at most one ILI write per `MIN_SLOT_BUFFER` slots. This is the
+/// only slot-based limit on ILI writes, single or batched.
+pub fn check_slot_rate_limit(last_update_slot: u64, current_slot: u64) -> Result<()> {
     require!(
-        ili_value > 0 && ili_value <= MAX_ILI_VALUE,
+        slots_since_update(last_update_slot, current_slot)? >= MIN_SLOT_BUFFER,
+        ICBError::ILIUpdateRateLimited
+    );
+    Ok(())
+}
+
+/// Require the new update to be strictly later than the last stored one
+pub fn validate_timestamp(last_update: i64, timestamp: i64) -> Result<()> {
+    require!(timestamp > last_update, ICBError::DuplicateILITimestamp);
+    Ok(())
+}
+
+/// Validate a single ILI data point against the oracle bounds (including the
+/// configured floor) and the maximum deviation from the previous value
+pub fn validate_snapshot(
+    previous_ili: u64,
+    snapshot: &ILISnapshot,
+    max_deviation_bps: u64,
+    min_ili_value: u64,
+) -> Result<()> {
+    require!(
+        snapshot.ili_value > 0
+            && snapshot.ili_value >= min_ili_value
+            && snapshot.ili_value <= MAX_ILI_VALUE,
         ICBError::InvalidILIValue
     );
     require!(
-        avg_yield <= MAX_YIELD_BPS,
+        snapshot.avg_yield <= MAX_YIELD_BPS,
         ICBError::InvalidYield
     );
     require!(
-        volatility <= MAX_VOLATILITY_BPS,
+        snapshot.volatility <= MAX_VOLATILITY_BPS,
         ICBError::InvalidVolatility
     );
     require!(
-        tvl > 0,
+        snapshot.tvl > 0,
         ICBError::InvalidTVL
     );
     
-    // Update ILI oracle
-    ili_oracle.current_ili = ili_value;
-    ili_oracle.last_update = clock.unix_timestamp;
-    ili_oracle.last_update_slot = clock.slot; // FIX #9: Update slot
-    ili_oracle.snapshot_count = ili_oracle.snapshot_count.saturating_add(1);
+    validate_deviation(previous_ili, snapshot.ili_value, max_deviation_bps)
+}
+
+/// Reject ILI moves larger than `max_deviation_bps` from the previous value
+pub fn validate_deviation(previous_ili: u64, ili_value: u64, max_deviation_bps: u64) -> Result<()> {
+    require!(
+        deviation_bps(previous_ili, ili_value)? <= max_deviation_bps,
+        ICBError::ILIDeviationTooLarge
+    );
     
-    msg!("ILI updated to: {}", ili_value);
-    msg!("Avg yield: {} bps", avg_yield);
-    msg!("Volatility: {} bps", volatility);
-    msg!("TVL: ${}", tvl);
-    msg!("Timestamp: {}", clock.unix_timestamp);
-    msg!("Slot: {}", clock.slot);
+    Ok(())
+}
+
+/// Move from `previous_ili` to `ili_value` in basis points, saturating at
+/// u64::MAX. The first update after initialization has nothing to deviate
+/// from and counts as 0.
+pub fn deviation_bps(previous_ili: u64, ili_value: u64) -> Result<u64> {
+    if previous_ili == 0 {
+        return Ok(0);
+    }
     
+    let deviation_bps = (previous_ili.abs_diff(ili_value) as u128)
+        .checked_mul(BPS_DENOMINATOR as u128)
+        .ok_or(ICBError::ArithmeticOverflow)?
+        .checked_div(previous_ili as u128)
+        .ok_or(ICBError::ArithmeticOverflow)?;
+    
+    Ok(deviation_bps.min(u64::MAX as u128) as u64)
+}
+
+/// The oracle being written must be the one global_state points at, not
+/// merely an account at a valid oracle seed
+pub fn check_oracle_account(global_state: &GlobalState, ili_oracle: &Pubkey) -> Result<()> {
+    require_keys_eq!(*ili_oracle, global_state.ili_oracle, ICBError::OracleMismatch);
     Ok(())
 }
+
+/// Keys of the extra signers passed in `remaining_accounts`, as candidate
+/// oracle cosigners
+pub fn cosigner_keys(remaining_accounts: &[AccountInfo]) -> Vec<Pubkey> {
+    remaining_accounts
+        .iter()
+        .filter(|account| account.is_signer)
+        .map(|account| account.key())
+        .collect()
+}
+
+/// Submitters whose `OracleSubmitter` registrations are passed in
+/// `remaining_accounts`; only these count as cosigners
+pub fn registered_submitters(remaining_accounts: &[AccountInfo], program_id: &Pubkey) -> Vec<Pubkey> {
+    remaining_accounts
+        .iter()
+        .filter(|account| account.owner == program_id)
+        .filter_map(|account| {
+            let data = account.try_borrow_data().ok()?;
+            OracleSubmitter::try_deserialize(&mut &data[..]).ok()
+        })
+        .map(|registration| registration.submitter)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_oracle_must_match_global_state() {
+        let ili_oracle = Pubkey::new_unique();
+        let global_state = GlobalState { ili_oracle, ..Default::default() };
+        
+        assert!(check_oracle_account(&global_state, &ili_oracle).is_ok());
+        
+        // Another oracle account, e.g. from a different seed set, is rejected
+        let other = Pubkey::new_unique();
+        assert_eq!(
+            check_oracle_account(&global_state, &other).unwrap_err(),
+            ICBError::OracleMismatch.into()
+        );
+    }
+    
+    #[test]
+    fn test_deviation_limits_by_mode() {
+        // 30% move: rejected under normal limits, accepted in degraded mode
+        assert_eq!(
+            validate_deviation(1_000_000, 1_300_000, MAX_ILI_DEVIATION_BPS).unwrap_err(),
+            ICBError::ILIDeviationTooLarge.into()
+        );
+        assert!(validate_deviation(1_000_000, 1_300_000, DEGRADED_MAX_ILI_DEVIATION_BPS).is_ok());
+        
+        // Moves at the limit pass in both modes
+        assert!(validate_deviation(1_000_000, 800_000, MAX_ILI_DEVIATION_BPS).is_ok());
+        assert!(validate_deviation(1_000_000, 1_500_000, DEGRADED_MAX_ILI_DEVIATION_BPS).is_ok());
+        assert!(validate_deviation(1_000_000, 1_500_100, DEGRADED_MAX_ILI_DEVIATION_BPS).is_err());
+        
+        // First update has no reference value
+        assert!(validate_deviation(0, 5_000_000, MAX_ILI_DEVIATION_BPS).is_ok());
+    }
+    
+    #[test]
+    fn test_min_ili_value_floor() {
+        let snapshot = |ili_value: u64| ILISnapshot {
+            timestamp: 1_700_000_000,
+            ili_value,
+            avg_yield: 500,
+            volatility: 1_000,
+            tvl: 1_000_000,
+        };
+        let floor = 500_000;
+        
+        // At the floor is accepted, just below it is not
+        assert!(validate_snapshot(0, &snapshot(floor), MAX_ILI_DEVIATION_BPS, floor).is_ok());
+        assert_eq!(
+            validate_snapshot(0, &snapshot(floor - 1), MAX_ILI_DEVIATION_BPS, floor).unwrap_err(),
+            ICBError::InvalidILIValue.into()
+        );
+        
+        // The default floor only rejects zero, as before
+        assert!(validate_snapshot(0, &snapshot(1), MAX_ILI_DEVIATION_BPS, DEFAULT_MIN_ILI_VALUE).is_ok());
+        assert!(validate_snapshot(0, &snapshot(0), MAX_ILI_DEVIATION_BPS, DEFAULT_MIN_ILI_VALUE).is_err());
+        assert!(validate_snapshot(0, &snapshot(0), MAX_ILI_DEVIATION_BPS, 0).is_err());
+    }
+    
+    #[test]
+    fn test_duplicate_timestamp_rejected() {
+        let now = 1_700_000_000;
+        let mut last_update = now - 300;
+        
+        // First update at `now` is accepted and recorded
+        assert!(validate_timestamp(last_update, now).is_ok());
+        last_update = now;
+        
+        // A second update carrying the same timestamp is rejected
+        assert_eq!(
+            validate_timestamp(last_update, now).unwrap_err(),
+            ICBError::DuplicateILITimestamp.into()
+        );
+        assert!(validate_timestamp(last_update, now - 1).is_err());
+        assert!(validate_timestamp(last_update, now + 1).is_ok());
+    }
+    
+    #[test]
+    fn test_slot_rate_limit() {
+        let slot = 1_000;
+        
+        // Second update in the adjacent slot is rejected
+        assert_eq!(
+            check_slot_rate_limit(slot, slot + 1).unwrap_err(),
+            ICBError::ILIUpdateRateLimited.into()
+        );
+        assert!(check_slot_rate_limit(slot, slot).is_err());
+        assert!(check_slot_rate_limit(slot, slot + MIN_SLOT_BUFFER - 1).is_err());
+        assert!(check_slot_rate_limit(slot, slot + MIN_SLOT_BUFFER).is_ok());
+    }
+    
+    #[test]
+    fn test_submitters_rate_limited_by_own_phase() {
+        let interval = DEFAULT_ILI_UPDATE_INTERVAL;
+        let base = 1_700_000_100 - 1_700_000_100 % interval;
+        let submitter = |phase_offset: i64, last_submission: i64| OracleSubmitter {
+            submitter: Pubkey::new_unique(),
+            phase_offset,
+            last_submission,
+            bump: 255,
+        };
+        
+        // Both wrote at the start of their current window
+        let early = submitter(0, base);
+        let late = submitter(interval / 2, base + interval / 2);
+        
+        // Each is blocked for the rest of its own window...
+        assert!(!early.in_new_window(base + interval - 1, interval));
+        assert!(!late.in_new_window(base + interval + interval / 2 - 1, interval));
+        
+        // ...and allowed again once its next window opens, half an interval apart
+        assert!(early.in_new_window(base + interval, interval));
+        assert!(!late.in_new_window(base + interval, interval));
+        assert!(late.in_new_window(base + interval + interval / 2, interval));
+        
+        // A fresh submitter may write immediately
+        assert!(submitter(interval / 2, 0).in_new_window(base, interval));
+    }
+    
+    #[test]
+    fn test_registered_submitters_sign_for_the_oracle() {
+        let oracle_authority = Pubkey::new_unique();
+        let submitter = Pubkey::new_unique();
+        let schedule = OracleSubmitter {
+            submitter,
+            phase_offset: 0,
+            last_submission: 0,
+            bump: 255,
+        };
+        let unscheduled = GlobalState::default();
+        let scheduled = GlobalState { oracle_submitter_count: 2, ..Default::default() };
+        
+        // Any registered submitter may write with its own schedule account
+        assert!(check_submitter(&scheduled, &oracle_authority, &submitter, Some(&schedule)).is_ok());
+        assert_eq!(
+            check_submitter(&scheduled, &oracle_authority, &Pubkey::new_unique(), Some(&schedule)).unwrap_err(),
+            ICBError::Unauthorized.into()
+        );
+        
+        // The authority alone only while nobody is scheduled
+        assert!(check_submitter(&unscheduled, &oracle_authority, &oracle_authority, None).is_ok());
+        assert_eq!(
+            check_submitter(&scheduled, &oracle_authority, &oracle_authority, None).unwrap_err(),
+            ICBError::OracleSubmitterRequired.into()
+        );
+        assert_eq!(
+            check_submitter(&unscheduled, &oracle_authority, &submitter, None).unwrap_err(),
+            ICBError::Unauthorized.into()
+        );
+    }
+    
+    #[test]
+    fn test_submission_window() {
+        let interval = DEFAULT_ILI_UPDATE_INTERVAL;
+        let base = 1_700_000_100 - 1_700_000_100 % interval;
+        let schedule = OracleSubmitter {
+            submitter: Pubkey::new_unique(),
+            phase_offset: interval / 2,
+            last_submission: base + interval / 2,
+            bump: 255,
+        };
+        
+        // A schedule is judged by its own window, not the oracle's last write
+        assert_eq!(
+            check_submission_window(Some(&schedule), 0, base + interval, interval).unwrap_err(),
+            ICBError::ILIUpdateTooSoon.into()
+        );
+        assert!(check_submission_window(Some(&schedule), 0, base + interval + interval / 2, interval).is_ok());
+        
+        // Unscheduled writes wait a full interval after the last update
+        assert!(check_submission_window(None, base, base + interval - 1, interval).is_err());
+        assert!(check_submission_window(None, base, base + interval, interval).is_ok());
+    }
+    
+    #[test]
+    fn test_future_last_update_slot_does_not_panic() {
+        let slot = 1_000;
+        
+        assert_eq!(
+            slots_since_update(slot + 1, slot).unwrap_err(),
+            ICBError::LastUpdateSlotInFuture.into()
+        );
+        assert_eq!(slots_since_update(u64::MAX, 0).unwrap_err(), ICBError::LastUpdateSlotInFuture.into());
+        assert_eq!(
+            check_slot_rate_limit(slot + 1, slot).unwrap_err(),
+            ICBError::LastUpdateSlotInFuture.into()
+        );
+        
+        assert_eq!(slots_since_update(slot, slot).unwrap(), 0);
+        assert_eq!(slots_since_update(slot, slot + MIN_SLOT_BUFFER).unwrap(), MIN_SLOT_BUFFER);
+    }
+}
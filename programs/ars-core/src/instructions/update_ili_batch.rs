@@ -0,0 +1,259 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::ICBError;
+use crate::instructions::update_ili::{
+    check_oracle_account, check_slot_rate_limit, check_submission_window, check_submitter,
+    cosigner_keys, deviation_bps, registered_submitters, validate_snapshot, UpdateILI,
+};
+
+/// Record several ILI data points at once, e.g. to backfill history after
+/// oracle downtime. The whole batch is validated before anything is written.
+pub fn handler(ctx: Context<UpdateILI>, snapshots: Vec<ILISnapshot>) -> Result<()> {
+    // ARS-SA-2026-001: Validate agent authentication
+    crate::validate_agent_auth(
+        &ctx.accounts.instructions_sysvar,
+        &ctx.accounts.submitter.key(),
+    )?;
+    
+    ctx.accounts.global_state.check_batch_size(snapshots.len())?;
+    check_oracle_account(&ctx.accounts.global_state, &ctx.accounts.ili_oracle.key())?;
+    check_submitter(
+        &ctx.accounts.global_state,
+        &ctx.accounts.ili_oracle.authority,
+        &ctx.accounts.submitter.key(),
+        ctx.accounts.oracle_submitter.as_deref(),
+    )?;
+    
+    let ili_oracle = &mut ctx.accounts.ili_oracle;
+    let clock = Clock::get()?;
+    
+    // FIX #9: Keep the slot buffer between writes
+    check_slot_rate_limit(ili_oracle.last_update_slot, clock.slot)?;
+    
+    // A batch counts as the submitter's one write for its window
+    check_submission_window(
+        ctx.accounts.oracle_submitter.as_deref(),
+        ili_oracle.last_update,
+        clock.unix_timestamp,
+        ili_oracle.update_interval,
+    )?;
+    
+    validate_batch(
+        ili_oracle.current_ili,
+        ili_oracle.last_update,
+        clock.unix_timestamp,
+        ili_oracle.max_deviation_bps(),
+        ili_oracle.min_ili_value,
+        &snapshots,
+    )?;
+    
+    // A batch moving ILI far from its pre-batch value, in one step or over
+    // several, needs the same cosigner quorum as a single large update
+    let signer_count = OracleSubmitter::signer_count(
+        &ctx.accounts.submitter.key(),
+        &cosigner_keys(ctx.remaining_accounts),
+        &registered_submitters(ctx.remaining_accounts, ctx.program_id),
+    );
+    ctx.accounts.global_state.check_ili_move_quorum(
+        max_batch_deviation_bps(ili_oracle.current_ili, &snapshots)?,
+        signer_count,
+        ili_oracle.degraded,
+    )?;
+    
+    for snapshot in snapshots.iter() {
+        ili_oracle.push_snapshot(*snapshot);
+    }
+    
+    let latest = snapshots[snapshots.len() - 1];
+    ili_oracle.current_ili = latest.ili_value;
+    ili_oracle.last_update = latest.timestamp;
+    ili_oracle.last_update_slot = clock.slot;
+    if let Some(submitter) = ctx.accounts.oracle_submitter.as_mut() {
+        submitter.last_submission = clock.unix_timestamp;
+    }
+    
+    msg!("ILI batch recorded: {} snapshots", snapshots.len());
+    msg!("ILI updated to: {}", latest.ili_value);
+    msg!("Latest timestamp: {}", latest.timestamp);
+    
+    #[cfg(feature = "compute-report")]
+    crate::utils::report_compute_units("update_ili_batch", snapshots.len() as u32);
+    
+    Ok(())
+}
+
+/// Validate a batch of snapshots in order: bounded size, strictly increasing
+/// timestamps after the last recorded update and not in the future, and each
+/// value within bounds and within the deviation limit of the pre-batch ILI.
+/// Measuring from the pre-batch value rather than step by step keeps a
+/// batch of small steps from compounding past the limit.
+pub fn validate_batch(
+    current_ili: u64,
+    last_update: i64,
+    now: i64,
+    max_deviation_bps: u64,
+    min_ili_value: u64,
+    snapshots: &[ILISnapshot],
+) -> Result<()> {
+    require!(
+        !snapshots.is_empty() && snapshots.len() <= MAX_ILI_BATCH_SIZE,
+        ICBError::InvalidBatchSize
+    );
+    
+    let reference_ili = batch_reference_ili(current_ili, snapshots);
+    let mut previous_timestamp = last_update;
+    
+    for snapshot in snapshots {
+        require!(
+            snapshot.timestamp > previous_timestamp && snapshot.timestamp <= now,
+            ICBError::NonMonotonicTimestamp
+        );
+        validate_snapshot(reference_ili, snapshot, max_deviation_bps, min_ili_value)?;
+        
+        previous_timestamp = snapshot.timestamp;
+    }
+    
+    Ok(())
+}
+
+/// Largest move of any value in the batch away from the pre-batch ILI
+pub fn max_batch_deviation_bps(current_ili: u64, snapshots: &[ILISnapshot]) -> Result<u64> {
+    let reference_ili = batch_reference_ili(current_ili, snapshots);
+    let mut max_deviation = 0;
+    
+    for snapshot in snapshots {
+        max_deviation = max_deviation.max(deviation_bps(reference_ili, snapshot.ili_value)?);
+    }
+    
+    Ok(max_deviation)
+}
+
+/// Value the whole batch is measured against: the pre-batch ILI, or on a
+/// fresh oracle with nothing recorded yet, the batch's first entry
+fn batch_reference_ili(current_ili: u64, snapshots: &[ILISnapshot]) -> u64 {
+    match snapshots.first() {
+        Some(first) if current_ili == 0 => first.ili_value,
+        _ => current_ili,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    // Oracle at ILI 1.0 last updated at t=1000, normal deviation limits
+    fn check(now: i64, batch: &[ILISnapshot]) -> Result<()> {
+        validate_batch(1_000_000, 1_000, now, MAX_ILI_DEVIATION_BPS, DEFAULT_MIN_ILI_VALUE, batch)
+    }
+    
+    fn snapshot(timestamp: i64, ili_value: u64) -> ILISnapshot {
+        ILISnapshot {
+            timestamp,
+            ili_value,
+            avg_yield: 500,
+            volatility: 1_000,
+            tvl: 1_000_000,
+        }
+    }
+    
+    #[test]
+    fn test_valid_batch() {
+        let batch = vec![
+            snapshot(1_300, 1_000_000),
+            snapshot(1_600, 1_050_000),
+            snapshot(1_900, 1_020_000),
+        ];
+        assert!(check(2_000, &batch).is_ok());
+    }
+    
+    #[test]
+    fn test_out_of_order_batch_rejected() {
+        let batch = vec![snapshot(1_600, 1_000_000), snapshot(1_300, 1_000_000)];
+        assert_eq!(
+            check(2_000, &batch).unwrap_err(),
+            ICBError::NonMonotonicTimestamp.into()
+        );
+        
+        // Duplicate timestamps are not strictly increasing either
+        let batch = vec![snapshot(1_300, 1_000_000), snapshot(1_300, 1_000_000)];
+        assert!(check(2_000, &batch).is_err());
+        
+        // Entries must come after the last recorded update and not from the future
+        assert!(check(2_000, &[snapshot(1_000, 1_000_000)]).is_err());
+        assert!(check(2_000, &[snapshot(2_001, 1_000_000)]).is_err());
+    }
+    
+    #[test]
+    fn test_bounds_violation_rejects_batch() {
+        let batch = vec![snapshot(1_300, 1_000_000), snapshot(1_600, MAX_ILI_VALUE + 1)];
+        assert_eq!(
+            check(2_000, &batch).unwrap_err(),
+            ICBError::InvalidILIValue.into()
+        );
+        
+        // A jump beyond the deviation limit relative to the previous entry
+        let batch = vec![snapshot(1_300, 1_000_000), snapshot(1_600, 1_500_000)];
+        assert_eq!(
+            check(2_000, &batch).unwrap_err(),
+            ICBError::ILIDeviationTooLarge.into()
+        );
+    }
+    
+    #[test]
+    fn test_batch_size_limits() {
+        assert!(check(2_000, &[]).is_err());
+        
+        let batch: Vec<ILISnapshot> = (1..=MAX_ILI_BATCH_SIZE as i64 + 1)
+            .map(|i| snapshot(1_000 + i, 1_000_000))
+            .collect();
+        assert_eq!(
+            check(5_000, &batch).unwrap_err(),
+            ICBError::InvalidBatchSize.into()
+        );
+        assert!(check(5_000, &batch[..MAX_ILI_BATCH_SIZE]).is_ok());
+    }
+    
+    #[test]
+    fn test_max_batch_deviation() {
+        // 1.0 -> 1.05 -> 1.2: measured from 1.0, the last entry moved 20%
+        let batch = vec![snapshot(1_300, 1_050_000), snapshot(1_600, 1_200_000)];
+        assert_eq!(max_batch_deviation_bps(1_000_000, &batch).unwrap(), 2_000);
+        
+        // A fresh oracle measures the batch from its first entry, 1.05
+        assert_eq!(max_batch_deviation_bps(0, &batch[..1]).unwrap(), 0);
+        assert_eq!(max_batch_deviation_bps(0, &batch).unwrap(), 1_428);
+    }
+    
+    #[test]
+    fn test_large_move_split_across_batch() {
+        // Steps of ~9%, each under the large-move threshold and the
+        // deviation limit on its own
+        let batch = vec![
+            snapshot(1_300, 1_090_000),
+            snapshot(1_600, 1_188_000),
+            snapshot(1_900, 1_295_000),
+        ];
+        
+        // Together they move ILI ~29.5%, past the deviation limit
+        assert_eq!(
+            check(2_000, &batch).unwrap_err(),
+            ICBError::ILIDeviationTooLarge.into()
+        );
+        
+        // Two steps stay within the limit but still need the cosigner quorum
+        let global_state = GlobalState {
+            large_ili_move_bps: DEFAULT_LARGE_ILI_MOVE_BPS,
+            large_move_quorum: DEFAULT_LARGE_MOVE_QUORUM,
+            ..Default::default()
+        };
+        assert!(check(2_000, &batch[..2]).is_ok());
+        let deviation = max_batch_deviation_bps(1_000_000, &batch[..2]).unwrap();
+        assert_eq!(deviation, 1_880);
+        assert_eq!(
+            global_state.check_ili_move_quorum(deviation, 1, false).unwrap_err(),
+            ICBError::LargeILIMoveNeedsCosigner.into()
+        );
+        assert!(global_state.check_ili_move_quorum(deviation, 2, false).is_ok());
+    }
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::ICBError;
+
+#[derive(Accounts)]
+pub struct StageParameters<'info> {
+    #[account(
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump,
+        has_one = authority @ ICBError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = PendingParameters::LEN,
+        seeds = [PENDING_PARAMETERS_SEED],
+        bump
+    )]
+    pub pending_parameters: Account<'info, PendingParameters>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Stage a parameter change behind PARAMETER_TIMELOCK. Values are validated
+/// now against a copy of the global state so a bad change fails up front.
+pub fn stage_parameters(ctx: Context<StageParameters>, params: ParameterUpdate) -> Result<()> {
+    params.apply(&mut (*ctx.accounts.global_state).clone())?;
+    
+    let clock = Clock::get()?;
+    let pending = &mut ctx.accounts.pending_parameters;
+    pending.stage(params, clock.unix_timestamp, ctx.accounts.authority.key())?;
+    pending.bump = ctx.bumps.pending_parameters;
+    
+    msg!("Parameter changes staged, applicable after {}", pending.apply_after);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ApplyStagedParameters<'info> {
+    #[account(
+        mut,
+        seeds = [GLOBAL_STATE_SEED],
+        bump = global_state.bump
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    
+    #[account(
+        mut,
+        seeds = [PENDING_PARAMETERS_SEED],
+        bump = pending_parameters.bump
+    )]
+    pub pending_parameters: Account<'info, PendingParameters>,
+}
+
+/// Apply staged changes once their timelock has passed. Permissionless: the
+/// authority already approved them when staging.
+pub fn apply_staged_parameters(ctx: Context<ApplyStagedParameters>) -> Result<()> {
+    let clock = Clock::get()?;
+    let params = ctx.accounts.pending_parameters.take_due(clock.unix_timestamp)?;
+    params.apply(&mut ctx.accounts.global_state)?;
+    
+    msg!("Staged parameter changes applied");
+    
+    Ok(())
+}
@@ -10,7 +10,8 @@ pub struct VoteOnProposal<'info> {
         mut,
         seeds = [PROPOSAL_SEED, &proposal.id.to_le_bytes()],
         bump = proposal.bump,
-        constraint = proposal.status == ProposalStatus::Active @ ICBError::ProposalNotActive
+        constraint = proposal.status == ProposalStatus::Active @ ICBError::ProposalNotActive,
+        constraint = !proposal.is_escrowed() @ ICBError::EscrowModeMismatch
     )]
     pub proposal: Account<'info, PolicyProposal>,
     
@@ -23,6 +24,13 @@ pub struct VoteOnProposal<'info> {
     )]
     pub vote_record: Account<'info, VoteRecord>,
     
+    #[account(
+        mut,
+        seeds = [AGENT_SEED, agent.key().as_ref()],
+        bump = agent_registry.bump
+    )]
+    pub agent_registry: Account<'info, AgentRegistry>,
+    
     #[account(mut)]
     pub agent: Signer<'info>,
     
@@ -45,18 +53,61 @@ pub fn handler(
         &ctx.accounts.agent.key(),
     )?;
     
+    let clock = Clock::get()?;
+    let ballot = Ballot { prediction, stake_amount, agent_signature };
+    
+    ctx.accounts.vote_record.proposal = ctx.accounts.proposal.key();
+    ctx.accounts.vote_record.bump = ctx.bumps.vote_record;
+    
+    // Stake is only tallied here; stake_and_vote escrows it as well
+    cast_vote(
+        &mut ctx.accounts.proposal,
+        &mut ctx.accounts.vote_record,
+        &mut ctx.accounts.agent_registry,
+        ctx.accounts.agent.key(),
+        &ballot,
+        &clock,
+        |_, _| Ok(()),
+    )?;
+    
+    Ok(())
+}
+
+/// A vote as submitted by an agent
+pub struct Ballot {
+    pub prediction: bool,
+    pub stake_amount: u64,
+    pub agent_signature: [u8; 64],
+}
+
+/// Validate and record a vote, shared by vote_on_proposal and stake_and_vote.
+/// `escrow` receives the locked proposal and the stake amount, and runs only
+/// after every check has passed, so a rejected vote never moves tokens.
+/// The caller sets the vote record's proposal and bump. Returns the vote's
+/// voting power.
+pub fn cast_vote(
+    proposal: &mut PolicyProposal,
+    vote_record: &mut VoteRecord,
+    agent_registry: &mut AgentRegistry,
+    agent: Pubkey,
+    ballot: &Ballot,
+    clock: &Clock,
+    escrow: impl FnOnce(&PolicyProposal, u64) -> Result<()>,
+) -> Result<u64> {
+    let Ballot { prediction, stake_amount, agent_signature } = *ballot;
+    
     require!(stake_amount > 0, ICBError::InvalidStakeAmount);
     
-    let proposal = &mut ctx.accounts.proposal;
-    let vote_record = &mut ctx.accounts.vote_record;
-    let clock = Clock::get()?;
+    // The stored signature must be real and not lifted from an earlier vote
+    agent_registry.record_vote_signature(agent_signature)?;
     
-    // Check if already voted (FIX #5: Prevent duplicate voting)
-    require!(!vote_record.claimed, ICBError::AlreadyVoted);
+    // Check if already voted (FIX #5: Prevent duplicate voting). `claimed`
+    // cannot tell a fresh record from a re-vote, the stored agent can.
+    vote_record.check_can_vote()?;
     
     // Check if voting period is still active
     require!(
-        clock.unix_timestamp < proposal.end_time,
+        proposal.status == ProposalStatus::Active && clock.unix_timestamp < proposal.end_time,
         ICBError::ProposalNotActive
     );
     
@@ -66,33 +117,45 @@ pub fn handler(
     // FIX #2: Use fixed-point sqrt instead of f64 for deterministic computation
     let voting_power = calculate_voting_power(stake_amount)?;
     
-    if prediction {
-        proposal.yes_stake = proposal.yes_stake
-            .checked_add(voting_power)
-            .ok_or(ICBError::ArithmeticOverflow)?;
-    } else {
-        proposal.no_stake = proposal.no_stake
-            .checked_add(voting_power)
-            .ok_or(ICBError::ArithmeticOverflow)?;
-    }
+    // Snapshot minority status against the tally before this vote lands
+    let contrarian = proposal.is_contrarian_vote(prediction, clock.unix_timestamp);
+    
+    // Escrow transfer and tally update run under the proposal lock.
+    // Re-votes were rejected above, so every vote here is a new voter.
+    proposal.with_lock(|proposal| {
+        escrow(proposal, stake_amount)?;
+        proposal.add_vote(prediction, voting_power)?;
+        proposal.add_stake(prediction, stake_amount, contrarian)?;
+        proposal.record_voter()
+    })?;
     
     // Record vote
-    vote_record.proposal = proposal.key();
-    vote_record.agent = ctx.accounts.agent.key();
+    vote_record.agent = agent;
     vote_record.stake_amount = stake_amount;
     vote_record.prediction = prediction;
-    vote_record.timestamp = clock.unix_timestamp;
-    vote_record.claimed = true; // Mark as voted
+    vote_record.stamp_vote(clock);
+    vote_record.claimed = false; // Set once settled by claim_rewards
     vote_record.agent_signature = agent_signature; // FIX #2: Store verified signature
-    vote_record.bump = ctx.bumps.vote_record;
+    vote_record.contrarian = contrarian;
+    
+    agent_registry.record_activity(stake_amount, clock.unix_timestamp)?;
+    
+    emit!(VoteCast {
+        proposal_id: proposal.id,
+        agent,
+        prediction,
+        stake_amount,
+        voted_slot: vote_record.voted_slot,
+        timestamp: vote_record.timestamp,
+    });
     
     msg!("Vote recorded for proposal: {}", proposal.id);
-    msg!("Agent: {}", ctx.accounts.agent.key());
+    msg!("Agent: {}", agent);
     msg!("Prediction: {}", if prediction { "YES" } else { "NO" });
     msg!("Stake: {}", stake_amount);
     msg!("Voting power: {}", voting_power);
     msg!("Total YES stake: {}", proposal.yes_stake);
     msg!("Total NO stake: {}", proposal.no_stake);
     
-    Ok(())
+    Ok(voting_power)
 }
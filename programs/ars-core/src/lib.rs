@@ -9,20 +9,22 @@ pub mod constants;
 pub mod utils;
 pub mod math;
 
+// Anchor-free message construction and voting math, also usable from WASM clients
+pub use ars_common;
+
 use instructions::*;
 use state::*;
-use errors::ICBError;
 
 // ARS-SA-2026-001: Secure Agent Verification
 // This module prevents policy manipulation by illegal agents
-use anchor_lang::solana_program::ed25519_program;
-use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use utils::AuthDiagnosis;
 
 /// Validates that the agent is properly authenticated via Ed25519 signature
 /// 
 /// Security Advisory: ARS-SA-2026-001
 /// This function ensures that:
-/// 1. The previous instruction is an Ed25519 signature verification
+/// 1. One of the `MAX_ED25519_LOOKBACK` preceding instructions is an Ed25519
+///    signature verification
 /// 2. The public key in the signature matches the expected agent
 /// 3. Prevents agent impersonation attacks
 ///
@@ -36,53 +38,16 @@ pub fn validate_agent_auth(
     instructions_sysvar: &AccountInfo,
     expected_agent: &Pubkey,
 ) -> Result<()> {
-    // Load the instructions sysvar
-    let _data = instructions_sysvar.try_borrow_data()?;
-    let current_index = sysvar_instructions::load_current_index_checked(instructions_sysvar)?;
-    
-    // Ensure there is a previous instruction
-    if current_index == 0 {
-        return err!(ICBError::MissingSignatureVerification);
-    }
-    
-    // Load the previous instruction (signature verification)
-    let prev_index = current_index.saturating_sub(1);
-    let prev_ix = sysvar_instructions::load_instruction_at_checked(
-        prev_index as usize,
-        instructions_sysvar,
-    )?;
-    
-    // Verify that the previous instruction is Ed25519 signature verification
-    if prev_ix.program_id != ed25519_program::ID {
-        return err!(ICBError::InvalidSignatureProgram);
-    }
-    
-    // Extract and verify the public key from the Ed25519 instruction data
-    // Ed25519 instruction format:
-    // - Bytes 0-1: Number of signatures (u16, little-endian)
-    // - Bytes 2-3: Padding
-    // - Bytes 4-67: Signature (64 bytes)
-    // - Bytes 68-99: Public key (32 bytes)
-    // - Bytes 100+: Message
+    // Find the agent's signature verification among the preceding
+    // instructions and run the same checks debug_verify_auth reports on
+    let preceding = utils::load_preceding_instructions(instructions_sysvar, utils::MAX_ED25519_LOOKBACK)?;
+    let diagnosis = utils::diagnose_agent_auth_window(&preceding, expected_agent, None);
     
-    if prev_ix.data.len() < 100 {
-        return err!(ICBError::SignatureVerificationFailed);
+    if diagnosis == AuthDiagnosis::PubkeyMismatch {
+        msg!("Agent mismatch: expected {:?}", expected_agent);
     }
-    
-    // Extract public key (bytes 68-99, but we use 16-48 for the actual key data)
-    let pubkey_offset = 16; // Adjusted offset for Ed25519 instruction format
-    let pubkey_end = pubkey_offset + 32;
-    
-    if prev_ix.data.len() < pubkey_end {
-        return err!(ICBError::SignatureVerificationFailed);
-    }
-    
-    let pubkey_data = &prev_ix.data[pubkey_offset..pubkey_end];
-    
-    // Verify that the public key matches the expected agent
-    if pubkey_data != expected_agent.as_ref() {
-        msg!("Agent mismatch: expected {:?}, got {:?}", expected_agent, pubkey_data);
-        return err!(ICBError::AgentMismatch);
+    if let Some(error) = diagnosis.to_error() {
+        return Err(error);
     }
     
     msg!("Agent authentication successful for: {:?}", expected_agent);
@@ -100,6 +65,7 @@ pub mod ars_core {
         mint_burn_cap_bps: u16,
         stability_fee_bps: u16,
         vhr_threshold: u16,
+        ili_decimals: u8,
     ) -> Result<()> {
         instructions::initialize::handler(
             ctx,
@@ -107,6 +73,7 @@ pub mod ars_core {
             mint_burn_cap_bps,
             stability_fee_bps,
             vhr_threshold,
+            ili_decimals,
         )
     }
 
@@ -115,6 +82,50 @@ pub mod ars_core {
         instructions::initialize::set_reserve_vault(ctx)
     }
 
+    /// Initialize the protocol with its reserve vault and ICU mint in one step
+    pub fn initialize_full(
+        ctx: Context<InitializeFull>,
+        epoch_duration: i64,
+        mint_burn_cap_bps: u16,
+        stability_fee_bps: u16,
+        vhr_threshold: u16,
+        ili_decimals: u8,
+    ) -> Result<()> {
+        instructions::initialize::initialize_full(
+            ctx,
+            epoch_duration,
+            mint_burn_cap_bps,
+            stability_fee_bps,
+            vhr_threshold,
+            ili_decimals,
+        )
+    }
+
+    /// Stage governance-tunable parameter changes behind the parameter timelock
+    pub fn update_parameters(ctx: Context<StageParameters>, params: ParameterUpdate) -> Result<()> {
+        instructions::update_parameters::stage_parameters(ctx, params)
+    }
+
+    /// Apply staged parameter changes once their timelock has passed
+    pub fn apply_staged_parameters(ctx: Context<ApplyStagedParameters>) -> Result<()> {
+        instructions::update_parameters::apply_staged_parameters(ctx)
+    }
+
+    /// Return staged parameter changes and when they apply, or None
+    pub fn query_pending_parameters(ctx: Context<QueryPendingParameters>) -> Result<Option<PendingParametersView>> {
+        instructions::query_pending_parameters::handler(ctx)
+    }
+
+    /// Migrate GlobalState to the current layout version
+    pub fn migrate_global_state(ctx: Context<MigrateGlobalState>) -> Result<()> {
+        instructions::migrate_global_state::handler(ctx)
+    }
+
+    /// Rewrite the ILI oracle in the current layout, carrying over its history
+    pub fn migrate_oracle(ctx: Context<MigrateOracle>) -> Result<()> {
+        instructions::migrate_oracle::handler(ctx)
+    }
+
     /// Update the ILI oracle value
     pub fn update_ili(
         ctx: Context<UpdateILI>,
@@ -124,6 +135,21 @@ pub mod ars_core {
         tvl: u64,
     ) -> Result<()> {
         instructions::update_ili::handler(ctx, ili_value, avg_yield, volatility, tvl)
+            .map_err(utils::report_rejection)
+    }
+
+    /// Record a batch of ILI snapshots to backfill history after downtime
+    pub fn update_ili_batch(
+        ctx: Context<UpdateILI>,
+        snapshots: Vec<ILISnapshot>,
+    ) -> Result<()> {
+        instructions::update_ili_batch::handler(ctx, snapshots)
+            .map_err(utils::report_rejection)
+    }
+
+    /// Grow or shrink the ILI history ring buffer
+    pub fn resize_ili_history(ctx: Context<ResizeILIHistory>, new_capacity: u16) -> Result<()> {
+        instructions::resize_ili_history::handler(ctx, new_capacity)
     }
 
     /// Query the current ILI value
@@ -131,14 +157,80 @@ pub mod ars_core {
         instructions::query_ili::handler(ctx)
     }
 
+    /// Report which agent authentication check the preceding Ed25519
+    /// instruction fails, without rejecting the transaction
+    pub fn debug_verify_auth(
+        ctx: Context<DebugVerifyAuth>,
+        expected_agent: Pubkey,
+        expected_message: Option<Vec<u8>>,
+    ) -> Result<AuthDiagnosis> {
+        instructions::debug_verify_auth::handler(ctx, expected_agent, expected_message)
+    }
+
+    /// Return the earliest pending keeper action across the protocol
+    pub fn query_next_action<'info>(
+        ctx: Context<'_, '_, 'info, 'info, QueryNextAction<'info>>,
+        epoch_start_time: Option<i64>,
+    ) -> Result<Option<NextAction>> {
+        instructions::query_next_action::handler(ctx, epoch_start_time)
+    }
+
+    /// Return a proposal's live tally, quorum and projected outcome
+    pub fn query_proposal_status(ctx: Context<QueryProposalStatus>) -> Result<ProposalStatusView> {
+        instructions::query_proposal_status::handler(ctx)
+    }
+
+    /// Return the moving average of voting power cast per finalized proposal
+    pub fn query_turnout(ctx: Context<QueryTurnout>) -> Result<u64> {
+        instructions::query_turnout::handler(ctx)
+    }
+
+    /// Return seconds left in a proposal's voting period and whether voting is open
+    pub fn query_time_remaining(ctx: Context<QueryTimeRemaining>) -> Result<TimeRemainingView> {
+        instructions::query_time_remaining::handler(ctx)
+    }
+
+    /// Return an agent's stats, nonce and number of unsettled votes
+    pub fn query_agent_summary<'info>(
+        ctx: Context<'_, '_, 'info, 'info, QueryAgentSummary<'info>>,
+    ) -> Result<AgentSummary> {
+        instructions::query_agent_summary::handler(ctx)
+    }
+
+    /// Return the recorded outcomes of proposals `start_id..start_id + count`
+    pub fn query_proposal_history(
+        ctx: Context<QueryProposalHistory>,
+        start_id: u64,
+        count: u16,
+    ) -> Result<Vec<ProposalOutcome>> {
+        instructions::query_proposal_history::handler(ctx, start_id, count)
+    }
+
+    /// Register an agent and its type (authority only)
+    pub fn register_agent(ctx: Context<RegisterAgent>, agent_type: AgentType) -> Result<()> {
+        instructions::register_agent::handler(ctx, agent_type)
+    }
+
     /// Create a new policy proposal
     pub fn create_proposal(
         ctx: Context<CreateProposal>,
         policy_type: PolicyType,
         policy_params: Vec<u8>,
         duration: i64,
+        depends_on: Option<u64>,
     ) -> Result<()> {
-        instructions::create_proposal::handler(ctx, policy_type, policy_params, duration)
+        instructions::create_proposal::handler(ctx, policy_type, policy_params, duration, depends_on)
+            .map_err(utils::report_rejection)
+    }
+
+    /// Allocate and fund the next proposal account ahead of create_proposal
+    pub fn preallocate_proposal(ctx: Context<PreallocateProposal>) -> Result<()> {
+        instructions::preallocate_proposal::handler(ctx)
+    }
+
+    /// Advance the proposal counter to a free id after a migration
+    pub fn reconcile_counter(ctx: Context<ReconcileCounter>, expected_next: u64) -> Result<()> {
+        instructions::reconcile_counter::handler(ctx, expected_next)
     }
 
     /// Vote on a policy proposal (FIX #2, #5)
@@ -149,11 +241,101 @@ pub mod ars_core {
         agent_signature: [u8; 64],
     ) -> Result<()> {
         instructions::vote_on_proposal::handler(ctx, prediction, stake_amount, agent_signature)
+            .map_err(utils::report_rejection)
+    }
+
+    /// Escrow a stake and vote with it in one instruction
+    pub fn stake_and_vote(
+        ctx: Context<StakeAndVote>,
+        prediction: bool,
+        stake_amount: u64,
+        agent_signature: [u8; 64],
+    ) -> Result<()> {
+        instructions::stake_and_vote::handler(ctx, prediction, stake_amount, agent_signature)
+            .map_err(utils::report_rejection)
+    }
+
+    /// Refund a finished proposal's deposit if it met quorum, else forfeit it to the reserve
+    pub fn settle_deposit(ctx: Context<SettleDeposit>) -> Result<()> {
+        instructions::settle_deposit::handler(ctx)
+    }
+
+    /// Settle a vote on a finished proposal: reward, slash or refund its stake
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<u64> {
+        instructions::claim_rewards::handler(ctx)
+    }
+
+    /// Close settled proposals and claimed vote records in bulk, refunding rent to their payers
+    pub fn sweep_closed<'info>(ctx: Context<'_, '_, 'info, 'info, SweepClosed<'info>>) -> Result<()> {
+        instructions::sweep_closed::handler(ctx)
+    }
+
+    /// Close a claimed vote record on a finished proposal and reclaim its rent
+    pub fn close_vote_record(ctx: Context<CloseVoteRecord>) -> Result<()> {
+        instructions::close_vote_record::handler(ctx)
     }
 
     /// Execute an approved proposal (FIX #3, #8)
     pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
         instructions::execute_proposal::handler(ctx)
+            .map_err(utils::report_rejection)
+    }
+
+    /// Create the ring buffer that records finalized proposal outcomes
+    pub fn initialize_proposal_history(ctx: Context<InitializeProposalHistory>) -> Result<()> {
+        instructions::proposal_history::initialize_proposal_history(ctx)
+    }
+
+    /// Extend the voting period of a proposal that is below quorum
+    pub fn extend_proposal(ctx: Context<ExtendProposal>, additional_secs: i64) -> Result<()> {
+        instructions::extend_proposal::handler(ctx, additional_secs)
+    }
+
+    /// Allow a keeper to execute proposals
+    pub fn add_executor(ctx: Context<ManageExecutors>, executor: Pubkey) -> Result<()> {
+        instructions::executors::add_executor(ctx, executor)
+    }
+
+    /// Revoke a keeper's permission to execute proposals
+    pub fn remove_executor(ctx: Context<ManageExecutors>, executor: Pubkey) -> Result<()> {
+        instructions::executors::remove_executor(ctx, executor)
+    }
+
+    /// Register an ILI submitter with a phase-offset schedule, up to max_submitters
+    pub fn add_oracle_submitter(
+        ctx: Context<AddOracleSubmitter>,
+        submitter: Pubkey,
+        phase_offset: i64,
+    ) -> Result<()> {
+        instructions::oracle_submitter::add_oracle_submitter(ctx, submitter, phase_offset)
+    }
+
+    /// Deregister an ILI submitter and reclaim its schedule account
+    pub fn remove_oracle_submitter(ctx: Context<RemoveOracleSubmitter>) -> Result<()> {
+        instructions::oracle_submitter::remove_oracle_submitter(ctx)
+    }
+
+    /// Stagger a registered submitter's ILI update windows by a fixed phase offset
+    pub fn set_submitter_phase_offset(
+        ctx: Context<SetSubmitterPhaseOffset>,
+        phase_offset: i64,
+    ) -> Result<()> {
+        instructions::oracle_submitter::set_submitter_phase_offset(ctx, phase_offset)
+    }
+
+    /// Set or clear the guardian allowed to veto proposals
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Option<Pubkey>) -> Result<()> {
+        instructions::guardian::set_guardian(ctx, guardian)
+    }
+
+    /// Cancel an Active or Passed proposal (guardian only)
+    pub fn veto_proposal(ctx: Context<VetoProposal>) -> Result<()> {
+        instructions::guardian::veto_proposal(ctx)
+    }
+
+    /// Fail an Active proposal left unfinalized past its maximum lifetime
+    pub fn force_expire(ctx: Context<ForceExpire>) -> Result<()> {
+        instructions::force_expire::handler(ctx)
     }
 
     /// Request circuit breaker activation (FIX #7)
@@ -170,4 +352,28 @@ pub mod ars_core {
     pub fn deactivate_circuit_breaker(ctx: Context<DeactivateCircuitBreaker>) -> Result<()> {
         instructions::circuit_breaker::deactivate_circuit_breaker(ctx)
     }
+
+    /// Clear the circuit breaker once VHR and the oracle have recovered, if auto-recovery is on
+    pub fn try_auto_deactivate(ctx: Context<TryAutoDeactivate>) -> Result<()> {
+        instructions::circuit_breaker::try_auto_deactivate(ctx)
+    }
+
+    /// Toggle oracle degraded mode (relaxed deviation, stricter staleness)
+    pub fn set_oracle_degraded(ctx: Context<SetOracleDegraded>, degraded: bool) -> Result<()> {
+        instructions::set_oracle_degraded::handler(ctx, degraded)
+    }
+
+    /// Set the lowest ILI value the oracle accepts
+    pub fn set_min_ili_value(ctx: Context<SetMinILIValue>, min_ili_value: u64) -> Result<()> {
+        instructions::set_min_ili_value::handler(ctx, min_ili_value)
+    }
+
+    /// Return the message bytes an agent must sign for an action
+    pub fn build_signing_payload(
+        ctx: Context<BuildSigningPayload>,
+        action: SigningAction,
+        timestamp: i64,
+    ) -> Result<Vec<u8>> {
+        instructions::build_signing_payload::handler(ctx, action, timestamp)
+    }
 }
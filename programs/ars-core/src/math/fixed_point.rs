@@ -1,6 +1,8 @@
 // FIX #2: Fixed-Point Arithmetic for Quadratic Staking
 use anchor_lang::prelude::*;
+use anchor_lang::error::Error;
 use crate::errors::ICBError;
+use ars_common::VotingMathError;
 
 /// Precision for fixed-point arithmetic (9 decimals, Solana native)
 pub const PRECISION: u64 = 1_000_000_000;
@@ -8,43 +10,10 @@ pub const PRECISION: u64 = 1_000_000_000;
 /// Calculate square root using fixed-point arithmetic
 /// Uses Babylonian method (Newton's method) for sqrt calculation
 /// 
-/// This replaces floating point sqrt to prevent precision loss and manipulation
+/// This replaces floating point sqrt to prevent precision loss and manipulation.
+/// The implementation lives in `ars_common` so clients compute identical values.
 pub fn sqrt_fixed(x: u64) -> Result<u64> {
-    if x == 0 {
-        return Ok(0);
-    }
-    
-    if x == 1 {
-        return Ok(1);
-    }
-    
-    // For small numbers, use direct calculation
-    if x < 4 {
-        return Ok(1);
-    }
-    
-    // Initial guess: x / 2
-    let mut z = x / 2;
-    let mut y = x;
-    
-    // Iterate until convergence (max 20 iterations for safety)
-    for _ in 0..20 {
-        if z >= y {
-            break;
-        }
-        y = z;
-        
-        // Newton's method: z = (x/z + z) / 2
-        let x_div_z = x.checked_div(z)
-            .ok_or(ICBError::MathOverflow)?;
-        
-        z = x_div_z.checked_add(z)
-            .ok_or(ICBError::MathOverflow)?
-            .checked_div(2)
-            .ok_or(ICBError::MathOverflow)?;
-    }
-    
-    Ok(y)
+    ars_common::sqrt_fixed(x).map_err(voting_math_error)
 }
 
 /// Calculate voting power using quadratic staking
@@ -52,13 +21,33 @@ pub fn sqrt_fixed(x: u64) -> Result<u64> {
 /// 
 /// This prevents whale dominance while rewarding larger stakes
 pub fn calculate_voting_power(stake_amount: u64) -> Result<u64> {
-    require!(stake_amount > 0, ICBError::InvalidStakeAmount);
-    
-    // Calculate sqrt using fixed-point arithmetic
-    let voting_power = sqrt_fixed(stake_amount)?;
-    
-    // Ensure minimum voting power of 1
-    Ok(voting_power.max(1))
+    ars_common::calculate_voting_power(stake_amount).map_err(voting_math_error)
+}
+
+fn voting_math_error(error: VotingMathError) -> Error {
+    match error {
+        VotingMathError::ZeroStake => error!(ICBError::InvalidStakeAmount),
+        VotingMathError::Overflow => error!(ICBError::MathOverflow),
+    }
+}
+
+/// `a * b / denominator` rounded down, computed in u128. Use for amounts paid
+/// out to users (rewards, redistributed slashes) so the protocol never
+/// distributes more than it holds.
+pub fn mul_div_floor(a: u64, b: u64, denominator: u64) -> Result<u64> {
+    require!(denominator > 0, ICBError::DivisionByZero);
+    let quotient = (a as u128) * (b as u128) / denominator as u128;
+    u64::try_from(quotient).map_err(|_| error!(ICBError::MathOverflow))
+}
+
+/// `a * b / denominator` rounded up, computed in u128. Use for amounts charged
+/// to users (fees) so rounding never undercharges the protocol.
+pub fn mul_div_ceil(a: u64, b: u64, denominator: u64) -> Result<u64> {
+    require!(denominator > 0, ICBError::DivisionByZero);
+    let product = (a as u128) * (b as u128);
+    let denominator = denominator as u128;
+    let quotient = product.div_ceil(denominator);
+    u64::try_from(quotient).map_err(|_| error!(ICBError::MathOverflow))
 }
 
 /// Checked multiplication with overflow protection
@@ -123,6 +112,11 @@ mod tests {
         
         // Large stake
         assert_eq!(calculate_voting_power(1000000).unwrap(), 1000);
+        
+        // Largest possible stakes neither overflow nor stop short of the root
+        assert_eq!(calculate_voting_power(u64::MAX).unwrap(), u32::MAX as u64);
+        assert_eq!(calculate_voting_power(u64::MAX - 1).unwrap(), u32::MAX as u64);
+        assert_eq!(calculate_voting_power(1 << 63).unwrap(), 3_037_000_499);
     }
     
     #[test]
@@ -135,4 +129,37 @@ mod tests {
         // But not linearly (quadratic dampening)
         assert!(vp2 < vp1 * 4); // 4x stake doesn't give 4x power
     }
+    
+    #[test]
+    fn test_mul_div_rounding() {
+        // Exact divisions agree
+        assert_eq!(mul_div_floor(1_000, 1_000, 10_000).unwrap(), 100);
+        assert_eq!(mul_div_ceil(1_000, 1_000, 10_000).unwrap(), 100);
+        
+        // Non-exact divisions differ by exactly one
+        for (a, b, d) in [(1u64, 1u64, 3u64), (999, 10, 10_000), (12_345, 1_000, 10_000), (u64::MAX, 1, 2)] {
+            let floor = mul_div_floor(a, b, d).unwrap();
+            let ceil = mul_div_ceil(a, b, d).unwrap();
+            assert_eq!(ceil, floor + 1);
+        }
+        
+        // Intermediate product may exceed u64 as long as the result fits
+        assert_eq!(mul_div_floor(u64::MAX, u64::MAX, u64::MAX).unwrap(), u64::MAX);
+        assert_eq!(mul_div_ceil(u64::MAX, 3, 2).unwrap_err(), ICBError::MathOverflow.into());
+        assert_eq!(mul_div_floor(1, 1, 0).unwrap_err(), ICBError::DivisionByZero.into());
+    }
+    
+    #[test]
+    fn test_voting_power_matches_client_crate() {
+        for stake in [1u64, 2, 3, 4, 99, 100, 12_345, 1_000_000] {
+            assert_eq!(
+                calculate_voting_power(stake).unwrap(),
+                ars_common::calculate_voting_power(stake).unwrap()
+            );
+        }
+        assert_eq!(
+            calculate_voting_power(0).unwrap_err(),
+            ICBError::InvalidStakeAmount.into()
+        );
+    }
 }
@@ -1,7 +1,43 @@
 use anchor_lang::prelude::*;
+use crate::constants::*;
+use crate::errors::ICBError;
+use crate::math::{mul_div_ceil, mul_div_floor};
+use crate::utils::acquire_lock;
+
+/// Collateral ratio in basis points (15000 = 150%), only constructible
+/// within [MIN_VHR_THRESHOLD, MAX_VHR_THRESHOLD]. Serialized as a bare u16,
+/// so it is a drop-in for the field it replaced.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ICRatio(u16);
+
+impl ICRatio {
+    pub fn new(bps: u16) -> Result<Self> {
+        require!(
+            (MIN_VHR_THRESHOLD..=MAX_VHR_THRESHOLD).contains(&bps),
+            ICBError::InvalidVHRThreshold
+        );
+        Ok(Self(bps))
+    }
+    
+    pub fn bps(self) -> u16 {
+        self.0
+    }
+    
+    /// Whether a reserve at `vhr` bps is collateralized to this ratio
+    pub fn is_met_by(self, vhr: u16) -> bool {
+        vhr >= self.0
+    }
+}
+
+impl Default for ICRatio {
+    fn default() -> Self {
+        Self(DEFAULT_VHR_THRESHOLD)
+    }
+}
 
 /// Global state for the ARS protocol
 #[account]
+#[derive(Default)]
 pub struct GlobalState {
     pub authority: Pubkey,
     pub ili_oracle: Pubkey,
@@ -10,12 +46,40 @@ pub struct GlobalState {
     pub epoch_duration: i64,        // 24 hours in seconds
     pub mint_burn_cap_bps: u16,     // 200 = 2%
     pub stability_fee_bps: u16,     // 10 = 0.1%
-    pub vhr_threshold: u16,         // 15000 = 150%
+    pub vhr_threshold: ICRatio,     // 15000 = 150%
     pub circuit_breaker_active: bool,
     pub proposal_counter: u64,      // FIX #1: Monotonic counter for proposal IDs
     pub circuit_breaker_requested_at: i64, // FIX #7: Timelock for circuit breaker
     pub last_update_slot: u64,      // FIX #9: Slot-based validation
     pub bump: u8,
+    // Fields below are appended after `bump` to keep the original layout prefix
+    pub agent_action_cooldown: i64, // Minimum seconds between an agent's proposals
+    pub version: u8,                // Layout version, see migrate_global_state
+    pub breaker_exempt_policies: u8, // PolicyType bitmask still proposable under the breaker
+    pub active_proposals: Vec<u64>, // Ids of proposals still in voting, for keepers
+    pub execution_max_oracle_age: i64, // Max ILI age when executing price-sensitive policies
+    pub finalization_grace_secs: i64, // Delay after end_time before a proposal can be finalized
+    pub min_quorum_stake: u64,      // Minimum yes + no voting power for a proposal to pass (0 = off)
+    pub executor_allowlist: Vec<Pubkey>, // Keepers allowed to execute proposals besides the authority
+    pub oracle_layout_version: u8,  // ILIOracle layout the oracle account is stored in, see migrate_oracle
+    pub min_unique_voters: u32,     // Minimum distinct voters for a proposal to pass (0 = off)
+    pub max_active_proposals: u8,   // Governance limit on proposals in voting, at most MAX_ACTIVE_PROPOSALS
+    pub guardian: Option<Pubkey>,   // May veto proposals but has no other powers
+    pub max_batch_size: u8,         // Governance limit on items per batch instruction, at most MAX_ILI_BATCH_SIZE
+    pub max_stale_secs: i64,        // Time after end_time before the authority may force-expire an Active proposal
+    pub contrarian_bonus_bps: u16,  // Extra reward for early minority-side winners (0 = off)
+    pub large_ili_move_bps: u16,    // ILI moves above this need large_move_quorum submitters
+    pub large_move_quorum: u8,      // Submitters (the writer included) required for a large ILI move
+    pub min_reward_payout: u64,     // Reward shares below this are retained as dust (0 = off)
+    pub last_deactivated_at: i64,   // When the circuit breaker was last deactivated (0 = never)
+    pub breaker_rerequest_cooldown: i64, // Seconds after deactivation before the breaker may be requested again
+    pub max_signature_age: i64,     // Oldest signed-action timestamp accepted, in seconds
+    pub turnout_ema: u64,           // Moving average of voting power cast per finalized proposal
+    pub turnout_samples: u64,       // Finalized proposals folded into turnout_ema
+    pub auto_recover: bool,         // Anyone may clear the breaker once VHR and the oracle recover
+    pub proposal_deposit: u64,      // ICU escrowed by create_proposal, refunded on quorum (0 = off)
+    pub oracle_submitter_count: u16, // OracleSubmitter accounts registered by add_oracle_submitter
+    pub max_submitters: u16,        // Governance limit on oracle submitters, at most MAX_ORACLE_SUBMITTERS
 }
 
 impl GlobalState {
@@ -32,30 +96,536 @@ impl GlobalState {
         8 +  // proposal_counter (FIX #1)
         8 +  // circuit_breaker_requested_at (FIX #7)
         8 +  // last_update_slot (FIX #9)
-        1;   // bump
+        1 +  // bump
+        8 +  // agent_action_cooldown
+        1 +  // version
+        1 +  // breaker_exempt_policies
+        4 + MAX_ACTIVE_PROPOSALS * 8 + // active_proposals (vec)
+        8 +  // execution_max_oracle_age
+        8 +  // finalization_grace_secs
+        8 +  // min_quorum_stake
+        4 + MAX_EXECUTORS * 32 + // executor_allowlist (vec)
+        1 +  // oracle_layout_version
+        4 +  // min_unique_voters
+        1 +  // max_active_proposals
+        1 + 32 + // guardian (option)
+        1 +  // max_batch_size
+        8 +  // max_stale_secs
+        2 +  // contrarian_bonus_bps
+        2 +  // large_ili_move_bps
+        1 +  // large_move_quorum
+        8 +  // min_reward_payout
+        8 +  // last_deactivated_at
+        8 +  // breaker_rerequest_cooldown
+        8 +  // max_signature_age
+        8 +  // turnout_ema
+        8 +  // turnout_samples
+        1 +  // auto_recover
+        8 +  // proposal_deposit
+        2 +  // oracle_submitter_count
+        2;   // max_submitters
+    
+    /// Layout version written by this program. Bump it whenever fields are
+    /// appended, give them defaults in `migrate_from` and read them in
+    /// `migrate_global_state::deserialize_version`.
+    pub const CURRENT_VERSION: u8 = 24;
+    
+    /// Initialize fields introduced after `from_version` with their defaults
+    pub fn migrate_from(&mut self, from_version: u8) {
+        if from_version < 1 {
+            self.agent_action_cooldown = DEFAULT_AGENT_ACTION_COOLDOWN;
+        }
+        if from_version < 2 {
+            self.breaker_exempt_policies = 0;
+        }
+        if from_version < 3 {
+            self.active_proposals = Vec::new();
+        }
+        if from_version < 4 {
+            self.execution_max_oracle_age = DEFAULT_EXECUTION_MAX_ORACLE_AGE;
+        }
+        if from_version < 5 {
+            self.finalization_grace_secs = DEFAULT_FINALIZATION_GRACE_SECS;
+        }
+        if from_version < 6 {
+            self.min_quorum_stake = DEFAULT_MIN_QUORUM_STAKE;
+        }
+        if from_version < 7 {
+            self.executor_allowlist = Vec::new();
+        }
+        if from_version < 8 && self.proposal_counter == NO_PROPOSAL_ID {
+            // No proposals yet; skip the reserved id
+            self.proposal_counter = FIRST_PROPOSAL_ID;
+        }
+        if from_version < 9 {
+            // Oracles created before layout tracking are in the legacy layout
+            self.oracle_layout_version = 0;
+        }
+        if from_version < 10 {
+            self.min_unique_voters = DEFAULT_MIN_UNIQUE_VOTERS;
+        }
+        if from_version < 11 {
+            self.max_active_proposals = MAX_ACTIVE_PROPOSALS as u8;
+        }
+        if from_version < 12 {
+            self.guardian = None;
+        }
+        if from_version < 13 {
+            self.max_batch_size = MAX_ILI_BATCH_SIZE as u8;
+        }
+        if from_version < 14 {
+            self.max_stale_secs = DEFAULT_MAX_PROPOSAL_STALE_SECS;
+        }
+        if from_version < 15 {
+            self.contrarian_bonus_bps = DEFAULT_CONTRARIAN_BONUS_BPS;
+        }
+        if from_version < 16 {
+            self.large_ili_move_bps = DEFAULT_LARGE_ILI_MOVE_BPS;
+            self.large_move_quorum = DEFAULT_LARGE_MOVE_QUORUM;
+        }
+        if from_version < 17 {
+            self.min_reward_payout = DEFAULT_MIN_REWARD_PAYOUT;
+        }
+        if from_version < 18 {
+            self.last_deactivated_at = 0;
+            self.breaker_rerequest_cooldown = DEFAULT_BREAKER_REREQUEST_COOLDOWN;
+        }
+        if from_version < 19 {
+            self.max_signature_age = MAX_SIGNATURE_AGE;
+        }
+        if from_version < 20 {
+            self.turnout_ema = 0;
+            self.turnout_samples = 0;
+        }
+        if from_version < 21 {
+            self.auto_recover = false;
+        }
+        if from_version < 22 {
+            self.proposal_deposit = 0;
+        }
+        if from_version < 23 {
+            // Submitters scheduled before the cap existed are not counted
+            self.oracle_submitter_count = 0;
+            self.max_submitters = DEFAULT_MAX_SUBMITTERS;
+        }
+        self.version = Self::CURRENT_VERSION;
+    }
+    
+    /// Fold a finalized proposal's turnout into the moving average, weighting
+    /// the new value by TURNOUT_EMA_ALPHA_BPS. The first sample seeds it.
+    pub fn record_turnout(&mut self, turnout: u64) -> Result<()> {
+        self.turnout_ema = if self.turnout_samples == 0 {
+            turnout
+        } else {
+            let alpha = TURNOUT_EMA_ALPHA_BPS as u128;
+            let weighted = (self.turnout_ema as u128) * (BPS_DENOMINATOR as u128 - alpha)
+                + (turnout as u128) * alpha;
+            (weighted / BPS_DENOMINATOR as u128) as u64
+        };
+        self.turnout_samples = self.turnout_samples
+            .checked_add(1)
+            .ok_or(ICBError::ArithmeticOverflow)?;
+        Ok(())
+    }
+    
+    /// Reject a new circuit breaker request until the cooldown after the last
+    /// deactivation has passed, so the breaker cannot be flapped
+    pub fn check_breaker_rerequest(&self, now: i64) -> Result<()> {
+        if self.last_deactivated_at != 0 {
+            require!(
+                now >= self.last_deactivated_at.saturating_add(self.breaker_rerequest_cooldown),
+                ICBError::CircuitBreakerCooldownActive
+            );
+        }
+        Ok(())
+    }
+    
+    /// Allow try_auto_deactivate only when auto-recovery is enabled, the
+    /// breaker is active and neither trigger still holds: the reserve VHR is
+    /// back at vhr_threshold and the oracle is fresh.
+    pub fn check_breaker_recovered(&self, reserve_vhr: u16, oracle_stale: bool) -> Result<()> {
+        require!(self.auto_recover, ICBError::AutoRecoverDisabled);
+        require!(self.circuit_breaker_active, ICBError::CircuitBreakerNotActive);
+        require!(
+            self.vhr_threshold.is_met_by(reserve_vhr) && !oracle_stale,
+            ICBError::BreakerConditionPersists
+        );
+        Ok(())
+    }
+    
+    /// Clear the circuit breaker and any pending request, starting the
+    /// re-request cooldown
+    pub fn deactivate_breaker(&mut self, now: i64) {
+        self.circuit_breaker_active = false;
+        self.circuit_breaker_requested_at = 0; // Reset request
+        self.last_deactivated_at = now;
+    }
+    
+    /// Whether the active circuit breaker freezes `policy_type`. Exempt policy
+    /// types are never blocked.
+    pub fn is_blocked_by_breaker(&self, policy_type: &PolicyType) -> bool {
+        self.circuit_breaker_active && self.breaker_exempt_policies & policy_type.bit() == 0
+    }
+    
+    /// Whether a proposal of `policy_type` may be created right now. While the
+    /// circuit breaker is active only exempt policy types are allowed.
+    pub fn can_propose(&self, policy_type: &PolicyType) -> bool {
+        !self.is_blocked_by_breaker(policy_type)
+    }
+    
+    /// Stability fee owed on `amount`, rounded up so the protocol is never
+    /// undercharged
+    pub fn stability_fee(&self, amount: u64) -> Result<u64> {
+        mul_div_ceil(amount, self.stability_fee_bps as u64, BPS_DENOMINATOR as u64)
+    }
+    
+    /// Policies that act on the reserve can only be proposed once
+    /// set_reserve_vault has run
+    pub fn is_reserve_ready_for(&self, policy_type: &PolicyType) -> bool {
+        !policy_type.requires_reserve() || self.reserve_vault != Pubkey::default()
+    }
+    
+    /// The authority and allowlisted keepers may execute proposals
+    pub fn is_executor(&self, key: &Pubkey) -> bool {
+        self.authority == *key || self.executor_allowlist.contains(key)
+    }
+    
+    /// Require `key` to be an executor. On failure the error carries the
+    /// rejected key and the authority so both show up in the logs.
+    pub fn check_executor(&self, key: &Pubkey) -> Result<()> {
+        if self.is_executor(key) {
+            return Ok(());
+        }
+        Err(error!(ICBError::Unauthorized).with_pubkeys((*key, self.authority)))
+    }
+    
+    /// Whether `key` is the configured guardian
+    pub fn is_guardian(&self, key: &Pubkey) -> bool {
+        self.guardian == Some(*key)
+    }
+    
+    /// Set or clear the guardian. It must be a separate key from the
+    /// authority so blocking and acting powers stay split.
+    pub fn set_guardian(&mut self, guardian: Option<Pubkey>) -> Result<()> {
+        require!(guardian != Some(self.authority), ICBError::InvalidParameter);
+        self.guardian = guardian;
+        Ok(())
+    }
+    
+    /// Allow `executor` to execute proposals
+    pub fn add_executor(&mut self, executor: Pubkey) -> Result<()> {
+        require!(
+            !self.executor_allowlist.contains(&executor),
+            ICBError::ExecutorAlreadyAllowed
+        );
+        require!(
+            self.executor_allowlist.len() < MAX_EXECUTORS,
+            ICBError::ExecutorAllowlistFull
+        );
+        self.executor_allowlist.push(executor);
+        Ok(())
+    }
+    
+    /// Revoke `executor`'s permission to execute proposals
+    pub fn remove_executor(&mut self, executor: &Pubkey) -> Result<()> {
+        let index = self.executor_allowlist
+            .iter()
+            .position(|key| key == executor)
+            .ok_or(ICBError::ExecutorNotFound)?;
+        self.executor_allowlist.swap_remove(index);
+        Ok(())
+    }
+    
+    /// Count a newly registered oracle submitter, rejecting one beyond
+    /// max_submitters
+    pub fn register_submitter(&mut self) -> Result<()> {
+        require!(
+            self.oracle_submitter_count < self.max_submitters,
+            ICBError::OracleSubmitterLimitReached
+        );
+        self.oracle_submitter_count += 1;
+        Ok(())
+    }
+    
+    /// Release a removed oracle submitter's slot
+    pub fn unregister_submitter(&mut self) {
+        self.oracle_submitter_count = self.oracle_submitter_count.saturating_sub(1);
+    }
+    
+    /// Require `large_move_quorum` submitters for an ILI move larger than
+    /// `large_ili_move_bps`. Normal-sized moves need only the authority,
+    /// except in degraded mode, where every update needs a cosigner.
+    pub fn check_ili_move_quorum(&self, deviation_bps: u64, signer_count: usize, degraded: bool) -> Result<()> {
+        if degraded {
+            require!(
+                signer_count >= DEGRADED_MIN_ILI_SIGNERS,
+                ICBError::DegradedUpdateNeedsCosigner
+            );
+        }
+        if deviation_bps > self.large_ili_move_bps as u64 {
+            require!(
+                signer_count >= self.large_move_quorum as usize,
+                ICBError::LargeILIMoveNeedsCosigner
+            );
+        }
+        Ok(())
+    }
+    
+    /// Allocate the next proposal id. Ids start at FIRST_PROPOSAL_ID so that
+    /// NO_PROPOSAL_ID (0) can be used as a sentinel, and the counter never
+    /// wraps: once it reaches u64::MAX creation fails with CounterOverflow.
+    pub fn allocate_proposal_id(&mut self) -> Result<u64> {
+        let proposal_id = self.proposal_counter;
+        require!(proposal_id != NO_PROPOSAL_ID, ICBError::InvalidProposalId);
+        self.proposal_counter = proposal_id
+            .checked_add(1)
+            .ok_or(ICBError::CounterOverflow)?;
+        Ok(proposal_id)
+    }
+    
+    /// Move the proposal counter forward to `expected_next`, e.g. past ids
+    /// already taken after a migration. The counter never moves backwards.
+    pub fn reconcile_counter(&mut self, expected_next: u64) -> Result<()> {
+        require!(
+            expected_next > self.proposal_counter,
+            ICBError::ProposalCounterNotAdvanced
+        );
+        self.proposal_counter = expected_next;
+        Ok(())
+    }
+    
+    /// Record a newly created proposal in the active index
+    pub fn track_active_proposal(&mut self, proposal_id: u64) -> Result<()> {
+        let limit = (self.max_active_proposals as usize).min(MAX_ACTIVE_PROPOSALS);
+        require!(
+            self.active_proposals.len() < limit,
+            ICBError::TooManyActiveProposals
+        );
+        self.active_proposals.push(proposal_id);
+        Ok(())
+    }
+    
+    /// Reject batches larger than the governance limit, so a batch handler
+    /// fails up front instead of running out of compute partway through
+    pub fn check_batch_size(&self, len: usize) -> Result<()> {
+        let limit = (self.max_batch_size as usize).min(MAX_ILI_BATCH_SIZE);
+        require!(len <= limit, ICBError::BatchTooLarge);
+        Ok(())
+    }
+    
+    /// Evict a proposal from the active index once voting is finalized
+    pub fn untrack_active_proposal(&mut self, proposal_id: u64) {
+        self.active_proposals.retain(|id| *id != proposal_id);
+    }
 }
 
 /// ILI Oracle account
 #[account]
+#[derive(Default)]
 pub struct ILIOracle {
     pub authority: Pubkey,
-    pub current_ili: u64,           // Scaled by 1e6
+    pub current_ili: u64,           // Scaled by 10^ili_decimals
+    pub ili_decimals: u8,           // Fixed-point decimals of current_ili and snapshots
+    pub min_ili_value: u64,         // Updates below this floor are rejected, same scale as current_ili
     pub last_update: i64,
     pub update_interval: i64,       // 300 seconds (5 min)
     pub snapshot_count: u16,
     pub last_update_slot: u64,      // FIX #9: Slot-based validation
+    pub degraded: bool,             // Relaxed deviation, stricter staleness
+    pub history_capacity: u16,      // Ring buffer size the account is allocated for
+    pub history_head: u16,          // Next write index in the history ring buffer
+    pub history: Vec<ILISnapshot>,  // Ring buffer of recent snapshots for TWAP
     pub bump: u8,
 }
 
 impl ILIOracle {
-    pub const LEN: usize = 8 + // discriminator
+    /// Layout written by this program. Bump it on incompatible changes and
+    /// teach `From<LegacyILIOracle>` how to carry the old fields over.
+    pub const LAYOUT_VERSION: u8 = 3;
+    pub const DEFAULT_HISTORY_CAPACITY: usize = 64;
+    pub const MAX_HISTORY_CAPACITY: usize = 1024;
+    pub const LEN: usize = Self::space(Self::DEFAULT_HISTORY_CAPACITY);
+    
+    /// Account size for a given history capacity
+    pub const fn space(history_capacity: usize) -> usize {
+        8 + // discriminator
         32 + // authority
         8 +  // current_ili
+        1 +  // ili_decimals
+        8 +  // min_ili_value
         8 +  // last_update
         8 +  // update_interval
         2 +  // snapshot_count
         8 +  // last_update_slot (FIX #9)
-        1;   // bump
+        1 +  // degraded
+        2 +  // history_capacity
+        2 +  // history_head
+        4 + history_capacity * ILISnapshot::LEN + // history (vec)
+        1    // bump
+    }
+    
+    /// Append a snapshot to the history ring buffer, overwriting the oldest
+    /// entry once the buffer is full
+    pub fn push_snapshot(&mut self, snapshot: ILISnapshot) {
+        let capacity = self.history_capacity as usize;
+        let head = self.history_head as usize;
+        if self.history.len() < capacity {
+            self.history.push(snapshot);
+        } else {
+            self.history[head] = snapshot;
+        }
+        self.history_head = ((head + 1) % capacity) as u16;
+        self.snapshot_count = self.snapshot_count.saturating_add(1);
+    }
+    
+    /// Change the ring buffer capacity. Existing snapshots are re-indexed
+    /// oldest-first so later writes continue in chronological order.
+    pub fn resize_history(&mut self, new_capacity: u16) -> Result<()> {
+        let used = self.history.len();
+        require!(
+            new_capacity as usize >= used.max(1)
+                && new_capacity as usize <= Self::MAX_HISTORY_CAPACITY,
+            ICBError::InvalidHistoryCapacity
+        );
+        
+        // Unroll a wrapped buffer so the oldest snapshot sits at index 0
+        let head = self.history_head as usize;
+        if head < used {
+            self.history.rotate_left(head);
+        }
+        
+        self.history_capacity = new_capacity;
+        self.history_head = (used % new_capacity as usize) as u16;
+        Ok(())
+    }
+    
+    /// Maximum allowed ILI move between updates; widened in degraded mode so
+    /// a recovering feed can catch up
+    pub fn max_deviation_bps(&self) -> u64 {
+        if self.degraded {
+            DEGRADED_MAX_ILI_DEVIATION_BPS
+        } else {
+            MAX_ILI_DEVIATION_BPS
+        }
+    }
+    
+    /// Maximum age of the ILI before consumers must treat it as stale;
+    /// tightened in degraded mode so consumers fail closed
+    pub fn max_staleness(&self) -> i64 {
+        if self.degraded {
+            DEGRADED_MAX_ILI_STALENESS
+        } else {
+            MAX_ILI_STALENESS
+        }
+    }
+    
+    /// Oldest ILI a monetary proposal may execute against: the governance
+    /// execution age, never looser than the oracle's own staleness limit
+    pub fn execution_max_age(&self, execution_max_oracle_age: i64) -> i64 {
+        execution_max_oracle_age.min(self.max_staleness())
+    }
+    
+    pub fn is_stale(&self, now: i64) -> bool {
+        now.saturating_sub(self.last_update) > self.max_staleness()
+    }
+    
+    /// Split a raw ILI value into whole units and the fractional remainder
+    /// (in units of 10^-ili_decimals) for display
+    pub fn to_display(&self, raw: u64) -> Result<(u64, u64)> {
+        let scale = ili_scale(self.ili_decimals)?;
+        Ok((raw / scale, raw % scale))
+    }
+    
+    /// Inverse of `to_display`
+    pub fn from_display(&self, whole: u64, fraction: u64) -> Result<u64> {
+        let scale = ili_scale(self.ili_decimals)?;
+        require!(fraction < scale, ICBError::InvalidILIDecimals);
+        whole
+            .checked_mul(scale)
+            .and_then(|raw| raw.checked_add(fraction))
+            .ok_or(error!(ICBError::ArithmeticOverflow))
+    }
+}
+
+/// 10^decimals for an ILI fixed-point setting, rejecting unsupported decimals
+pub fn ili_scale(decimals: u8) -> Result<u64> {
+    require!(decimals <= MAX_ILI_DECIMALS, ICBError::InvalidILIDecimals);
+    Ok(10u64.pow(decimals as u32))
+}
+
+/// ILI oracle as stored before `LAYOUT_VERSION` 2. Read by migrate_oracle;
+/// this mirrors the deployed bytes, so never change it alongside ILIOracle.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LegacyILIOracle {
+    pub authority: Pubkey,
+    pub current_ili: u64,
+    pub last_update: i64,
+    pub update_interval: i64,
+    pub snapshot_count: u16,
+    pub last_update_slot: u64,
+    pub degraded: bool,
+    pub history_capacity: u16,
+    pub history_head: u16,
+    pub history: Vec<ILISnapshot>,
+    pub bump: u8,
+}
+
+impl From<LegacyILIOracle> for ILIOracle {
+    fn from(legacy: LegacyILIOracle) -> Self {
+        ILIOracle {
+            authority: legacy.authority,
+            current_ili: legacy.current_ili,
+            ili_decimals: DEFAULT_ILI_DECIMALS, // Always implicitly 6 before
+            min_ili_value: DEFAULT_MIN_ILI_VALUE,
+            last_update: legacy.last_update,
+            update_interval: legacy.update_interval,
+            snapshot_count: legacy.snapshot_count,
+            last_update_slot: legacy.last_update_slot,
+            degraded: legacy.degraded,
+            history_capacity: legacy.history_capacity,
+            history_head: legacy.history_head,
+            history: legacy.history,
+            bump: legacy.bump,
+        }
+    }
+}
+
+/// ILI oracle as stored in `LAYOUT_VERSION` 2, before `min_ili_value`.
+/// Read by migrate_oracle; never change it alongside ILIOracle.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ILIOracleV2 {
+    pub authority: Pubkey,
+    pub current_ili: u64,
+    pub ili_decimals: u8,
+    pub last_update: i64,
+    pub update_interval: i64,
+    pub snapshot_count: u16,
+    pub last_update_slot: u64,
+    pub degraded: bool,
+    pub history_capacity: u16,
+    pub history_head: u16,
+    pub history: Vec<ILISnapshot>,
+    pub bump: u8,
+}
+
+impl From<ILIOracleV2> for ILIOracle {
+    fn from(v2: ILIOracleV2) -> Self {
+        ILIOracle {
+            authority: v2.authority,
+            current_ili: v2.current_ili,
+            ili_decimals: v2.ili_decimals,
+            min_ili_value: DEFAULT_MIN_ILI_VALUE,
+            last_update: v2.last_update,
+            update_interval: v2.update_interval,
+            snapshot_count: v2.snapshot_count,
+            last_update_slot: v2.last_update_slot,
+            degraded: v2.degraded,
+            history_capacity: v2.history_capacity,
+            history_head: v2.history_head,
+            history: v2.history,
+            bump: v2.bump,
+        }
+    }
 }
 
 /// ILI snapshot for historical data
@@ -68,8 +638,17 @@ pub struct ILISnapshot {
     pub tvl: u64,                   // USD scaled by 1e6
 }
 
+impl ILISnapshot {
+    pub const LEN: usize = 8 + // timestamp
+        8 +  // ili_value
+        4 +  // avg_yield
+        4 +  // volatility
+        8;   // tvl
+}
+
 /// Policy proposal account
 #[account]
+#[derive(Default)]
 pub struct PolicyProposal {
     pub id: u64,
     pub proposer: Pubkey,
@@ -82,6 +661,24 @@ pub struct PolicyProposal {
     pub status: ProposalStatus,
     pub execution_tx: Option<[u8; 64]>,
     pub passed_at: i64,             // FIX #3: Track when proposal passed for execution delay
+    pub unique_voter_count: u32,    // Distinct agents with a VoteRecord on this proposal
+    pub depends_on: Option<u64>,    // Earlier proposal this one builds on
+    pub settled_vote_count: u32,    // Votes settled through claim_rewards
+    pub yes_staked: u64,            // Tokens staked on YES; yes_stake holds voting power
+    pub no_staked: u64,             // Tokens staked on NO
+    pub yes_contrarian_staked: u64, // Part of yes_staked from contrarian votes
+    pub no_contrarian_staked: u64,  // Part of no_staked from contrarian votes
+    pub rewards_paid: u64,          // Losing-pool rewards paid to winners so far
+    pub reward_dust: u64,           // Losing-pool remainder retained by the protocol
+    pub created_slot: u64,          // Slot of creation; a fork-resistant ordering key for indexers
+    pub deposit_amount: u64,        // Anti-spam deposit posted by the proposer (0 = none)
+    pub deposit_escrow: Pubkey,     // Token account holding the deposit, owned by this proposal
+    pub deposit_settled: bool,      // Deposit refunded or forfeited by settle_deposit
+    pub min_quorum_stake: u64,      // Quorum, fixed at creation
+    pub min_unique_voters: u32,     // Voter minimum, fixed at creation
+    pub rent_payer: Pubkey,         // Funded the account (proposer, or whoever preallocated it); gets the rent back on close
+    pub stake_escrow: Pubkey,       // Token account holding stake_and_vote stakes, owned by this proposal (default = none)
+    pub locked: bool,               // Reentrancy guard for the vote path
     pub bump: u8,
 }
 
@@ -99,21 +696,412 @@ impl PolicyProposal {
         1 +  // status
         1 + 64 + // execution_tx (option + signature)
         8 +  // passed_at (FIX #3)
+        4 +  // unique_voter_count
+        1 + 8 + // depends_on (option)
+        4 +  // settled_vote_count
+        8 +  // yes_staked
+        8 +  // no_staked
+        8 +  // yes_contrarian_staked
+        8 +  // no_contrarian_staked
+        8 +  // rewards_paid
+        8 +  // reward_dust
+        8 +  // created_slot
+        8 +  // deposit_amount
+        32 + // deposit_escrow
+        1 +  // deposit_settled
+        8 +  // min_quorum_stake
+        4 +  // min_unique_voters
+        32 + // rent_payer
+        32 + // stake_escrow
+        1 +  // locked
         1;   // bump
+    
+    /// Record when the proposal opens, by wall clock and by slot
+    pub fn stamp_created(&mut self, clock: &Clock) {
+        self.start_time = clock.unix_timestamp;
+        self.created_slot = clock.slot;
+    }
+    
+    /// Record who funded the account's rent. The first payer sticks: a
+    /// proposal preallocated by a keeper keeps that keeper as its payer when
+    /// the proposer later fills it.
+    pub fn record_rent_payer(&mut self, payer: Pubkey) {
+        if self.rent_payer == Pubkey::default() {
+            self.rent_payer = payer;
+        }
+    }
+    
+    /// Who gets the rent back when the proposal is closed: always whoever
+    /// paid it, never the party closing it. Accounts written before
+    /// rent_payer existed were paid for by their proposer.
+    pub fn rent_recipient(&self) -> Pubkey {
+        if self.rent_payer == Pubkey::default() {
+            self.proposer
+        } else {
+            self.rent_payer
+        }
+    }
+    
+    /// Fix the governance-tunable rules this proposal is judged by to the
+    /// current parameters, so a later update_parameters cannot move the
+    /// goalposts mid-vote. The pass threshold and execution delay are
+    /// compile-time constants and need no snapshot.
+    pub fn snapshot_rules(&mut self, global_state: &GlobalState) {
+        self.min_quorum_stake = global_state.min_quorum_stake;
+        self.min_unique_voters = global_state.min_unique_voters;
+    }
+    
+    /// Settle the proposer's deposit once voting is over: refunded if the
+    /// proposal drew votes meeting `min_quorum_stake`, forfeited otherwise.
+    /// Returns whether it is refunded.
+    pub fn settle_deposit(&mut self, min_quorum_stake: u64) -> Result<bool> {
+        require!(
+            self.deposit_amount > 0 && !self.deposit_settled,
+            ICBError::NoDepositToSettle
+        );
+        require!(self.status != ProposalStatus::Active, ICBError::ProposalStillActive);
+        
+        self.deposit_settled = true;
+        Ok(self.yes_stake.saturating_add(self.no_stake) > 0 && self.meets_quorum(min_quorum_stake))
+    }
+    
+    /// Whether this proposal's votes escrow their stake
+    pub fn is_escrowed(&self) -> bool {
+        self.stake_escrow != Pubkey::default()
+    }
+    
+    /// Bind the stake escrow on the first stake_and_vote; later stakes must
+    /// go to the same account. Escrowed and tally-only votes never share a
+    /// proposal, or winners could be owed a losing pool the escrow never held.
+    pub fn bind_stake_escrow(&mut self, escrow: Pubkey) -> Result<()> {
+        if self.is_escrowed() {
+            require_keys_eq!(self.stake_escrow, escrow, ICBError::InvalidStakeEscrow);
+            return Ok(());
+        }
+        require!(self.unique_voter_count == 0, ICBError::EscrowModeMismatch);
+        self.stake_escrow = escrow;
+        Ok(())
+    }
+    
+    /// Write the proposal, lock included, to its account data so an
+    /// instruction re-entered from a CPI loads the current state
+    pub fn persist(&self, data: &mut [u8]) -> Result<()> {
+        let mut writer: &mut [u8] = data;
+        self.try_serialize(&mut writer)
+    }
+    
+    /// Run `f` with the proposal locked so a re-entrant call (e.g. from a
+    /// token program invoked for escrow) is rejected with ReentrancyDetected.
+    /// When `f` performs a CPI the lock must be persisted to the account
+    /// first so the re-entered instruction observes it.
+    pub fn with_lock<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        acquire_lock(&mut self.locked)?;
+        let result = f(self);
+        self.locked = false;
+        result
+    }
+    
+    /// Add `voting_power` to the yes or no tally
+    pub fn add_vote(&mut self, prediction: bool, voting_power: u64) -> Result<()> {
+        let tally = if prediction { &mut self.yes_stake } else { &mut self.no_stake };
+        *tally = tally
+            .checked_add(voting_power)
+            .ok_or(ICBError::ArithmeticOverflow)?;
+        Ok(())
+    }
+    
+    /// Add a vote's staked tokens to its side, for the losing pool and the
+    /// reward split
+    pub fn add_stake(&mut self, prediction: bool, stake_amount: u64, contrarian: bool) -> Result<()> {
+        let (staked, contrarian_staked) = if prediction {
+            (&mut self.yes_staked, &mut self.yes_contrarian_staked)
+        } else {
+            (&mut self.no_staked, &mut self.no_contrarian_staked)
+        };
+        *staked = staked
+            .checked_add(stake_amount)
+            .ok_or(ICBError::ArithmeticOverflow)?;
+        if contrarian {
+            *contrarian_staked = contrarian_staked
+                .checked_add(stake_amount)
+                .ok_or(ICBError::ArithmeticOverflow)?;
+        }
+        Ok(())
+    }
+    
+    /// Whether yes + no voting power reaches `min_quorum_stake`
+    pub fn meets_quorum(&self, min_quorum_stake: u64) -> bool {
+        self.yes_stake.saturating_add(self.no_stake) >= min_quorum_stake
+    }
+    
+    /// Whether a vote at `now` falls in the first EARLY_VOTING_WINDOW_BPS of
+    /// the voting period
+    pub fn is_early_vote(&self, now: i64) -> bool {
+        let period = self.end_time.saturating_sub(self.start_time).max(0) as u64;
+        let window = period.saturating_mul(EARLY_VOTING_WINDOW_BPS as u64) / BPS_DENOMINATOR as u64;
+        now < self.start_time.saturating_add(window as i64)
+    }
+    
+    /// Whether an early vote for `prediction` goes against the side leading
+    /// the tally at that moment. Must be checked before the vote is added.
+    pub fn is_contrarian_vote(&self, prediction: bool, now: i64) -> bool {
+        let (own, other) = if prediction {
+            (self.yes_stake, self.no_stake)
+        } else {
+            (self.no_stake, self.yes_stake)
+        };
+        self.is_early_vote(now) && own < other
+    }
+    
+    /// The prediction that turned out correct: YES once executed, NO once
+    /// failed. Cancelled proposals have no winner and stakes are refunded.
+    pub fn winning_side(&self) -> Option<bool> {
+        match self.status {
+            ProposalStatus::Executed => Some(true),
+            ProposalStatus::Failed => Some(false),
+            _ => None,
+        }
+    }
+    
+    /// SLASHING_PENALTY_BPS of the losing side's stake, split among the
+    /// winners. Zero while there is no winning side.
+    pub fn losing_pool(&self) -> Result<u64> {
+        let losing_staked = match self.winning_side() {
+            Some(true) => self.no_staked,
+            Some(false) => self.yes_staked,
+            None => return Ok(0),
+        };
+        mul_div_floor(losing_staked, SLASHING_PENALTY_BPS as u64, BPS_DENOMINATOR as u64)
+    }
+    
+    /// Total reward weight of the winning side: its stake, with contrarian
+    /// stake raised by `contrarian_bonus_bps`
+    pub fn winning_weight(&self, contrarian_bonus_bps: u16) -> Result<u64> {
+        let (staked, contrarian_staked) = match self.winning_side() {
+            Some(true) => (self.yes_staked, self.yes_contrarian_staked),
+            Some(false) => (self.no_staked, self.no_contrarian_staked),
+            None => return Ok(0),
+        };
+        let bonus = mul_div_floor(contrarian_staked, contrarian_bonus_bps as u64, BPS_DENOMINATOR as u64)?;
+        staked
+            .checked_add(bonus)
+            .ok_or(error!(ICBError::ArithmeticOverflow))
+    }
+    
+    /// Pay a winner's `share` of the losing pool, capped at what is left of
+    /// it. Shares below `min_payout` are retained as dust instead. Returns
+    /// the amount paid.
+    pub fn pay_reward(&mut self, share: u64, min_payout: u64) -> Result<u64> {
+        let remaining = self.losing_pool()?
+            .saturating_sub(self.rewards_paid)
+            .saturating_sub(self.reward_dust);
+        let share = share.min(remaining);
+        
+        if share < min_payout {
+            self.reward_dust = self.reward_dust
+                .checked_add(share)
+                .ok_or(ICBError::ArithmeticOverflow)?;
+            return Ok(0);
+        }
+        
+        self.rewards_paid = self.rewards_paid
+            .checked_add(share)
+            .ok_or(ICBError::ArithmeticOverflow)?;
+        Ok(share)
+    }
+    
+    /// Count a vote settled by claim_rewards. Once every vote is settled,
+    /// whatever rounding left of the losing pool is retained as dust.
+    pub fn record_settlement(&mut self) -> Result<()> {
+        self.settled_vote_count = self.settled_vote_count
+            .checked_add(1)
+            .ok_or(ICBError::ArithmeticOverflow)?;
+        if self.settled_vote_count >= self.unique_voter_count {
+            self.reward_dust = self.losing_pool()?.saturating_sub(self.rewards_paid);
+        }
+        Ok(())
+    }
+    
+    /// Terminal with every vote settled and any deposit settled, so no
+    /// claim or settle_deposit still needs the account
+    pub fn is_sweepable(&self) -> bool {
+        self.status.is_terminal()
+            && self.settled_vote_count >= self.unique_voter_count
+            && (self.deposit_amount == 0 || self.deposit_settled)
+    }
+    
+    /// Count a voter whose VoteRecord was just created
+    pub fn record_voter(&mut self) -> Result<()> {
+        self.unique_voter_count = self.unique_voter_count
+            .checked_add(1)
+            .ok_or(ICBError::ArithmeticOverflow)?;
+        Ok(())
+    }
+    
+    /// Whether at least `min_unique_voters` distinct agents voted, so a
+    /// single large holder cannot carry a proposal alone
+    pub fn meets_voter_minimum(&self, min_unique_voters: u32) -> bool {
+        self.unique_voter_count >= min_unique_voters
+    }
+    
+    /// Move the proposal from exactly `from` to `to`, so a second keeper
+    /// racing on the same proposal fails instead of redoing the work.
+    /// Finalizing (from Active) reports ProposalNotActive, executing (from
+    /// Passed) ProposalNotPassed.
+    pub fn transition(&mut self, from: ProposalStatus, to: ProposalStatus) -> Result<()> {
+        if self.status != from {
+            return match from {
+                ProposalStatus::Active => err!(ICBError::ProposalNotActive),
+                ProposalStatus::Passed => err!(ICBError::ProposalNotPassed),
+                _ => err!(ICBError::ProposalNotReadyForExecution),
+            };
+        }
+        self.status = to;
+        Ok(())
+    }
+    
+    /// Cancel an Active or Passed proposal on behalf of the guardian
+    pub fn veto(&mut self) -> Result<()> {
+        require!(
+            matches!(self.status, ProposalStatus::Active | ProposalStatus::Passed),
+            ICBError::ProposalNotVetoable
+        );
+        self.status = ProposalStatus::Cancelled;
+        Ok(())
+    }
+    
+    /// Close a proposal whose dependency ended Failed or Cancelled: it can no
+    /// longer take effect, so it is cancelled (no winning side, stakes are
+    /// refunded) instead of staying open to votes. Returns whether it was.
+    /// `dependency` must be given exactly when the proposal declares one, so
+    /// leaving it out cannot skip the check.
+    pub fn cancel_for_failed_dependency(&mut self, dependency: Option<&PolicyProposal>) -> Result<bool> {
+        let dependency = match (self.depends_on, dependency) {
+            (None, None) => return Ok(false),
+            (Some(id), Some(dependency)) if dependency.id == id => dependency,
+            _ => return err!(ICBError::InvalidDependency),
+        };
+        
+        let dependency_failed = matches!(
+            dependency.status,
+            ProposalStatus::Failed | ProposalStatus::Cancelled
+        );
+        if !dependency_failed || !matches!(self.status, ProposalStatus::Active | ProposalStatus::Passed) {
+            return Ok(false);
+        }
+        
+        self.status = ProposalStatus::Cancelled;
+        Ok(true)
+    }
+    
+    /// Fail an Active proposal nobody finalized within `max_stale_secs` of
+    /// its end time, so it stops occupying the active index
+    pub fn force_expire(&mut self, max_stale_secs: i64, now: i64) -> Result<()> {
+        require!(self.status == ProposalStatus::Active, ICBError::ProposalNotActive);
+        require!(
+            now >= self.end_time.saturating_add(max_stale_secs),
+            ICBError::ProposalNotStale
+        );
+        self.status = ProposalStatus::Failed;
+        Ok(())
+    }
+    
+    /// Push `end_time` forward for an active proposal that is still below
+    /// quorum. The total voting period may not exceed
+    /// `MAX_VOTING_PERIOD + MAX_PROPOSAL_EXTENSION`. Returns the new end time.
+    pub fn extend_voting(&mut self, additional_secs: i64, min_quorum_stake: u64, now: i64) -> Result<i64> {
+        require!(
+            self.status == ProposalStatus::Active && now < self.end_time,
+            ICBError::ProposalNotActive
+        );
+        require!(!self.meets_quorum(min_quorum_stake), ICBError::QuorumAlreadyMet);
+        require!(additional_secs > 0, ICBError::InvalidVotingPeriod);
+        
+        let new_end_time = self.end_time
+            .checked_add(additional_secs)
+            .ok_or(ICBError::ArithmeticOverflow)?;
+        require!(
+            new_end_time - self.start_time <= MAX_VOTING_PERIOD + MAX_PROPOSAL_EXTENSION,
+            ICBError::InvalidVotingPeriod
+        );
+        
+        self.end_time = new_end_time;
+        Ok(new_end_time)
+    }
 }
 
 /// Policy type enum
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug, Default)]
 pub enum PolicyType {
+    #[default]
     MintICU,
     BurnICU,
     UpdateICR,
     RebalanceVault,
 }
 
+impl PolicyType {
+    /// Bit used for this policy type in policy bitmasks
+    pub fn bit(&self) -> u8 {
+        match self {
+            PolicyType::MintICU => 1 << 0,
+            PolicyType::BurnICU => 1 << 1,
+            PolicyType::UpdateICR => 1 << 2,
+            PolicyType::RebalanceVault => 1 << 3,
+        }
+    }
+    
+    /// Policies whose effect depends on the current ILI and must not execute
+    /// against a stale oracle
+    pub fn is_price_sensitive(&self) -> bool {
+        matches!(self, PolicyType::MintICU | PolicyType::BurnICU)
+    }
+    
+    /// Policies executed against the reserve vault or ICU mint
+    pub fn requires_reserve(&self) -> bool {
+        matches!(self, PolicyType::MintICU | PolicyType::BurnICU | PolicyType::RebalanceVault)
+    }
+    
+    /// Build a bitmask from a list of policy types
+    pub fn mask(policy_types: &[PolicyType]) -> u8 {
+        policy_types.iter().fold(0, |mask, p| mask | p.bit())
+    }
+}
+
+impl TryFrom<u8> for PolicyType {
+    type Error = anchor_lang::error::Error;
+    
+    /// Decode a raw discriminant (e.g. one embedded in policy_params or
+    /// passed by another program), rejecting values this build does not know
+    fn try_from(discriminant: u8) -> Result<Self> {
+        match discriminant {
+            0 => Ok(PolicyType::MintICU),
+            1 => Ok(PolicyType::BurnICU),
+            2 => Ok(PolicyType::UpdateICR),
+            3 => Ok(PolicyType::RebalanceVault),
+            _ => err!(ICBError::InvalidPolicyType),
+        }
+    }
+}
+
+/// One leg of a RebalanceVault policy: sell `amount_in` from a reserve
+/// token account
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RebalanceSwap {
+    pub source: Pubkey,
+    pub amount_in: u64,
+}
+
+/// `policy_params` of a RebalanceVault proposal
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct RebalanceParams {
+    pub swaps: Vec<RebalanceSwap>,
+}
+
 /// Proposal status enum
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Default)]
 pub enum ProposalStatus {
+    #[default]
     Active,
     Passed,
     Failed,
@@ -121,6 +1109,296 @@ pub enum ProposalStatus {
     Cancelled,
 }
 
+impl ProposalStatus {
+    /// No further votes, finalization or execution can happen
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, ProposalStatus::Executed | ProposalStatus::Failed | ProposalStatus::Cancelled)
+    }
+}
+
+/// Final tally of a proposal as kept in ProposalHistory
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct ProposalOutcome {
+    pub id: u64,
+    pub status: ProposalStatus,
+    pub yes_stake: u64,             // Yes voting power
+    pub no_stake: u64,              // No voting power
+}
+
+impl ProposalOutcome {
+    pub const LEN: usize = 8 + // id
+        1 +  // status
+        8 +  // yes_stake
+        8;   // no_stake
+}
+
+impl From<&PolicyProposal> for ProposalOutcome {
+    fn from(proposal: &PolicyProposal) -> Self {
+        ProposalOutcome {
+            id: proposal.id,
+            status: proposal.status.clone(),
+            yes_stake: proposal.yes_stake,
+            no_stake: proposal.no_stake,
+        }
+    }
+}
+
+/// Governance-tunable protocol parameters. Only fields set to `Some` are updated.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct ParameterUpdate {
+    pub agent_action_cooldown: Option<i64>,
+    pub breaker_exempt_policies: Option<Vec<PolicyType>>,
+    pub execution_max_oracle_age: Option<i64>,
+    pub finalization_grace_secs: Option<i64>,
+    pub min_quorum_stake: Option<u64>,
+    pub min_unique_voters: Option<u32>,
+    pub max_active_proposals: Option<u8>,
+    pub max_batch_size: Option<u8>,
+    pub max_stale_secs: Option<i64>,
+    pub contrarian_bonus_bps: Option<u16>,
+    pub large_ili_move_bps: Option<u16>,
+    pub large_move_quorum: Option<u8>,
+    pub min_reward_payout: Option<u64>,
+    pub breaker_rerequest_cooldown: Option<i64>,
+    pub max_signature_age: Option<i64>,
+    pub vhr_threshold: Option<u16>,
+    pub auto_recover: Option<bool>,
+    pub proposal_deposit: Option<u64>,
+    pub max_submitters: Option<u16>,
+}
+
+impl ParameterUpdate {
+    /// Validate and apply the requested changes to the global state
+    pub fn apply(&self, global_state: &mut GlobalState) -> Result<()> {
+        if let Some(cooldown) = self.agent_action_cooldown {
+            require!(cooldown >= 0, ICBError::InvalidParameter);
+            global_state.agent_action_cooldown = cooldown;
+            msg!("Agent action cooldown: {} seconds", cooldown);
+        }
+        
+        if let Some(policy_types) = &self.breaker_exempt_policies {
+            global_state.breaker_exempt_policies = PolicyType::mask(policy_types);
+            msg!("Circuit breaker exemptions: {:?}", policy_types);
+        }
+        
+        if let Some(max_age) = self.execution_max_oracle_age {
+            require!(max_age > 0, ICBError::InvalidParameter);
+            global_state.execution_max_oracle_age = max_age;
+            msg!("Execution max oracle age: {} seconds", max_age);
+        }
+        
+        if let Some(grace) = self.finalization_grace_secs {
+            require!(grace >= 0, ICBError::InvalidParameter);
+            global_state.finalization_grace_secs = grace;
+            msg!("Finalization grace: {} seconds", grace);
+        }
+        
+        if let Some(quorum) = self.min_quorum_stake {
+            global_state.min_quorum_stake = quorum;
+            msg!("Minimum quorum: {}", quorum);
+        }
+        
+        if let Some(min_voters) = self.min_unique_voters {
+            global_state.min_unique_voters = min_voters;
+            msg!("Minimum unique voters: {}", min_voters);
+        }
+        
+        if let Some(max_active) = self.max_active_proposals {
+            // Bounded by the space reserved for GlobalState::active_proposals
+            require!(
+                max_active > 0 && max_active as usize <= MAX_ACTIVE_PROPOSALS,
+                ICBError::InvalidParameter
+            );
+            global_state.max_active_proposals = max_active;
+            msg!("Maximum active proposals: {}", max_active);
+        }
+        
+        if let Some(max_batch) = self.max_batch_size {
+            // Bounded by the largest batch known to fit in the compute budget
+            require!(
+                max_batch > 0 && max_batch as usize <= MAX_ILI_BATCH_SIZE,
+                ICBError::InvalidParameter
+            );
+            global_state.max_batch_size = max_batch;
+            msg!("Maximum batch size: {}", max_batch);
+        }
+        
+        if let Some(max_stale) = self.max_stale_secs {
+            require!(max_stale >= 0, ICBError::InvalidParameter);
+            global_state.max_stale_secs = max_stale;
+            msg!("Proposal max stale time: {} seconds", max_stale);
+        }
+        
+        if let Some(bonus) = self.contrarian_bonus_bps {
+            require!(bonus <= MAX_CONTRARIAN_BONUS_BPS, ICBError::InvalidParameter);
+            global_state.contrarian_bonus_bps = bonus;
+            msg!("Contrarian bonus: {} bps", bonus);
+        }
+        
+        if let Some(large_move) = self.large_ili_move_bps {
+            require!(large_move > 0, ICBError::InvalidParameter);
+            global_state.large_ili_move_bps = large_move;
+            msg!("Large ILI move threshold: {} bps", large_move);
+        }
+        
+        if let Some(quorum) = self.large_move_quorum {
+            // At most every registered submitter
+            require!(
+                quorum > 0 && quorum as u16 <= MAX_ORACLE_SUBMITTERS,
+                ICBError::InvalidParameter
+            );
+            global_state.large_move_quorum = quorum;
+            msg!("Large ILI move quorum: {}", quorum);
+        }
+        
+        if let Some(min_payout) = self.min_reward_payout {
+            global_state.min_reward_payout = min_payout;
+            msg!("Minimum reward payout: {}", min_payout);
+        }
+        
+        if let Some(cooldown) = self.breaker_rerequest_cooldown {
+            require!(cooldown >= 0, ICBError::InvalidParameter);
+            global_state.breaker_rerequest_cooldown = cooldown;
+            msg!("Circuit breaker re-request cooldown: {} seconds", cooldown);
+        }
+        
+        if let Some(max_age) = self.max_signature_age {
+            require!(max_age > 0, ICBError::InvalidParameter);
+            global_state.max_signature_age = max_age;
+            msg!("Max signature age: {} seconds", max_age);
+        }
+        
+        if let Some(vhr_threshold) = self.vhr_threshold {
+            global_state.vhr_threshold = ICRatio::new(vhr_threshold)?;
+            msg!("VHR threshold: {} bps", vhr_threshold);
+        }
+        
+        if let Some(auto_recover) = self.auto_recover {
+            global_state.auto_recover = auto_recover;
+            msg!("Circuit breaker auto-recovery: {}", auto_recover);
+        }
+        
+        if let Some(deposit) = self.proposal_deposit {
+            global_state.proposal_deposit = deposit;
+            msg!("Proposal deposit: {}", deposit);
+        }
+        
+        if let Some(max_submitters) = self.max_submitters {
+            // Bounded so anything iterating the submitter set stays within compute
+            require!(
+                max_submitters > 0 && max_submitters <= MAX_ORACLE_SUBMITTERS,
+                ICBError::InvalidParameter
+            );
+            global_state.max_submitters = max_submitters;
+            msg!("Maximum oracle submitters: {}", max_submitters);
+        }
+        
+        Ok(())
+    }
+}
+
+/// Parameter changes staged by update_parameters, applied by anyone once
+/// PARAMETER_TIMELOCK has passed
+#[account]
+pub struct PendingParameters {
+    pub params: ParameterUpdate,    // Staged changes, default when nothing is pending
+    pub apply_after: i64,           // Earliest apply time (0 = nothing pending)
+    pub staged_by: Pubkey,
+    pub bump: u8,
+}
+
+impl PendingParameters {
+    pub const MAX_PARAMS_LEN: usize = 256;
+    pub const LEN: usize = 8 + // discriminator
+        Self::MAX_PARAMS_LEN + // params
+        8 +  // apply_after
+        32 + // staged_by
+        1;   // bump
+    
+    pub fn is_pending(&self) -> bool {
+        self.apply_after != 0
+    }
+    
+    /// Stage `params`, replacing anything already pending and restarting
+    /// the timelock from `now`
+    pub fn stage(&mut self, params: ParameterUpdate, now: i64, staged_by: Pubkey) -> Result<()> {
+        require!(
+            params.try_to_vec()?.len() <= Self::MAX_PARAMS_LEN,
+            ICBError::InvalidParameter
+        );
+        self.apply_after = now
+            .checked_add(PARAMETER_TIMELOCK)
+            .ok_or(ICBError::ArithmeticOverflow)?;
+        self.params = params;
+        self.staged_by = staged_by;
+        Ok(())
+    }
+    
+    /// Take the staged changes for applying, once the timelock has passed
+    pub fn take_due(&mut self, now: i64) -> Result<ParameterUpdate> {
+        require!(self.is_pending(), ICBError::NoPendingParameters);
+        require!(now >= self.apply_after, ICBError::ParameterTimelockNotMet);
+        self.apply_after = 0;
+        Ok(std::mem::take(&mut self.params))
+    }
+}
+
+/// Outcomes of finalized proposals, retained after the proposals themselves
+/// are closed
+#[account]
+pub struct ProposalHistory {
+    pub head: u16,                      // Next write index in the ring buffer
+    pub total_recorded: u64,            // Outcomes ever recorded, including overwritten ones
+    pub outcomes: Vec<ProposalOutcome>, // Ring buffer of the most recent outcomes
+    pub bump: u8,
+}
+
+impl ProposalHistory {
+    pub const CAPACITY: usize = 256;
+    pub const LEN: usize = 8 + // discriminator
+        2 +  // head
+        8 +  // total_recorded
+        4 + Self::CAPACITY * ProposalOutcome::LEN + // outcomes (vec)
+        1;   // bump
+    
+    /// Record a proposal's outcome. A proposal already in the buffer (e.g.
+    /// Passed, then Executed) is updated in place; otherwise the outcome is
+    /// appended, overwriting the oldest entry once the buffer is full.
+    pub fn record(&mut self, outcome: ProposalOutcome) {
+        if let Some(existing) = self.outcomes.iter_mut().find(|o| o.id == outcome.id) {
+            *existing = outcome;
+            return;
+        }
+        
+        let head = self.head as usize;
+        if self.outcomes.len() < Self::CAPACITY {
+            self.outcomes.push(outcome);
+        } else {
+            self.outcomes[head] = outcome;
+        }
+        self.head = ((head + 1) % Self::CAPACITY) as u16;
+        self.total_recorded = self.total_recorded.saturating_add(1);
+    }
+    
+    /// Retained outcomes with ids in `start_id..start_id + count`, ordered by
+    /// id. Ids that were never finalized or have been overwritten are absent.
+    pub fn range(&self, start_id: u64, count: u16) -> Result<Vec<ProposalOutcome>> {
+        require!(
+            count as usize <= MAX_PROPOSAL_HISTORY_QUERY,
+            ICBError::InvalidParameter
+        );
+        let end_id = start_id.saturating_add(count as u64);
+        
+        let mut outcomes: Vec<ProposalOutcome> = self.outcomes
+            .iter()
+            .filter(|o| o.id >= start_id && o.id < end_id)
+            .cloned()
+            .collect();
+        outcomes.sort_by_key(|o| o.id);
+        Ok(outcomes)
+    }
+}
+
 /// Vote record account
 #[account]
 pub struct VoteRecord {
@@ -131,6 +1409,8 @@ pub struct VoteRecord {
     pub timestamp: i64,
     pub claimed: bool,
     pub agent_signature: [u8; 64],  // Ed25519 signature
+    pub contrarian: bool,           // Early vote against the side leading at the time
+    pub voted_slot: u64,            // Slot the vote landed in
     pub bump: u8,
 }
 
@@ -143,7 +1423,34 @@ impl VoteRecord {
         8 +  // timestamp
         1 +  // claimed
         64 + // agent_signature
+        1 +  // contrarian
+        8 +  // voted_slot
         1;   // bump
+    
+    /// Record when the vote landed, by wall clock and by slot
+    pub fn stamp_vote(&mut self, clock: &Clock) {
+        self.timestamp = clock.unix_timestamp;
+        self.voted_slot = clock.slot;
+    }
+    
+    /// Whether a vote has been written. `init_if_needed` hands back a zeroed
+    /// record on the first vote, so the agent key is the only reliable marker.
+    pub fn is_recorded(&self) -> bool {
+        self.agent != Pubkey::default()
+    }
+    
+    /// Reject a second vote by the same agent on the same proposal
+    pub fn check_can_vote(&self) -> Result<()> {
+        require!(!self.is_recorded(), ICBError::AlreadyVoted);
+        Ok(())
+    }
+    
+    /// A record can only be closed once claimed and its proposal is terminal
+    pub fn check_closable(&self, proposal_status: &ProposalStatus) -> Result<()> {
+        require!(self.claimed, ICBError::VoteNotClaimed);
+        require!(proposal_status.is_terminal(), ICBError::ProposalNotTerminal);
+        Ok(())
+    }
 }
 
 /// Agent registry account
@@ -156,6 +1463,7 @@ pub struct AgentRegistry {
     pub reputation_score: u32,
     pub registered_at: i64,
     pub last_active: i64,
+    pub last_vote_signature: [u8; 64], // Signature on the agent's most recent vote
     pub bump: u8,
 }
 
@@ -168,7 +1476,33 @@ impl AgentRegistry {
         4 +  // reputation_score
         8 +  // registered_at
         8 +  // last_active
+        64 + // last_vote_signature
         1;   // bump
+    
+    /// Accept the signature for a new vote. It must be non-zero and differ
+    /// from the one on the agent's previous vote - a weak replay check on top
+    /// of the nonce, since each vote signs a different payload.
+    pub fn record_vote_signature(&mut self, agent_signature: [u8; 64]) -> Result<()> {
+        require!(agent_signature != [0u8; 64], ICBError::InvalidAgentSignature);
+        require!(
+            agent_signature != self.last_vote_signature,
+            ICBError::SignatureReused
+        );
+        self.last_vote_signature = agent_signature;
+        Ok(())
+    }
+    
+    /// Record a governance action (vote or proposal) moving `volume` tokens
+    pub fn record_activity(&mut self, volume: u64, now: i64) -> Result<()> {
+        self.total_transactions = self.total_transactions
+            .checked_add(1)
+            .ok_or(ICBError::ArithmeticOverflow)?;
+        self.total_volume = self.total_volume
+            .checked_add(volume)
+            .ok_or(ICBError::ArithmeticOverflow)?;
+        self.last_active = now;
+        Ok(())
+    }
 }
 
 /// Agent type enum
@@ -182,6 +1516,26 @@ pub enum AgentType {
     TreasuryAgent,
 }
 
+impl AgentType {
+    /// Permission matrix of which policy types each agent type may propose
+    pub fn can_propose(&self, policy_type: &PolicyType) -> bool {
+        match policy_type {
+            PolicyType::MintICU | PolicyType::BurnICU => matches!(
+                self,
+                AgentType::PredictionAgent
+                    | AgentType::ArbitrageAgent
+                    | AgentType::LiquidityAgent
+                    | AgentType::TreasuryAgent
+            ),
+            PolicyType::UpdateICR => matches!(
+                self,
+                AgentType::LendingAgent | AgentType::YieldAgent | AgentType::TreasuryAgent
+            ),
+            PolicyType::RebalanceVault => *self == AgentType::TreasuryAgent,
+        }
+    }
+}
+
 /// Agent state for nonce tracking (prevents replay attacks)
 /// 
 /// Security Advisory: ARS-SA-2026-001 (High Priority Issue #1)
@@ -201,4 +1555,1394 @@ impl AgentState {
         8 +  // nonce
         8 +  // last_action_timestamp
         1;   // bump
+    
+    /// Reject an action taken less than `cooldown` seconds after the previous one
+    pub fn check_cooldown(&self, now: i64, cooldown: i64) -> Result<()> {
+        require!(
+            now.saturating_sub(self.last_action_timestamp) >= cooldown,
+            ICBError::ActionCooldownActive
+        );
+        Ok(())
+    }
+}
+
+/// Update schedule of one ILI submitter
+#[account]
+pub struct OracleSubmitter {
+    pub submitter: Pubkey,
+    pub phase_offset: i64,          // Seconds its update windows are shifted by, below update_interval
+    pub last_submission: i64,
+    pub bump: u8,
+}
+
+impl OracleSubmitter {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // submitter
+        8 +  // phase_offset
+        8 +  // last_submission
+        1;   // bump
+    
+    /// Index of the `interval`-long update window containing `timestamp`.
+    /// Windows start `phase_offset` seconds after the interval boundaries, so
+    /// submitters with different offsets are staggered deterministically.
+    pub fn window(&self, timestamp: i64, interval: i64) -> i64 {
+        timestamp
+            .saturating_sub(self.phase_offset)
+            .div_euclid(interval.max(1))
+    }
+    
+    /// Whether `now` falls in a later window than the last submission; each
+    /// submitter may write once per window
+    pub fn in_new_window(&self, now: i64, interval: i64) -> bool {
+        self.window(now, interval) > self.window(self.last_submission, interval)
+    }
+    
+    /// Submitters backing an ILI write: the one submitting plus each other
+    /// distinct `registered` submitter that is also among `signers`
+    pub fn signer_count(submitter: &Pubkey, signers: &[Pubkey], registered: &[Pubkey]) -> usize {
+        let mut cosigners: Vec<&Pubkey> = registered
+            .iter()
+            .filter(|key| *key != submitter && signers.contains(key))
+            .collect();
+        cosigners.sort();
+        cosigners.dedup();
+        1 + cosigners.len()
+    }
+}
+
+/// Emitted when governance extends a proposal's voting period
+#[event]
+pub struct ProposalExtended {
+    pub proposal_id: u64,
+    pub additional_secs: i64,
+    pub new_end_time: i64,
+    pub timestamp: i64,
+}
+
+/// Category of a rejected transaction, for monitoring
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum RejectionCategory {
+    Auth,
+    StaleOracle,
+    Overflow,
+    Reentrancy,
+    Other,
+}
+
+/// Emitted when a handler rejects a transaction. The transaction still
+/// fails; indexers read the event from its logs. Account constraint
+/// failures are not reported, see `utils::report_rejection`.
+#[event]
+pub struct RejectionEvent {
+    pub category: RejectionCategory,
+    pub error_code: u32,
+    pub timestamp: i64,
+}
+
+/// Emitted when the guardian cancels a proposal
+#[event]
+pub struct ProposalVetoed {
+    pub proposal_id: u64,
+    pub guardian: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a voter settles their stake on a finished proposal
+#[event]
+pub struct RewardClaimed {
+    pub proposal_id: u64,
+    pub agent: Pubkey,
+    pub stake_amount: u64,
+    pub payout: u64,
+    pub contrarian: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when the authority fails a proposal left Active past its lifetime
+#[event]
+pub struct ProposalForceExpired {
+    pub proposal_id: u64,
+    pub end_time: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the oracle enters or leaves degraded mode
+#[event]
+pub struct OracleDegradedModeChanged {
+    pub degraded: bool,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a proposal is created
+#[event]
+pub struct ProposalCreated {
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub policy_type: PolicyType,
+    pub created_slot: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a vote is recorded
+#[event]
+pub struct VoteCast {
+    pub proposal_id: u64,
+    pub agent: Pubkey,
+    pub prediction: bool,
+    pub stake_amount: u64,
+    pub voted_slot: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted at the end of a crank with the compute units it left unused, so
+/// keepers can tune batch sizes (`compute-report` feature)
+#[event]
+pub struct ComputeUnitsReported {
+    pub instruction: String,
+    pub items_processed: u32,
+    pub remaining_units: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn oracle(degraded: bool) -> ILIOracle {
+        ILIOracle {
+            authority: Pubkey::new_unique(),
+            current_ili: 1_000_000,
+            ili_decimals: DEFAULT_ILI_DECIMALS,
+            min_ili_value: DEFAULT_MIN_ILI_VALUE,
+            last_update: 1_000,
+            update_interval: DEFAULT_ILI_UPDATE_INTERVAL,
+            snapshot_count: 1,
+            last_update_slot: 0,
+            degraded,
+            history_capacity: ILIOracle::DEFAULT_HISTORY_CAPACITY as u16,
+            history_head: 0,
+            history: Vec::new(),
+            bump: 255,
+        }
+    }
+    
+    #[test]
+    fn test_degraded_mode_limits() {
+        let normal = oracle(false);
+        let degraded = oracle(true);
+        
+        assert!(degraded.max_deviation_bps() > normal.max_deviation_bps());
+        assert!(degraded.max_staleness() < normal.max_staleness());
+        
+        // An age that is acceptable normally is stale in degraded mode
+        let now = 1_000 + DEGRADED_MAX_ILI_STALENESS + 1;
+        assert!(!normal.is_stale(now));
+        assert!(degraded.is_stale(now));
+    }
+    
+    fn snapshot(timestamp: i64) -> ILISnapshot {
+        ILISnapshot {
+            timestamp,
+            ili_value: 1_000_000,
+            avg_yield: 500,
+            volatility: 1_000,
+            tvl: 1_000_000,
+        }
+    }
+    
+    fn timestamps(oracle: &ILIOracle) -> Vec<i64> {
+        oracle.history.iter().map(|s| s.timestamp).collect()
+    }
+    
+    #[test]
+    fn test_history_ring_buffer_wraps() {
+        let mut oracle = oracle(false);
+        oracle.resize_history(3).unwrap();
+        
+        for t in 1..=4 {
+            oracle.push_snapshot(snapshot(t));
+        }
+        
+        // Oldest entry overwritten in place
+        assert_eq!(timestamps(&oracle), vec![4, 2, 3]);
+        assert_eq!(oracle.history_head, 1);
+    }
+    
+    #[test]
+    fn test_resize_history_preserves_snapshots() {
+        let mut oracle = oracle(false);
+        oracle.resize_history(4).unwrap();
+        for t in 1..=6 {
+            oracle.push_snapshot(snapshot(t));
+        }
+        assert_eq!(timestamps(&oracle), vec![5, 6, 3, 4]);
+        
+        oracle.resize_history(8).unwrap();
+        assert_eq!(oracle.history_capacity, 8);
+        assert_eq!(timestamps(&oracle), vec![3, 4, 5, 6]);
+        
+        // New snapshots append after the newest existing one
+        oracle.push_snapshot(snapshot(7));
+        assert_eq!(timestamps(&oracle), vec![3, 4, 5, 6, 7]);
+    }
+    
+    #[test]
+    fn test_resize_history_bounds() {
+        let mut oracle = oracle(false);
+        for t in 1..=5 {
+            oracle.push_snapshot(snapshot(t));
+        }
+        
+        // Cannot shrink below the snapshots already stored
+        assert_eq!(
+            oracle.resize_history(4).unwrap_err(),
+            ICBError::InvalidHistoryCapacity.into()
+        );
+        assert!(oracle.resize_history(ILIOracle::MAX_HISTORY_CAPACITY as u16 + 1).is_err());
+        assert!(oracle.resize_history(5).is_ok());
+    }
+    
+    #[test]
+    fn test_ili_display_round_trip() {
+        let mut oracle = oracle(false);
+        
+        // 1.250000 at the default 6 decimals
+        assert_eq!(oracle.to_display(1_250_000).unwrap(), (1, 250_000));
+        assert_eq!(oracle.from_display(1, 250_000).unwrap(), 1_250_000);
+        
+        // 1.250000000 at 9 decimals
+        oracle.ili_decimals = 9;
+        assert_eq!(oracle.to_display(1_250_000_000).unwrap(), (1, 250_000_000));
+        
+        for decimals in [0, 6, 9, MAX_ILI_DECIMALS] {
+            oracle.ili_decimals = decimals;
+            for raw in [0, 1, 999_999, 1_250_000, MAX_ILI_VALUE, u64::MAX] {
+                let (whole, fraction) = oracle.to_display(raw).unwrap();
+                assert_eq!(oracle.from_display(whole, fraction).unwrap(), raw);
+            }
+        }
+        
+        // Fraction must fit in the decimals
+        oracle.ili_decimals = 6;
+        assert!(oracle.from_display(1, 1_000_000).is_err());
+        assert!(oracle.from_display(u64::MAX, 0).is_err());
+    }
+    
+    #[test]
+    fn test_ili_decimals_bounds() {
+        assert_eq!(ili_scale(0).unwrap(), 1);
+        assert_eq!(ili_scale(DEFAULT_ILI_DECIMALS).unwrap(), 1_000_000);
+        assert!(ili_scale(MAX_ILI_DECIMALS).is_ok());
+        assert_eq!(
+            ili_scale(MAX_ILI_DECIMALS + 1).unwrap_err(),
+            ICBError::InvalidILIDecimals.into()
+        );
+    }
+    
+    #[test]
+    fn test_circuit_breaker_exemptions() {
+        let mut global_state = GlobalState {
+            breaker_exempt_policies: PolicyType::mask(&[PolicyType::BurnICU]),
+            ..Default::default()
+        };
+        
+        // Everything is proposable while the breaker is down
+        assert!(global_state.can_propose(&PolicyType::MintICU));
+        assert!(global_state.can_propose(&PolicyType::BurnICU));
+        
+        // Only exempt types remain proposable once it trips
+        global_state.circuit_breaker_active = true;
+        assert!(global_state.can_propose(&PolicyType::BurnICU));
+        assert!(!global_state.can_propose(&PolicyType::MintICU));
+        assert!(!global_state.can_propose(&PolicyType::UpdateICR));
+        assert!(!global_state.can_propose(&PolicyType::RebalanceVault));
+        
+        global_state.breaker_exempt_policies = 0;
+        assert!(!global_state.can_propose(&PolicyType::BurnICU));
+    }
+    
+    #[test]
+    fn test_stability_fee_rounds_up() {
+        let global_state = GlobalState {
+            stability_fee_bps: 10, // 0.1%
+            ..Default::default()
+        };
+        
+        assert_eq!(global_state.stability_fee(1_000_000).unwrap(), 1_000);
+        assert_eq!(global_state.stability_fee(1_001).unwrap(), 2);
+        assert_eq!(global_state.stability_fee(1).unwrap(), 1);
+        assert_eq!(global_state.stability_fee(0).unwrap(), 0);
+    }
+    
+    #[test]
+    fn test_reserve_vault_required_for_monetary_proposals() {
+        let mut global_state = GlobalState::default();
+        
+        // Blocked until set_reserve_vault runs
+        assert!(!global_state.is_reserve_ready_for(&PolicyType::MintICU));
+        assert!(!global_state.is_reserve_ready_for(&PolicyType::BurnICU));
+        assert!(!global_state.is_reserve_ready_for(&PolicyType::RebalanceVault));
+        assert!(global_state.is_reserve_ready_for(&PolicyType::UpdateICR));
+        
+        global_state.reserve_vault = Pubkey::new_unique();
+        assert!(global_state.is_reserve_ready_for(&PolicyType::MintICU));
+        assert!(global_state.is_reserve_ready_for(&PolicyType::BurnICU));
+        assert!(global_state.is_reserve_ready_for(&PolicyType::RebalanceVault));
+    }
+    
+    #[test]
+    fn test_active_proposal_index() {
+        let mut global_state = GlobalState {
+            max_active_proposals: MAX_ACTIVE_PROPOSALS as u8,
+            ..Default::default()
+        };
+        
+        // Creation adds ids in order
+        for id in 0..3 {
+            global_state.track_active_proposal(id).unwrap();
+        }
+        assert_eq!(global_state.active_proposals, vec![0, 1, 2]);
+        
+        // Finalization evicts only the given id
+        global_state.untrack_active_proposal(1);
+        assert_eq!(global_state.active_proposals, vec![0, 2]);
+        
+        // Unknown ids are ignored
+        global_state.untrack_active_proposal(42);
+        assert_eq!(global_state.active_proposals, vec![0, 2]);
+    }
+    
+    #[test]
+    fn test_active_proposal_index_bounded() {
+        let mut global_state = GlobalState {
+            max_active_proposals: MAX_ACTIVE_PROPOSALS as u8,
+            ..Default::default()
+        };
+        for id in 0..MAX_ACTIVE_PROPOSALS as u64 {
+            global_state.track_active_proposal(id).unwrap();
+        }
+        
+        assert_eq!(
+            global_state.track_active_proposal(MAX_ACTIVE_PROPOSALS as u64).unwrap_err(),
+            ICBError::TooManyActiveProposals.into()
+        );
+        
+        // Finalizing one frees a slot
+        global_state.untrack_active_proposal(0);
+        assert!(global_state.track_active_proposal(MAX_ACTIVE_PROPOSALS as u64).is_ok());
+    }
+    
+    #[test]
+    fn test_active_proposal_limit_configurable() {
+        let mut global_state = GlobalState {
+            max_active_proposals: 2,
+            ..Default::default()
+        };
+        global_state.track_active_proposal(1).unwrap();
+        global_state.track_active_proposal(2).unwrap();
+        
+        // Creation fails at the governance limit
+        assert_eq!(
+            global_state.track_active_proposal(3).unwrap_err(),
+            ICBError::TooManyActiveProposals.into()
+        );
+        
+        // and succeeds again once one is finalized
+        global_state.untrack_active_proposal(1);
+        global_state.track_active_proposal(3).unwrap();
+        assert_eq!(global_state.active_proposals, vec![2, 3]);
+        
+        // A limit above the allocated index is clamped to it
+        global_state.max_active_proposals = u8::MAX;
+        for id in 4..MAX_ACTIVE_PROPOSALS as u64 + 2 {
+            global_state.track_active_proposal(id).unwrap();
+        }
+        assert!(global_state.track_active_proposal(100).is_err());
+    }
+    
+    #[test]
+    fn test_breaker_rerequest_cooldown() {
+        let deactivated_at = 1_700_000_000;
+        let global_state = GlobalState {
+            last_deactivated_at: deactivated_at,
+            breaker_rerequest_cooldown: 3_600,
+            ..Default::default()
+        };
+        
+        // Re-requesting within the cooldown is rejected
+        assert_eq!(
+            global_state.check_breaker_rerequest(deactivated_at).unwrap_err(),
+            ICBError::CircuitBreakerCooldownActive.into()
+        );
+        assert!(global_state.check_breaker_rerequest(deactivated_at + 3_599).is_err());
+        assert!(global_state.check_breaker_rerequest(deactivated_at + 3_600).is_ok());
+        
+        // Never deactivated: no cooldown applies
+        let fresh = GlobalState { breaker_rerequest_cooldown: 3_600, ..Default::default() };
+        assert!(fresh.check_breaker_rerequest(0).is_ok());
+    }
+    
+    #[test]
+    fn test_staged_parameters_timelock() {
+        let mut pending = PendingParameters {
+            params: ParameterUpdate::default(),
+            apply_after: 0,
+            staged_by: Pubkey::default(),
+            bump: 255,
+        };
+        assert!(!pending.is_pending());
+        assert_eq!(pending.take_due(0).unwrap_err(), ICBError::NoPendingParameters.into());
+        
+        let authority = Pubkey::new_unique();
+        let params = ParameterUpdate { min_quorum_stake: Some(5_000), ..Default::default() };
+        pending.stage(params, 1_000, authority).unwrap();
+        assert!(pending.is_pending());
+        assert_eq!(pending.apply_after, 1_000 + PARAMETER_TIMELOCK);
+        assert_eq!(pending.staged_by, authority);
+        
+        assert_eq!(
+            pending.take_due(1_000 + PARAMETER_TIMELOCK - 1).unwrap_err(),
+            ICBError::ParameterTimelockNotMet.into()
+        );
+        let due = pending.take_due(1_000 + PARAMETER_TIMELOCK).unwrap();
+        assert_eq!(due.min_quorum_stake, Some(5_000));
+        assert!(!pending.is_pending());
+        assert_eq!(pending.params.min_quorum_stake, None);
+        
+        // The largest possible update fits the account
+        let full = ParameterUpdate {
+            agent_action_cooldown: Some(0),
+            breaker_exempt_policies: Some(vec![
+                PolicyType::MintICU,
+                PolicyType::BurnICU,
+                PolicyType::UpdateICR,
+                PolicyType::RebalanceVault,
+            ]),
+            execution_max_oracle_age: Some(0),
+            finalization_grace_secs: Some(0),
+            min_quorum_stake: Some(0),
+            min_unique_voters: Some(0),
+            max_active_proposals: Some(0),
+            max_batch_size: Some(0),
+            max_stale_secs: Some(0),
+            contrarian_bonus_bps: Some(0),
+            large_ili_move_bps: Some(0),
+            large_move_quorum: Some(0),
+            min_reward_payout: Some(0),
+            breaker_rerequest_cooldown: Some(0),
+            max_signature_age: Some(0),
+            vhr_threshold: Some(0),
+            auto_recover: Some(false),
+            proposal_deposit: Some(0),
+            max_submitters: Some(DEFAULT_MAX_SUBMITTERS),
+        };
+        pending.stage(full, 2_000, authority).unwrap();
+        assert!(pending.try_to_vec().unwrap().len() + 8 <= PendingParameters::LEN);
+    }
+    
+    #[test]
+    fn test_deposit_refunded_only_with_participation() {
+        let with_deposit = |yes_stake, no_stake, status| PolicyProposal {
+            status,
+            deposit_amount: 5_000,
+            deposit_escrow: Pubkey::new_unique(),
+            ..proposal(yes_stake, no_stake)
+        };
+        
+        // Well attended: refunded, whatever the outcome
+        assert!(with_deposit(600, 400, ProposalStatus::Failed).settle_deposit(1_000).unwrap());
+        assert!(with_deposit(900, 100, ProposalStatus::Executed).settle_deposit(1_000).unwrap());
+        
+        // Low turnout forfeits it
+        let mut low_turnout = with_deposit(300, 200, ProposalStatus::Failed);
+        assert!(!low_turnout.settle_deposit(1_000).unwrap());
+        assert!(low_turnout.deposit_settled);
+        
+        // No votes at all forfeits even with quorum disabled
+        assert!(!with_deposit(0, 0, ProposalStatus::Failed).settle_deposit(0).unwrap());
+        
+        // Only once, only after voting, only if there is a deposit
+        assert_eq!(
+            low_turnout.settle_deposit(1_000).unwrap_err(),
+            ICBError::NoDepositToSettle.into()
+        );
+        assert_eq!(
+            with_deposit(900, 100, ProposalStatus::Active).settle_deposit(1_000).unwrap_err(),
+            ICBError::ProposalStillActive.into()
+        );
+        assert_eq!(
+            proposal_with_status(ProposalStatus::Failed).settle_deposit(0).unwrap_err(),
+            ICBError::NoDepositToSettle.into()
+        );
+    }
+    
+    #[test]
+    fn test_ic_ratio_layout() {
+        let ratio = ICRatio::new(15_000).unwrap();
+        assert_eq!(ratio.bps(), 15_000);
+        assert_eq!(ratio.try_to_vec().unwrap(), 15_000u16.to_le_bytes());
+        assert_eq!(ICRatio::default(), ratio);
+        
+        assert!(ratio.is_met_by(15_000));
+        assert!(!ratio.is_met_by(14_999));
+    }
+    
+    #[test]
+    fn test_auto_deactivate_requires_recovery() {
+        let mut global_state = GlobalState {
+            vhr_threshold: ICRatio::new(15_000).unwrap(),
+            circuit_breaker_active: true,
+            circuit_breaker_requested_at: 500,
+            auto_recover: true,
+            ..Default::default()
+        };
+        
+        // VHR still under threshold, or the oracle still stale
+        assert_eq!(
+            global_state.check_breaker_recovered(14_999, false).unwrap_err(),
+            ICBError::BreakerConditionPersists.into()
+        );
+        assert_eq!(
+            global_state.check_breaker_recovered(20_000, true).unwrap_err(),
+            ICBError::BreakerConditionPersists.into()
+        );
+        assert!(global_state.circuit_breaker_active);
+        
+        global_state.check_breaker_recovered(15_000, false).unwrap();
+        global_state.deactivate_breaker(9_000);
+        assert!(!global_state.circuit_breaker_active);
+        assert_eq!(global_state.circuit_breaker_requested_at, 0);
+        assert_eq!(global_state.last_deactivated_at, 9_000);
+        
+        // Nothing to clear once deactivated
+        assert_eq!(
+            global_state.check_breaker_recovered(20_000, false).unwrap_err(),
+            ICBError::CircuitBreakerNotActive.into()
+        );
+        
+        // Opt-in only
+        let manual = GlobalState { circuit_breaker_active: true, ..Default::default() };
+        assert_eq!(
+            manual.check_breaker_recovered(u16::MAX, false).unwrap_err(),
+            ICBError::AutoRecoverDisabled.into()
+        );
+    }
+    
+    #[test]
+    fn test_turnout_ema_tracks_recent_turnout() {
+        let mut global_state = GlobalState::default();
+        
+        // First finalization seeds the average
+        global_state.record_turnout(10_000).unwrap();
+        assert_eq!(global_state.turnout_ema, 10_000);
+        
+        // Higher turnout pulls it up, by the alpha share of the gap
+        global_state.record_turnout(20_000).unwrap();
+        let expected = 10_000 + 10_000 * TURNOUT_EMA_ALPHA_BPS as u64 / BPS_DENOMINATOR as u64;
+        assert_eq!(global_state.turnout_ema, expected);
+        
+        // Sustained turnout converges toward it without overshooting
+        for _ in 0..50 {
+            global_state.record_turnout(20_000).unwrap();
+            assert!(global_state.turnout_ema <= 20_000);
+        }
+        assert!(global_state.turnout_ema > 19_900);
+        
+        // And a drop moves it back down
+        let before = global_state.turnout_ema;
+        global_state.record_turnout(0).unwrap();
+        assert!(global_state.turnout_ema < before);
+        assert_eq!(global_state.turnout_samples, 53);
+        
+        // Extreme values do not overflow
+        global_state.record_turnout(u64::MAX).unwrap();
+        global_state.record_turnout(u64::MAX).unwrap();
+    }
+    
+    #[test]
+    fn test_batch_size_limit() {
+        let mut global_state = GlobalState {
+            max_batch_size: 4,
+            ..Default::default()
+        };
+        
+        // At the limit is allowed, one above is not
+        assert!(global_state.check_batch_size(4).is_ok());
+        assert_eq!(
+            global_state.check_batch_size(5).unwrap_err(),
+            ICBError::BatchTooLarge.into()
+        );
+        
+        // A limit above the compute-safe cap is clamped to it
+        global_state.max_batch_size = u8::MAX;
+        assert!(global_state.check_batch_size(MAX_ILI_BATCH_SIZE).is_ok());
+        assert!(global_state.check_batch_size(MAX_ILI_BATCH_SIZE + 1).is_err());
+    }
+    
+    #[test]
+    fn test_agent_policy_permissions() {
+        // One allowed and one disallowed combination per agent type
+        let cases = [
+            (AgentType::LendingAgent, PolicyType::UpdateICR, PolicyType::MintICU),
+            (AgentType::YieldAgent, PolicyType::UpdateICR, PolicyType::BurnICU),
+            (AgentType::LiquidityAgent, PolicyType::BurnICU, PolicyType::UpdateICR),
+            (AgentType::PredictionAgent, PolicyType::MintICU, PolicyType::RebalanceVault),
+            (AgentType::ArbitrageAgent, PolicyType::BurnICU, PolicyType::RebalanceVault),
+        ];
+        for (agent_type, allowed, disallowed) in cases {
+            assert!(agent_type.can_propose(&allowed));
+            assert!(!agent_type.can_propose(&disallowed));
+        }
+        
+        // Treasury agents may propose every policy type
+        for policy_type in [
+            PolicyType::MintICU,
+            PolicyType::BurnICU,
+            PolicyType::UpdateICR,
+            PolicyType::RebalanceVault,
+        ] {
+            assert!(AgentType::TreasuryAgent.can_propose(&policy_type));
+        }
+    }
+    
+    fn registry() -> AgentRegistry {
+        AgentRegistry {
+            agent_pubkey: Pubkey::new_unique(),
+            agent_type: AgentType::PredictionAgent,
+            total_transactions: 0,
+            total_volume: 0,
+            reputation_score: 0,
+            registered_at: 1_000,
+            last_active: 1_000,
+            last_vote_signature: [0; 64],
+            bump: 255,
+        }
+    }
+    
+    #[test]
+    fn test_agent_registry_activity() {
+        let mut registry = registry();
+        
+        // A proposal followed by three votes
+        registry.record_activity(0, 1_100).unwrap();
+        for (stake, now) in [(500, 1_200), (1_500, 1_300), (2_000, 1_400)] {
+            registry.record_activity(stake, now).unwrap();
+        }
+        
+        assert_eq!(registry.total_transactions, 4);
+        assert_eq!(registry.total_volume, 4_000);
+        assert_eq!(registry.last_active, 1_400);
+        
+        registry.total_volume = u64::MAX;
+        assert_eq!(
+            registry.record_activity(1, 1_500).unwrap_err(),
+            ICBError::ArithmeticOverflow.into()
+        );
+    }
+    
+    #[test]
+    fn test_vote_signature_checks() {
+        let mut registry = registry();
+        
+        assert_eq!(
+            registry.record_vote_signature([0; 64]).unwrap_err(),
+            ICBError::InvalidAgentSignature.into()
+        );
+        
+        registry.record_vote_signature([1; 64]).unwrap();
+        
+        // The same signature on the next vote is a replay
+        assert_eq!(
+            registry.record_vote_signature([1; 64]).unwrap_err(),
+            ICBError::SignatureReused.into()
+        );
+        
+        registry.record_vote_signature([2; 64]).unwrap();
+        assert_eq!(registry.last_vote_signature, [2; 64]);
+    }
+    
+    fn proposal(yes_stake: u64, no_stake: u64) -> PolicyProposal {
+        PolicyProposal {
+            id: 1,
+            proposer: Pubkey::new_unique(),
+            start_time: 1_000,
+            end_time: 1_000 + MIN_VOTING_PERIOD,
+            yes_stake,
+            no_stake,
+            status: ProposalStatus::Active,
+            bump: 255,
+            ..Default::default()
+        }
+    }
+    
+    fn proposal_with_status(status: ProposalStatus) -> PolicyProposal {
+        PolicyProposal { status, ..proposal(0, 0) }
+    }
+    
+    fn history() -> ProposalHistory {
+        ProposalHistory { head: 0, total_recorded: 0, outcomes: Vec::new(), bump: 255 }
+    }
+    
+    fn outcome(id: u64) -> ProposalOutcome {
+        ProposalOutcome { id, ..ProposalOutcome::from(&proposal_with_status(ProposalStatus::Failed)) }
+    }
+    
+    #[test]
+    fn test_finalization_appends_to_proposal_history() {
+        let mut history = history();
+        
+        let mut passed = PolicyProposal { id: 7, status: ProposalStatus::Passed, ..proposal(900, 100) };
+        history.record(ProposalOutcome::from(&passed));
+        let failed = PolicyProposal { id: 8, status: ProposalStatus::Failed, ..proposal(100, 900) };
+        history.record(ProposalOutcome::from(&failed));
+        
+        assert_eq!(history.outcomes.len(), 2);
+        assert_eq!(history.total_recorded, 2);
+        let recorded = &history.outcomes[0];
+        assert_eq!((recorded.id, recorded.yes_stake, recorded.no_stake), (7, 900, 100));
+        assert!(recorded.status == ProposalStatus::Passed);
+        
+        // Executing a passed proposal updates its entry rather than appending
+        passed.status = ProposalStatus::Executed;
+        history.record(ProposalOutcome::from(&passed));
+        assert_eq!(history.outcomes.len(), 2);
+        assert_eq!(history.total_recorded, 2);
+        assert!(history.outcomes[0].status == ProposalStatus::Executed);
+    }
+    
+    #[test]
+    fn test_proposal_history_ring_buffer_wraps() {
+        let mut history = history();
+        let recorded = ProposalHistory::CAPACITY as u64 + 3;
+        for id in 1..=recorded {
+            history.record(outcome(id));
+        }
+        
+        // The three oldest outcomes were overwritten in place
+        assert_eq!(history.outcomes.len(), ProposalHistory::CAPACITY);
+        assert_eq!(history.total_recorded, recorded);
+        assert_eq!(history.head, 3);
+        assert_eq!(history.outcomes[0].id, ProposalHistory::CAPACITY as u64 + 1);
+        assert!(history.range(1, 3).unwrap().is_empty());
+    }
+    
+    #[test]
+    fn test_proposal_history_range() {
+        let mut history = history();
+        // Finalization order does not follow id order, and id 4 is missing
+        for id in [3, 1, 5, 2, 6] {
+            history.record(outcome(id));
+        }
+        
+        let ids = |outcomes: Vec<ProposalOutcome>| outcomes.iter().map(|o| o.id).collect::<Vec<_>>();
+        assert_eq!(ids(history.range(2, 4).unwrap()), vec![2, 3, 5]);
+        assert_eq!(ids(history.range(1, 1).unwrap()), vec![1]);
+        assert_eq!(ids(history.range(6, 10).unwrap()), vec![6]);
+        assert!(history.range(7, 10).unwrap().is_empty());
+        assert!(history.range(1, 0).unwrap().is_empty());
+        assert!(history.range(u64::MAX - 1, 2).unwrap().is_empty());
+        
+        // Results must fit in return data
+        assert!(history.range(1, MAX_PROPOSAL_HISTORY_QUERY as u16).is_ok());
+        assert_eq!(
+            history.range(1, MAX_PROPOSAL_HISTORY_QUERY as u16 + 1).err(),
+            Some(ICBError::InvalidParameter.into())
+        );
+    }
+    
+    #[test]
+    fn test_unique_voter_minimum() {
+        let min_unique_voters = 3;
+        
+        // One whale voting alone has depth but no breadth
+        let mut whale = proposal(0, 0);
+        whale.add_vote(true, 1_000_000).unwrap();
+        whale.record_voter().unwrap();
+        assert!(whale.meets_quorum(DEFAULT_MIN_QUORUM_STAKE));
+        assert!(!whale.meets_voter_minimum(min_unique_voters));
+        
+        let mut broad = proposal(0, 0);
+        for power in [400, 300, 200] {
+            broad.add_vote(true, power).unwrap();
+            broad.record_voter().unwrap();
+        }
+        assert_eq!(broad.unique_voter_count, 3);
+        assert!(broad.meets_voter_minimum(min_unique_voters));
+        
+        // Disabled by default
+        assert!(proposal(0, 0).meets_voter_minimum(DEFAULT_MIN_UNIQUE_VOTERS));
+    }
+    
+    #[test]
+    fn test_extend_proposal_below_quorum() {
+        let mut proposal = proposal(40, 30);
+        let now = proposal.end_time - 60;
+        
+        let new_end = proposal.extend_voting(3_600, 100, now).unwrap();
+        assert_eq!(new_end, 1_000 + MIN_VOTING_PERIOD + 3_600);
+        assert_eq!(proposal.end_time, new_end);
+        
+        // Total voting period is capped
+        assert_eq!(
+            proposal.extend_voting(MAX_VOTING_PERIOD + MAX_PROPOSAL_EXTENSION, 100, now).unwrap_err(),
+            ICBError::InvalidVotingPeriod.into()
+        );
+        assert!(proposal.extend_voting(0, 100, now).is_err());
+    }
+    
+    #[test]
+    fn test_extend_proposal_rejected_with_quorum_or_inactive() {
+        // Quorum already met
+        let mut proposal = proposal(60, 40);
+        let now = proposal.end_time - 60;
+        assert_eq!(
+            proposal.extend_voting(3_600, 100, now).unwrap_err(),
+            ICBError::QuorumAlreadyMet.into()
+        );
+        
+        // Proposal no longer active
+        let mut proposal = proposal_with_status(ProposalStatus::Passed);
+        assert_eq!(
+            proposal.extend_voting(3_600, 100, now).unwrap_err(),
+            ICBError::ProposalNotActive.into()
+        );
+        
+        // Voting period already over
+        let mut proposal = proposal_with_status(ProposalStatus::Active);
+        let end_time = proposal.end_time;
+        assert!(proposal.extend_voting(3_600, 100, end_time).is_err());
+    }
+    
+    #[test]
+    fn test_vote_tally_under_lock() {
+        let mut proposal = proposal(0, 0);
+        
+        proposal.with_lock(|p| p.add_vote(true, 30)).unwrap();
+        proposal.with_lock(|p| p.add_vote(false, 20)).unwrap();
+        
+        assert_eq!((proposal.yes_stake, proposal.no_stake), (30, 20));
+        assert!(!proposal.locked);
+    }
+    
+    #[test]
+    fn test_stake_escrow_bound_by_first_stake() {
+        let escrow = Pubkey::new_unique();
+        let mut staked = proposal(0, 0);
+        assert!(!staked.is_escrowed());
+        
+        staked.bind_stake_escrow(escrow).unwrap();
+        assert!(staked.is_escrowed());
+        staked.record_voter().unwrap();
+        
+        // Later stakes must use the same escrow
+        assert!(staked.bind_stake_escrow(escrow).is_ok());
+        assert_eq!(
+            staked.bind_stake_escrow(Pubkey::new_unique()).unwrap_err(),
+            ICBError::InvalidStakeEscrow.into()
+        );
+        assert_eq!(staked.stake_escrow, escrow);
+        
+        // A proposal with tally-only votes cannot start escrowing
+        let mut tally_only = proposal(0, 0);
+        tally_only.record_voter().unwrap();
+        assert_eq!(
+            tally_only.bind_stake_escrow(escrow).unwrap_err(),
+            ICBError::EscrowModeMismatch.into()
+        );
+    }
+    
+    #[test]
+    fn test_vote_reentry_rejected() {
+        let mut proposal = proposal(0, 0);
+        
+        // A malicious token program re-entering the vote during the escrow transfer
+        let result = proposal.with_lock(|p| {
+            p.with_lock(|inner| inner.add_vote(true, 1_000))?;
+            p.add_vote(true, 1_000)
+        });
+        
+        assert_eq!(result.unwrap_err(), ICBError::ReentrancyDetected.into());
+        assert_eq!(proposal.yes_stake, 0);
+        assert!(!proposal.locked);
+    }
+    
+    #[test]
+    fn test_second_vote_by_same_agent_rejected() {
+        // What init_if_needed returns for a first-time voter
+        let mut vote_record = VoteRecord {
+            proposal: Pubkey::default(),
+            agent: Pubkey::default(),
+            stake_amount: 0,
+            prediction: false,
+            timestamp: 0,
+            claimed: false,
+            agent_signature: [0; 64],
+            contrarian: false,
+            voted_slot: 0,
+            bump: 0,
+        };
+        assert!(vote_record.check_can_vote().is_ok());
+        
+        vote_record.proposal = Pubkey::new_unique();
+        vote_record.agent = Pubkey::new_unique();
+        vote_record.stake_amount = 1_000;
+        
+        // Rejected regardless of the claimed flag
+        assert_eq!(vote_record.check_can_vote().unwrap_err(), ICBError::AlreadyVoted.into());
+        vote_record.claimed = true;
+        assert_eq!(vote_record.check_can_vote().unwrap_err(), ICBError::AlreadyVoted.into());
+    }
+    
+    #[test]
+    fn test_slots_recorded_from_clock() {
+        let clock = Clock {
+            slot: 250_000_123,
+            unix_timestamp: 1_700_000_000,
+            ..Default::default()
+        };
+        
+        let mut proposal = proposal(0, 0);
+        proposal.stamp_created(&clock);
+        assert_eq!(proposal.created_slot, clock.slot);
+        assert_eq!(proposal.start_time, clock.unix_timestamp);
+        
+        let later = Clock { slot: clock.slot + 40, unix_timestamp: clock.unix_timestamp + 16, ..clock };
+        let mut vote_record = VoteRecord {
+            proposal: Pubkey::new_unique(),
+            agent: Pubkey::new_unique(),
+            stake_amount: 1_000,
+            prediction: true,
+            timestamp: 0,
+            claimed: false,
+            agent_signature: [0; 64],
+            contrarian: false,
+            voted_slot: 0,
+            bump: 255,
+        };
+        vote_record.stamp_vote(&later);
+        assert_eq!(vote_record.voted_slot, later.slot);
+        assert_eq!(vote_record.timestamp, later.unix_timestamp);
+        assert!(vote_record.voted_slot > proposal.created_slot);
+    }
+    
+    #[test]
+    fn test_close_vote_record() {
+        let mut vote_record = VoteRecord {
+            proposal: Pubkey::new_unique(),
+            agent: Pubkey::new_unique(),
+            stake_amount: 1_000,
+            prediction: true,
+            timestamp: 1_000,
+            claimed: false,
+            agent_signature: [0; 64],
+            contrarian: false,
+            voted_slot: 0,
+            bump: 255,
+        };
+        
+        // Unclaimed records cannot be closed
+        assert_eq!(
+            vote_record.check_closable(&ProposalStatus::Executed).unwrap_err(),
+            ICBError::VoteNotClaimed.into()
+        );
+        
+        // Nor can records on proposals that are still live
+        vote_record.claimed = true;
+        for status in [ProposalStatus::Active, ProposalStatus::Passed] {
+            assert_eq!(
+                vote_record.check_closable(&status).unwrap_err(),
+                ICBError::ProposalNotTerminal.into()
+            );
+        }
+        
+        for status in [ProposalStatus::Executed, ProposalStatus::Failed, ProposalStatus::Cancelled] {
+            assert!(vote_record.check_closable(&status).is_ok());
+        }
+    }
+    
+    #[test]
+    fn test_executor_allowlist() {
+        let authority = Pubkey::new_unique();
+        let keeper = Pubkey::new_unique();
+        let mut global_state = GlobalState { authority, ..Default::default() };
+        
+        // The authority is always allowed, others only once allowlisted
+        assert!(global_state.is_executor(&authority));
+        assert!(!global_state.is_executor(&keeper));
+        
+        global_state.add_executor(keeper).unwrap();
+        assert!(global_state.is_executor(&keeper));
+        assert!(!global_state.is_executor(&Pubkey::new_unique()));
+        assert_eq!(
+            global_state.add_executor(keeper).unwrap_err(),
+            ICBError::ExecutorAlreadyAllowed.into()
+        );
+        
+        global_state.remove_executor(&keeper).unwrap();
+        assert!(!global_state.is_executor(&keeper));
+        assert_eq!(
+            global_state.remove_executor(&keeper).unwrap_err(),
+            ICBError::ExecutorNotFound.into()
+        );
+    }
+    
+    #[test]
+    fn test_executor_mismatch_reports_both_keys() {
+        let authority = Pubkey::new_unique();
+        let stranger = Pubkey::new_unique();
+        let global_state = GlobalState { authority, ..Default::default() };
+        
+        assert!(global_state.check_executor(&authority).is_ok());
+        
+        // Logged as Left (actual) / Right (expected) alongside the error
+        let err = global_state.check_executor(&stranger).unwrap_err();
+        assert_eq!(err, ICBError::Unauthorized.into());
+        match err {
+            Error::AnchorError(e) => match e.compared_values {
+                Some(anchor_lang::error::ComparedValues::Pubkeys((left, right))) => {
+                    assert_eq!((left, right), (stranger, authority));
+                }
+                _ => panic!("expected both keys in the error"),
+            },
+            other => panic!("expected an AnchorError, got {:?}", other),
+        }
+    }
+    
+    #[test]
+    fn test_double_finalization_rejected() {
+        let mut proposal = proposal(900, 100);
+        
+        // First keeper finalizes; the second, having also read Active, fails
+        proposal.transition(ProposalStatus::Active, ProposalStatus::Passed).unwrap();
+        assert_eq!(
+            proposal.transition(ProposalStatus::Active, ProposalStatus::Failed).unwrap_err(),
+            ICBError::ProposalNotActive.into()
+        );
+        assert!(proposal.status == ProposalStatus::Passed);
+        
+        // Same for execution
+        proposal.transition(ProposalStatus::Passed, ProposalStatus::Executed).unwrap();
+        assert_eq!(
+            proposal.transition(ProposalStatus::Passed, ProposalStatus::Executed).unwrap_err(),
+            ICBError::ProposalNotPassed.into()
+        );
+        assert!(proposal.status == ProposalStatus::Executed);
+    }
+    
+    #[test]
+    fn test_dependent_cancelled_when_dependency_fails() {
+        let mut dependency = proposal_with_status(ProposalStatus::Active);
+        let mut dependent = proposal_with_status(ProposalStatus::Active);
+        dependent.id = dependency.id + 1;
+        dependent.depends_on = Some(dependency.id);
+        
+        // Nothing happens while the dependency is still live or succeeded
+        for status in [ProposalStatus::Active, ProposalStatus::Passed, ProposalStatus::Executed] {
+            dependency.status = status;
+            assert!(!dependent.cancel_for_failed_dependency(Some(&dependency)).unwrap());
+            assert!(dependent.status == ProposalStatus::Active);
+        }
+        
+        // Once it fails the dependent is closed with stakes refunded
+        dependency.status = ProposalStatus::Failed;
+        assert!(dependent.cancel_for_failed_dependency(Some(&dependency)).unwrap());
+        assert!(dependent.status == ProposalStatus::Cancelled);
+        assert_eq!(dependent.winning_side(), None);
+        assert_eq!(dependent.losing_pool().unwrap(), 0);
+        
+        // A Passed dependent of a cancelled dependency is closed too
+        dependency.status = ProposalStatus::Cancelled;
+        dependent.status = ProposalStatus::Passed;
+        assert!(dependent.cancel_for_failed_dependency(Some(&dependency)).unwrap());
+        assert!(dependent.status == ProposalStatus::Cancelled);
+        
+        // Only the proposal it actually depends on counts
+        let mut unrelated = proposal_with_status(ProposalStatus::Failed);
+        unrelated.id = dependency.id + 5;
+        dependent.status = ProposalStatus::Active;
+        assert_eq!(
+            dependent.cancel_for_failed_dependency(Some(&unrelated)).unwrap_err(),
+            ICBError::InvalidDependency.into()
+        );
+    }
+    
+    #[test]
+    fn test_omitted_dependency_account_rejected() {
+        let mut dependent = proposal_with_status(ProposalStatus::Active);
+        dependent.id = 5;
+        dependent.depends_on = Some(4);
+        
+        // Leaving the account out must not finalize the dependent normally
+        assert_eq!(
+            dependent.cancel_for_failed_dependency(None).unwrap_err(),
+            ICBError::InvalidDependency.into()
+        );
+        assert!(dependent.status == ProposalStatus::Active);
+        
+        // A proposal without a dependency needs no account, and takes none
+        let mut independent = proposal_with_status(ProposalStatus::Active);
+        assert!(!independent.cancel_for_failed_dependency(None).unwrap());
+        assert_eq!(
+            independent.cancel_for_failed_dependency(Some(&dependent)).unwrap_err(),
+            ICBError::InvalidDependency.into()
+        );
+    }
+    
+    #[test]
+    fn test_guardian_can_only_veto() {
+        let authority = Pubkey::new_unique();
+        let guardian = Pubkey::new_unique();
+        let mut global_state = GlobalState { authority, ..Default::default() };
+        
+        // Must be distinct from the authority
+        assert_eq!(
+            global_state.set_guardian(Some(authority)).unwrap_err(),
+            ICBError::InvalidParameter.into()
+        );
+        global_state.set_guardian(Some(guardian)).unwrap();
+        assert!(global_state.is_guardian(&guardian));
+        assert!(!global_state.is_guardian(&authority));
+        
+        // Active and Passed proposals can be vetoed
+        for status in [ProposalStatus::Active, ProposalStatus::Passed] {
+            let mut proposal = proposal_with_status(status);
+            proposal.veto().unwrap();
+            assert!(proposal.status == ProposalStatus::Cancelled);
+        }
+        for status in [ProposalStatus::Executed, ProposalStatus::Failed, ProposalStatus::Cancelled] {
+            assert_eq!(
+                proposal_with_status(status).veto().unwrap_err(),
+                ICBError::ProposalNotVetoable.into()
+            );
+        }
+        
+        // No executor or authority powers
+        assert!(!global_state.is_executor(&guardian));
+        assert_ne!(global_state.authority, guardian);
+        
+        global_state.set_guardian(None).unwrap();
+        assert!(!global_state.is_guardian(&guardian));
+    }
+    
+    #[test]
+    fn test_contrarian_vote_only_in_early_window() {
+        let mut proposal = proposal(100, 40);
+        let early_end = proposal.start_time + MIN_VOTING_PERIOD / 4;
+        
+        // NO trails early on, YES is bandwagoning
+        assert!(proposal.is_contrarian_vote(false, proposal.start_time));
+        assert!(!proposal.is_contrarian_vote(true, proposal.start_time));
+        assert!(proposal.is_contrarian_vote(false, early_end - 1));
+        
+        // Past the early window nobody counts as contrarian
+        assert!(!proposal.is_contrarian_vote(false, early_end));
+        
+        // A tied tally has no minority
+        proposal.no_stake = 100;
+        assert!(!proposal.is_contrarian_vote(false, proposal.start_time));
+        assert!(!proposal.is_contrarian_vote(true, proposal.start_time));
+    }
+    
+    #[test]
+    fn test_force_expire_after_max_stale_window() {
+        let max_stale_secs = DEFAULT_MAX_PROPOSAL_STALE_SECS;
+        let mut proposal = proposal(0, 0);
+        let expires_at = proposal.end_time + max_stale_secs;
+        
+        // Rejected during voting and until the stale window has passed
+        for now in [proposal.start_time, proposal.end_time, expires_at - 1] {
+            assert_eq!(
+                proposal.force_expire(max_stale_secs, now).unwrap_err(),
+                ICBError::ProposalNotStale.into()
+            );
+        }
+        assert!(proposal.status == ProposalStatus::Active);
+        
+        proposal.force_expire(max_stale_secs, expires_at).unwrap();
+        assert!(proposal.status == ProposalStatus::Failed);
+        
+        // Only Active proposals can be force-expired
+        for status in [ProposalStatus::Passed, ProposalStatus::Executed, ProposalStatus::Failed, ProposalStatus::Cancelled] {
+            assert_eq!(
+                proposal_with_status(status).force_expire(max_stale_secs, i64::MAX).unwrap_err(),
+                ICBError::ProposalNotActive.into()
+            );
+        }
+    }
+    
+    #[test]
+    fn test_large_ili_move_needs_cosigner() {
+        let submitter = Pubkey::new_unique();
+        let cosigner = Pubkey::new_unique();
+        let registered = [submitter, cosigner];
+        let global_state = GlobalState {
+            large_ili_move_bps: DEFAULT_LARGE_ILI_MOVE_BPS,
+            large_move_quorum: DEFAULT_LARGE_MOVE_QUORUM,
+            ..Default::default()
+        };
+        let large_move = DEFAULT_LARGE_ILI_MOVE_BPS as u64 + 1;
+        
+        // Normal-sized moves proceed single-signer
+        let alone = OracleSubmitter::signer_count(&submitter, &[], &registered);
+        assert_eq!(alone, 1);
+        assert!(global_state.check_ili_move_quorum(DEFAULT_LARGE_ILI_MOVE_BPS as u64, alone, false).is_ok());
+        
+        // A large jump fails with one signer
+        assert_eq!(
+            global_state.check_ili_move_quorum(large_move, alone, false).unwrap_err(),
+            ICBError::LargeILIMoveNeedsCosigner.into()
+        );
+        
+        // An unregistered signer does not count, a registered submitter does
+        let stranger = OracleSubmitter::signer_count(&submitter, &[Pubkey::new_unique()], &registered);
+        assert!(global_state.check_ili_move_quorum(large_move, stranger, false).is_err());
+        let both = OracleSubmitter::signer_count(&submitter, &[cosigner, cosigner], &[submitter, cosigner, cosigner]);
+        assert_eq!(both, 2);
+        assert!(global_state.check_ili_move_quorum(large_move, both, false).is_ok());
+        
+        // Registration without a signature is not a co-signature either
+        assert_eq!(OracleSubmitter::signer_count(&submitter, &[], &registered), 1);
+        
+        // A submitter cannot co-sign its own update
+        assert_eq!(OracleSubmitter::signer_count(&submitter, &[submitter], &registered), 1);
+    }
+    
+    #[test]
+    fn test_degraded_update_needs_cosigner() {
+        let submitter = Pubkey::new_unique();
+        let cosigner = Pubkey::new_unique();
+        let registered = [submitter, cosigner];
+        let mut global_state = GlobalState {
+            large_ili_move_bps: DEFAULT_LARGE_ILI_MOVE_BPS,
+            large_move_quorum: DEFAULT_LARGE_MOVE_QUORUM,
+            ..Default::default()
+        };
+        let small_move = 10;
+        let alone = OracleSubmitter::signer_count(&submitter, &[], &registered);
+        let both = OracleSubmitter::signer_count(&submitter, &[cosigner], &registered);
+        
+        // A single submitter is fine normally
+        assert!(global_state.check_ili_move_quorum(small_move, alone, false).is_ok());
+        
+        // But not while degraded, however small the move
+        assert_eq!(
+            global_state.check_ili_move_quorum(small_move, alone, true).unwrap_err(),
+            ICBError::DegradedUpdateNeedsCosigner.into()
+        );
+        assert!(global_state.check_ili_move_quorum(0, alone, true).is_err());
+        assert!(global_state.check_ili_move_quorum(small_move, both, true).is_ok());
+        
+        // A large move in degraded mode still needs the large-move quorum
+        global_state.large_move_quorum = 3;
+        assert_eq!(
+            global_state.check_ili_move_quorum(DEFAULT_LARGE_ILI_MOVE_BPS as u64 + 1, both, true).unwrap_err(),
+            ICBError::LargeILIMoveNeedsCosigner.into()
+        );
+    }
+    
+    #[test]
+    fn test_executor_allowlist_bounded() {
+        let mut global_state = GlobalState::default();
+        for _ in 0..MAX_EXECUTORS {
+            global_state.add_executor(Pubkey::new_unique()).unwrap();
+        }
+        assert_eq!(
+            global_state.add_executor(Pubkey::new_unique()).unwrap_err(),
+            ICBError::ExecutorAllowlistFull.into()
+        );
+    }
+    
+    #[test]
+    fn test_oracle_submitters_capped() {
+        let mut global_state = GlobalState {
+            max_submitters: 3,
+            ..Default::default()
+        };
+        for _ in 0..3 {
+            global_state.register_submitter().unwrap();
+        }
+        assert_eq!(
+            global_state.register_submitter().unwrap_err(),
+            ICBError::OracleSubmitterLimitReached.into()
+        );
+        
+        // Removing one frees its slot
+        global_state.unregister_submitter();
+        global_state.register_submitter().unwrap();
+        assert_eq!(global_state.oracle_submitter_count, 3);
+        
+        // Lowering the cap below the current count blocks further additions
+        // without touching existing submitters
+        ParameterUpdate { max_submitters: Some(2), ..Default::default() }
+            .apply(&mut global_state)
+            .unwrap();
+        assert_eq!(global_state.oracle_submitter_count, 3);
+        assert!(global_state.register_submitter().is_err());
+        
+        // The cap itself is bounded to keep submitter iteration affordable
+        for max_submitters in [0, MAX_ORACLE_SUBMITTERS + 1] {
+            assert!(ParameterUpdate { max_submitters: Some(max_submitters), ..Default::default() }
+                .apply(&mut global_state)
+                .is_err());
+        }
+    }
+    
+    #[test]
+    fn test_proposal_ids_start_at_one() {
+        let mut global_state = GlobalState {
+            proposal_counter: FIRST_PROPOSAL_ID,
+            ..Default::default()
+        };
+        
+        assert_eq!(global_state.allocate_proposal_id().unwrap(), 1);
+        assert_eq!(global_state.allocate_proposal_id().unwrap(), 2);
+        assert_eq!(global_state.proposal_counter, 3);
+        
+        // 0 is never allocated, so it is free to mean "no proposal"
+        let mut legacy = GlobalState::default();
+        assert_eq!(
+            legacy.allocate_proposal_id().unwrap_err(),
+            ICBError::InvalidProposalId.into()
+        );
+        legacy.migrate_from(7);
+        assert_eq!(legacy.allocate_proposal_id().unwrap(), FIRST_PROPOSAL_ID);
+    }
+    
+    #[test]
+    fn test_proposal_counter_never_wraps() {
+        let mut global_state = GlobalState {
+            proposal_counter: u64::MAX - 1,
+            ..Default::default()
+        };
+        
+        assert_eq!(global_state.allocate_proposal_id().unwrap(), u64::MAX - 1);
+        assert_eq!(
+            global_state.allocate_proposal_id().unwrap_err(),
+            ICBError::CounterOverflow.into()
+        );
+        assert_eq!(global_state.proposal_counter, u64::MAX);
+    }
+    
+    #[test]
+    fn test_agent_action_cooldown() {
+        let mut agent_state = AgentState {
+            agent_pubkey: Pubkey::new_unique(),
+            nonce: 0,
+            last_action_timestamp: 0,
+            bump: 255,
+        };
+        
+        // First action is always allowed
+        assert!(agent_state.check_cooldown(10_000, 600).is_ok());
+        agent_state.last_action_timestamp = 10_000;
+        
+        // Second action within the cooldown is rejected
+        assert_eq!(
+            agent_state.check_cooldown(10_599, 600).unwrap_err(),
+            ICBError::ActionCooldownActive.into()
+        );
+        
+        // Allowed once the cooldown elapses
+        assert!(agent_state.check_cooldown(10_600, 600).is_ok());
+    }
 }
@@ -0,0 +1,405 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use crate::errors::ICBError;
+
+/// Size of the per-signature offsets header in an Ed25519 instruction
+pub const ED25519_OFFSETS_LEN: usize = 14;
+
+/// Offset of the first offsets header (after num_signatures + padding)
+pub const ED25519_OFFSETS_START: usize = 2;
+
+/// Instruction index meaning "data lives in the Ed25519 instruction itself"
+pub const ED25519_CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// A public key / message pair verified by an Ed25519 instruction
+#[derive(Debug)]
+pub struct VerifiedSignature<'a> {
+    pub pubkey: &'a [u8],
+    pub message: &'a [u8],
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16> {
+    let bytes = data
+        .get(offset..offset + 2)
+        .ok_or(ICBError::SignatureVerificationFailed)?;
+    Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn slice_at(data: &[u8], offset: u16, len: usize) -> Result<&[u8]> {
+    let start = offset as usize;
+    data.get(start..start + len)
+        .ok_or(error!(ICBError::SignatureVerificationFailed))
+}
+
+/// Parse every signature entry of an Ed25519 program instruction.
+///
+/// Layout: num_signatures (u8), padding (u8), then `num_signatures` offsets
+/// headers of 7 little-endian u16s: signature_offset,
+/// signature_instruction_index, public_key_offset,
+/// public_key_instruction_index, message_data_offset, message_data_size,
+/// message_instruction_index. Only entries whose key and message live in the
+/// Ed25519 instruction itself are accepted.
+pub fn parse_ed25519_signatures(data: &[u8]) -> Result<Vec<VerifiedSignature<'_>>> {
+    let num_signatures = *data.first().ok_or(ICBError::SignatureVerificationFailed)? as usize;
+    require!(num_signatures > 0, ICBError::SignatureVerificationFailed);
+    
+    let mut signatures = Vec::with_capacity(num_signatures);
+    for i in 0..num_signatures {
+        let header = ED25519_OFFSETS_START + i * ED25519_OFFSETS_LEN;
+        let public_key_offset = read_u16(data, header + 4)?;
+        let public_key_instruction_index = read_u16(data, header + 6)?;
+        let message_data_offset = read_u16(data, header + 8)?;
+        let message_data_size = read_u16(data, header + 10)?;
+        let message_instruction_index = read_u16(data, header + 12)?;
+        
+        // Data referenced from other instructions can't be checked here
+        require!(
+            public_key_instruction_index == ED25519_CURRENT_INSTRUCTION
+                && message_instruction_index == ED25519_CURRENT_INSTRUCTION,
+            ICBError::SignatureVerificationFailed
+        );
+        
+        signatures.push(VerifiedSignature {
+            pubkey: slice_at(data, public_key_offset, 32)?,
+            message: slice_at(data, message_data_offset, message_data_size as usize)?,
+        });
+    }
+    
+    Ok(signatures)
+}
+
+/// For each expected (agent, message) pair, report whether the Ed25519
+/// instruction data contains a matching verified signature
+pub fn match_expected_signers(data: &[u8], expected: &[(Pubkey, Vec<u8>)]) -> Result<Vec<bool>> {
+    let signatures = parse_ed25519_signatures(data)?;
+    
+    Ok(expected
+        .iter()
+        .map(|(agent, message)| {
+            signatures
+                .iter()
+                .any(|sig| sig.pubkey == agent.as_ref() && sig.message == message.as_slice())
+        })
+        .collect())
+}
+
+/// Minimum Ed25519 instruction size validate_agent_auth accepts
+pub const ED25519_MIN_DATA_LEN: usize = 100;
+
+/// Offset of the public key in a single-signature Ed25519 instruction
+pub const ED25519_PUBKEY_OFFSET: usize = 16;
+
+/// How many instructions before the current one validate_agent_auth searches
+/// for the agent's Ed25519 verification, so compute-budget or other
+/// instructions may sit in between
+pub const MAX_ED25519_LOOKBACK: usize = 4;
+
+/// Result of checking the Ed25519 instruction in front of an agent action.
+/// Every failure corresponds to one way validate_agent_auth rejects.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AuthDiagnosis {
+    Valid,
+    MissingPrevInstruction,
+    WrongProgram,
+    MalformedInstruction,
+    PubkeyMismatch,
+    MessageMismatch,
+}
+
+impl AuthDiagnosis {
+    /// Error validate_agent_auth raises for this diagnosis
+    pub fn to_error(self) -> Option<Error> {
+        match self {
+            AuthDiagnosis::Valid => None,
+            AuthDiagnosis::MissingPrevInstruction => Some(error!(ICBError::MissingSignatureVerification)),
+            AuthDiagnosis::WrongProgram => Some(error!(ICBError::InvalidSignatureProgram)),
+            AuthDiagnosis::MalformedInstruction => Some(error!(ICBError::SignatureVerificationFailed)),
+            AuthDiagnosis::PubkeyMismatch => Some(error!(ICBError::AgentMismatch)),
+            AuthDiagnosis::MessageMismatch => Some(error!(ICBError::SignatureVerificationFailed)),
+        }
+    }
+}
+
+/// Up to `max_lookback` instructions before the current one, nearest first
+pub fn load_preceding_instructions(
+    instructions_sysvar: &AccountInfo,
+    max_lookback: usize,
+) -> Result<Vec<Instruction>> {
+    let current_index = sysvar_instructions::load_current_index_checked(instructions_sysvar)? as usize;
+    
+    (1..=max_lookback.min(current_index))
+        .map(|back| {
+            sysvar_instructions::load_instruction_at_checked(current_index - back, instructions_sysvar)
+                .map_err(Into::into)
+        })
+        .collect()
+}
+
+/// Search `preceding` (nearest first) for the first Ed25519 instruction
+/// verifying `expected_agent` and diagnose it. When none matches, report
+/// why the nearest Ed25519 instruction, or failing that the nearest
+/// instruction, does not qualify.
+pub fn diagnose_agent_auth_window(
+    preceding: &[Instruction],
+    expected_agent: &Pubkey,
+    expected_message: Option<&[u8]>,
+) -> AuthDiagnosis {
+    let mut nearest_ed25519 = None;
+    
+    for ix in preceding.iter().filter(|ix| ix.program_id == ed25519_program::ID) {
+        let diagnosis = diagnose_agent_auth(Some(ix), expected_agent, expected_message);
+        if matches!(diagnosis, AuthDiagnosis::Valid | AuthDiagnosis::MessageMismatch) {
+            return diagnosis;
+        }
+        nearest_ed25519.get_or_insert(diagnosis);
+    }
+    
+    nearest_ed25519.unwrap_or_else(|| diagnose_agent_auth(preceding.first(), expected_agent, expected_message))
+}
+
+/// Check that `prev_ix` is an Ed25519 verification of `expected_agent`'s
+/// key and, when given, of `expected_message`. Checks run in the order
+/// validate_agent_auth applies them and the first failure is reported.
+pub fn diagnose_agent_auth(
+    prev_ix: Option<&Instruction>,
+    expected_agent: &Pubkey,
+    expected_message: Option<&[u8]>,
+) -> AuthDiagnosis {
+    let Some(prev_ix) = prev_ix else {
+        return AuthDiagnosis::MissingPrevInstruction;
+    };
+    if prev_ix.program_id != ed25519_program::ID {
+        return AuthDiagnosis::WrongProgram;
+    }
+    if prev_ix.data.len() < ED25519_MIN_DATA_LEN {
+        return AuthDiagnosis::MalformedInstruction;
+    }
+    
+    let pubkey = &prev_ix.data[ED25519_PUBKEY_OFFSET..ED25519_PUBKEY_OFFSET + 32];
+    if pubkey != expected_agent.as_ref() {
+        return AuthDiagnosis::PubkeyMismatch;
+    }
+    
+    if let Some(expected_message) = expected_message {
+        let Ok(signatures) = parse_ed25519_signatures(&prev_ix.data) else {
+            return AuthDiagnosis::MalformedInstruction;
+        };
+        if signatures[0].message != expected_message {
+            return AuthDiagnosis::MessageMismatch;
+        }
+    }
+    
+    AuthDiagnosis::Valid
+}
+
+/// Multi-signature counterpart of `validate_agent_auth`: checks the Ed25519
+/// instruction preceding the current one and returns, per expected
+/// (agent, message) pair, whether it was verified. Used by batch votes.
+pub fn validate_agent_auth_batch(
+    instructions_sysvar: &AccountInfo,
+    expected: &[(Pubkey, Vec<u8>)],
+) -> Result<Vec<bool>> {
+    let current_index = sysvar_instructions::load_current_index_checked(instructions_sysvar)?;
+    if current_index == 0 {
+        return err!(ICBError::MissingSignatureVerification);
+    }
+    
+    let prev_ix = sysvar_instructions::load_instruction_at_checked(
+        (current_index - 1) as usize,
+        instructions_sysvar,
+    )?;
+    if prev_ix.program_id != ed25519_program::ID {
+        return err!(ICBError::InvalidSignatureProgram);
+    }
+    
+    match_expected_signers(&prev_ix.data, expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// Build Ed25519 instruction data carrying the given (pubkey, message) pairs
+    /// with zeroed signatures, using the same layout as the native program
+    fn ed25519_data(entries: &[(Pubkey, &[u8])]) -> Vec<u8> {
+        let mut data = vec![entries.len() as u8, 0];
+        let mut payload = Vec::new();
+        let payload_start = ED25519_OFFSETS_START + entries.len() * ED25519_OFFSETS_LEN;
+        
+        for (pubkey, message) in entries {
+            let public_key_offset = (payload_start + payload.len()) as u16;
+            payload.extend_from_slice(pubkey.as_ref());
+            let signature_offset = (payload_start + payload.len()) as u16;
+            payload.extend_from_slice(&[0u8; 64]);
+            let message_data_offset = (payload_start + payload.len()) as u16;
+            payload.extend_from_slice(message);
+            
+            for value in [
+                signature_offset,
+                ED25519_CURRENT_INSTRUCTION,
+                public_key_offset,
+                ED25519_CURRENT_INSTRUCTION,
+                message_data_offset,
+                message.len() as u16,
+                ED25519_CURRENT_INSTRUCTION,
+            ] {
+                data.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        
+        data.extend_from_slice(&payload);
+        data
+    }
+    
+    #[test]
+    fn test_two_signature_instruction() {
+        let (alice, bob, eve) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let data = ed25519_data(&[(alice, b"vote-a"), (bob, b"vote-b")]);
+        
+        // Both match
+        let both = [(alice, b"vote-a".to_vec()), (bob, b"vote-b".to_vec())];
+        assert_eq!(match_expected_signers(&data, &both).unwrap(), vec![true, true]);
+        
+        // Only one matches (wrong agent, then wrong message)
+        let one = [(alice, b"vote-a".to_vec()), (eve, b"vote-b".to_vec())];
+        assert_eq!(match_expected_signers(&data, &one).unwrap(), vec![true, false]);
+        let one = [(alice, b"vote-x".to_vec()), (bob, b"vote-b".to_vec())];
+        assert_eq!(match_expected_signers(&data, &one).unwrap(), vec![false, true]);
+        
+        // Neither matches
+        let neither = [(eve, b"vote-a".to_vec()), (bob, b"vote-a".to_vec())];
+        assert_eq!(match_expected_signers(&data, &neither).unwrap(), vec![false, false]);
+    }
+    
+    fn ed25519_ix(data: Vec<u8>) -> Instruction {
+        Instruction { program_id: ed25519_program::ID, accounts: Vec::new(), data }
+    }
+    
+    #[test]
+    fn test_auth_diagnosis_codes() {
+        let agent = Pubkey::new_unique();
+        let message: &[u8] = b"vote:proposal-1:yes";
+        let valid = ed25519_ix(ed25519_data(&[(agent, message)]));
+        
+        assert_eq!(diagnose_agent_auth(Some(&valid), &agent, Some(message)), AuthDiagnosis::Valid);
+        assert_eq!(diagnose_agent_auth(Some(&valid), &agent, None), AuthDiagnosis::Valid);
+        
+        assert_eq!(
+            diagnose_agent_auth(None, &agent, Some(message)),
+            AuthDiagnosis::MissingPrevInstruction
+        );
+        
+        let wrong_program = Instruction { program_id: Pubkey::new_unique(), ..valid.clone() };
+        assert_eq!(
+            diagnose_agent_auth(Some(&wrong_program), &agent, Some(message)),
+            AuthDiagnosis::WrongProgram
+        );
+        
+        let truncated = ed25519_ix(valid.data[..ED25519_MIN_DATA_LEN - 1].to_vec());
+        assert_eq!(
+            diagnose_agent_auth(Some(&truncated), &agent, Some(message)),
+            AuthDiagnosis::MalformedInstruction
+        );
+        
+        assert_eq!(
+            diagnose_agent_auth(Some(&valid), &Pubkey::new_unique(), Some(message)),
+            AuthDiagnosis::PubkeyMismatch
+        );
+        
+        assert_eq!(
+            diagnose_agent_auth(Some(&valid), &agent, Some(b"vote:proposal-1:no")),
+            AuthDiagnosis::MessageMismatch
+        );
+    }
+    
+    #[test]
+    fn test_ed25519_found_past_compute_budget_ix() {
+        let agent = Pubkey::new_unique();
+        let message: &[u8] = b"vote:proposal-1:yes";
+        let compute_budget = Instruction {
+            program_id: Pubkey::new_unique(),
+            accounts: Vec::new(),
+            data: vec![2, 0, 0, 0, 0],
+        };
+        let signed = ed25519_ix(ed25519_data(&[(agent, message)]));
+        
+        // [ed25519, compute budget, vote]: scanned nearest first from the vote
+        let preceding = vec![compute_budget.clone(), signed.clone()];
+        assert_eq!(diagnose_agent_auth_window(&preceding, &agent, Some(message)), AuthDiagnosis::Valid);
+        assert_eq!(diagnose_agent_auth_window(&preceding, &agent, None), AuthDiagnosis::Valid);
+        
+        // Another agent's verification in between is skipped over
+        let other = ed25519_ix(ed25519_data(&[(Pubkey::new_unique(), message)]));
+        let preceding = vec![other.clone(), compute_budget.clone(), signed.clone()];
+        assert_eq!(diagnose_agent_auth_window(&preceding, &agent, Some(message)), AuthDiagnosis::Valid);
+        
+        // The matching verification still has to cover the expected message
+        assert_eq!(
+            diagnose_agent_auth_window(&preceding, &agent, Some(b"vote:proposal-1:no")),
+            AuthDiagnosis::MessageMismatch
+        );
+        
+        // Nothing for this agent: the nearest Ed25519 instruction is reported
+        assert_eq!(
+            diagnose_agent_auth_window(&[compute_budget.clone(), other], &agent, None),
+            AuthDiagnosis::PubkeyMismatch
+        );
+        
+        // No Ed25519 instruction at all, or nothing before the vote
+        assert_eq!(
+            diagnose_agent_auth_window(&[compute_budget.clone()], &agent, None),
+            AuthDiagnosis::WrongProgram
+        );
+        assert_eq!(diagnose_agent_auth_window(&[], &agent, None), AuthDiagnosis::MissingPrevInstruction);
+        
+        // Beyond the lookback window it is not found
+        let mut preceding = vec![compute_budget; MAX_ED25519_LOOKBACK];
+        preceding.push(signed);
+        assert_eq!(
+            diagnose_agent_auth_window(&preceding[..MAX_ED25519_LOOKBACK], &agent, None),
+            AuthDiagnosis::WrongProgram
+        );
+    }
+    
+    #[test]
+    fn test_auth_diagnosis_matches_validate_errors() {
+        assert!(AuthDiagnosis::Valid.to_error().is_none());
+        assert_eq!(
+            AuthDiagnosis::MissingPrevInstruction.to_error().unwrap(),
+            ICBError::MissingSignatureVerification.into()
+        );
+        assert_eq!(
+            AuthDiagnosis::WrongProgram.to_error().unwrap(),
+            ICBError::InvalidSignatureProgram.into()
+        );
+        assert_eq!(
+            AuthDiagnosis::PubkeyMismatch.to_error().unwrap(),
+            ICBError::AgentMismatch.into()
+        );
+        for diagnosis in [AuthDiagnosis::MalformedInstruction, AuthDiagnosis::MessageMismatch] {
+            assert_eq!(diagnosis.to_error().unwrap(), ICBError::SignatureVerificationFailed.into());
+        }
+    }
+    
+    #[test]
+    fn test_malformed_instruction_rejected() {
+        let alice = Pubkey::new_unique();
+        let data = ed25519_data(&[(alice, b"vote")]);
+        
+        // Truncated payload
+        assert_eq!(
+            parse_ed25519_signatures(&data[..data.len() - 1]).unwrap_err(),
+            ICBError::SignatureVerificationFailed.into()
+        );
+        
+        // No signatures
+        assert!(parse_ed25519_signatures(&[0, 0]).is_err());
+        
+        // Key referenced from another instruction
+        let mut external = data.clone();
+        external[ED25519_OFFSETS_START + 6..ED25519_OFFSETS_START + 8].copy_from_slice(&0u16.to_le_bytes());
+        assert!(parse_ed25519_signatures(&external).is_err());
+    }
+}
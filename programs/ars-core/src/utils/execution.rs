@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+use crate::constants::EXECUTION_DELAY;
+use crate::errors::ICBError;
+use crate::state::{PolicyProposal, ProposalStatus};
+
+/// Only Active (to finalize) and Passed (to execute) proposals can be acted
+/// on; every terminal status maps to its own error
+pub fn check_executable_status(status: &ProposalStatus) -> Result<()> {
+    match status {
+        ProposalStatus::Active | ProposalStatus::Passed => Ok(()),
+        ProposalStatus::Executed => err!(ICBError::ProposalAlreadyExecuted),
+        ProposalStatus::Failed => err!(ICBError::ProposalFailedCannotExecute),
+        ProposalStatus::Cancelled => err!(ICBError::ProposalCancelled),
+    }
+}
+
+/// Earliest time a passed proposal can be executed, rejecting i64 overflow
+pub fn execution_ready_at(passed_at: i64) -> Result<i64> {
+    passed_at
+        .checked_add(EXECUTION_DELAY)
+        .ok_or(error!(ICBError::ArithmeticOverflow))
+}
+
+/// Whether `proposal` can be executed at `now`: it has passed and its
+/// execution delay is over. An Active proposal still has to be finalized
+/// and is not executable yet; terminal statuses fail as in
+/// check_executable_status. The delay is only evaluated for Passed
+/// proposals. Every path deciding executability goes through here.
+pub fn is_executable(proposal: &PolicyProposal, now: i64) -> Result<bool> {
+    check_executable_status(&proposal.status)?;
+    if proposal.status != ProposalStatus::Passed {
+        return Ok(false);
+    }
+    Ok(now >= execution_ready_at(proposal.passed_at)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const PASSED_AT: i64 = 1_700_000_000;
+    
+    fn proposal(status: ProposalStatus, passed_at: i64) -> PolicyProposal {
+        PolicyProposal {
+            id: 1,
+            proposer: Pubkey::new_unique(),
+            status,
+            passed_at,
+            bump: 255,
+            ..Default::default()
+        }
+    }
+    
+    #[test]
+    fn test_execution_ready_at() {
+        assert_eq!(execution_ready_at(1_000).unwrap(), 1_000 + EXECUTION_DELAY);
+        assert_eq!(execution_ready_at(i64::MAX - EXECUTION_DELAY).unwrap(), i64::MAX);
+        assert_eq!(
+            execution_ready_at(i64::MAX - EXECUTION_DELAY + 1).unwrap_err(),
+            ICBError::ArithmeticOverflow.into()
+        );
+    }
+    
+    #[test]
+    fn test_terminal_status_errors() {
+        assert!(check_executable_status(&ProposalStatus::Active).is_ok());
+        assert!(check_executable_status(&ProposalStatus::Passed).is_ok());
+        
+        assert_eq!(
+            check_executable_status(&ProposalStatus::Executed).unwrap_err(),
+            ICBError::ProposalAlreadyExecuted.into()
+        );
+        assert_eq!(
+            check_executable_status(&ProposalStatus::Failed).unwrap_err(),
+            ICBError::ProposalFailedCannotExecute.into()
+        );
+        assert_eq!(
+            check_executable_status(&ProposalStatus::Cancelled).unwrap_err(),
+            ICBError::ProposalCancelled.into()
+        );
+    }
+    
+    #[test]
+    fn test_is_executable_passed_timing() {
+        let passed = proposal(ProposalStatus::Passed, PASSED_AT);
+        let ready_at = PASSED_AT + EXECUTION_DELAY;
+        
+        assert!(!is_executable(&passed, PASSED_AT).unwrap());
+        assert!(!is_executable(&passed, ready_at - 1).unwrap());
+        assert!(is_executable(&passed, ready_at).unwrap());
+        assert!(is_executable(&passed, ready_at + 1).unwrap());
+        
+        // An overflowing deadline is an error, not "never"
+        let overflow = proposal(ProposalStatus::Passed, i64::MAX);
+        assert_eq!(
+            is_executable(&overflow, i64::MAX).unwrap_err(),
+            ICBError::ArithmeticOverflow.into()
+        );
+    }
+    
+    #[test]
+    fn test_is_executable_by_status() {
+        let late = PASSED_AT + EXECUTION_DELAY * 10;
+        
+        // Still voting or awaiting finalization, whatever the time
+        for now in [0, PASSED_AT, late] {
+            assert!(!is_executable(&proposal(ProposalStatus::Active, 0), now).unwrap());
+        }
+        
+        // Terminal statuses error at any time, even with a stale passed_at
+        for now in [0, late] {
+            for (status, error) in [
+                (ProposalStatus::Executed, ICBError::ProposalAlreadyExecuted),
+                (ProposalStatus::Failed, ICBError::ProposalFailedCannotExecute),
+                (ProposalStatus::Cancelled, ICBError::ProposalCancelled),
+            ] {
+                assert_eq!(
+                    is_executable(&proposal(status, PASSED_AT), now).unwrap_err(),
+                    error.into()
+                );
+            }
+        }
+    }
+}
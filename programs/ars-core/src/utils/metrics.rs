@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use anchor_lang::error::Error;
+use crate::errors::ICBError;
+use crate::state::{ComputeUnitsReported, RejectionCategory, RejectionEvent};
+use anchor_lang::solana_program::compute_units::sol_remaining_compute_units;
+
+/// Map an ICBError code to the category operators alert on
+pub fn classify_rejection(error_code: u32) -> RejectionCategory {
+    let matches_any = |errors: &[ICBError]| {
+        errors.iter().any(|e| u32::from(*e) == error_code)
+    };
+    
+    if matches_any(&[
+        ICBError::Unauthorized,
+        ICBError::InvalidAgentSignature,
+        ICBError::InvalidSignatureProgram,
+        ICBError::SignatureVerificationFailed,
+        ICBError::MissingSignatureVerification,
+        ICBError::AgentMismatch,
+        ICBError::InvalidNonce,
+        ICBError::SignatureExpired,
+        ICBError::LargeILIMoveNeedsCosigner,
+    ]) {
+        RejectionCategory::Auth
+    } else if matches_any(&[ICBError::OracleStale]) {
+        RejectionCategory::StaleOracle
+    } else if matches_any(&[
+        ICBError::ArithmeticOverflow,
+        ICBError::ArithmeticUnderflow,
+        ICBError::CounterOverflow,
+        ICBError::MathOverflow,
+        ICBError::MathUnderflow,
+    ]) {
+        RejectionCategory::Overflow
+    } else if matches_any(&[ICBError::ReentrancyDetected]) {
+        RejectionCategory::Reentrancy
+    } else {
+        RejectionCategory::Other
+    }
+}
+
+/// Emit a classified RejectionEvent for a handler error and pass it through.
+/// State changes revert with the transaction, but the event stays in the
+/// transaction logs for indexers to aggregate.
+///
+/// Only errors returned by a handler wrapped with this in lib.rs are seen.
+/// Account constraint failures (has_one, seeds, signer, custom
+/// `constraint = ... @ ICBError::...`) are raised while Anchor validates
+/// the accounts, before the handler runs, and Anchor 0.30 offers no hook
+/// there, so they fail without a RejectionEvent. Monitors should count
+/// failed transactions with no event as unclassified rejections.
+pub fn report_rejection(error: Error) -> Error {
+    if let Error::AnchorError(anchor_error) = &error {
+        let error_code = anchor_error.error_code_number;
+        let timestamp = Clock::get().map(|clock| clock.unix_timestamp).unwrap_or(0);
+        
+        emit!(RejectionEvent {
+            category: classify_rejection(error_code),
+            error_code,
+            timestamp,
+        });
+    }
+    error
+}
+
+/// Compute units left for a crank that processed `items_processed` items.
+/// Always built so it stays tested; only the emit is behind the feature.
+pub fn compute_report(instruction: &str, items_processed: u32) -> ComputeUnitsReported {
+    ComputeUnitsReported {
+        instruction: instruction.to_string(),
+        items_processed,
+        remaining_units: sol_remaining_compute_units(),
+    }
+}
+
+/// Emit a ComputeUnitsReported for the current crank
+#[cfg(feature = "compute-report")]
+pub fn report_compute_units(instruction: &str, items_processed: u32) {
+    emit!(compute_report(instruction, items_processed));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_rejection_classification() {
+        let cases = [
+            (ICBError::AgentMismatch, RejectionCategory::Auth),
+            (ICBError::Unauthorized, RejectionCategory::Auth),
+            (ICBError::OracleStale, RejectionCategory::StaleOracle),
+            (ICBError::ArithmeticOverflow, RejectionCategory::Overflow),
+            (ICBError::MathUnderflow, RejectionCategory::Overflow),
+            (ICBError::ReentrancyDetected, RejectionCategory::Reentrancy),
+            (ICBError::ProposalNotActive, RejectionCategory::Other),
+        ];
+        
+        for (error, category) in cases {
+            assert_eq!(classify_rejection(error.into()), category);
+        }
+        
+        // Codes outside the program's range are not misclassified
+        assert_eq!(classify_rejection(0), RejectionCategory::Other);
+    }
+    
+    #[test]
+    fn test_report_rejection_passes_error_through() {
+        let error = report_rejection(error!(ICBError::OracleStale));
+        assert_eq!(error, ICBError::OracleStale.into());
+    }
+    
+    #[test]
+    fn test_compute_report() {
+        let report = compute_report("sweep_closed", 12);
+        
+        assert_eq!(report.instruction, "sweep_closed");
+        assert_eq!(report.items_processed, 12);
+        assert!(report.remaining_units <= crate::constants::MAX_TRANSACTION_COMPUTE_UNITS);
+    }
+}
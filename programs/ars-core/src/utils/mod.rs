@@ -1,5 +1,11 @@
 pub mod signature;
+pub mod ed25519;
 pub mod reentrancy;
+pub mod metrics;
+pub mod execution;
 
 pub use signature::*;
+pub use ed25519::*;
 pub use reentrancy::*;
+pub use metrics::*;
+pub use execution::*;
@@ -1,6 +1,8 @@
 // FIX #1: Ed25519 Signature Verification Implementation
 use anchor_lang::prelude::*;
+use crate::constants::{MAX_SIGNATURE_AGE, MAX_SIGNATURE_CLOCK_DRIFT};
 use crate::errors::ICBError;
+use crate::state::AgentState;
 
 /// Verify Ed25519 signature for agent actions
 /// 
@@ -27,7 +29,7 @@ pub fn verify_agent_signature(
     Ok(())
 }
 
-/// Construct message for proposal creation
+/// Construct message for proposal creation (see `ars_common::construct_proposal_message`)
 pub fn construct_proposal_message(
     agent_pubkey: &Pubkey,
     policy_type: u8,
@@ -35,17 +37,16 @@ pub fn construct_proposal_message(
     timestamp: i64,
     nonce: u64,
 ) -> Vec<u8> {
-    let mut message = Vec::new();
-    message.extend_from_slice(b"ARS_CREATE_PROPOSAL");
-    message.extend_from_slice(agent_pubkey.as_ref());
-    message.push(policy_type);
-    message.extend_from_slice(policy_params);
-    message.extend_from_slice(&timestamp.to_le_bytes());
-    message.extend_from_slice(&nonce.to_le_bytes());
-    message
+    ars_common::construct_proposal_message(
+        &agent_pubkey.to_bytes(),
+        policy_type,
+        policy_params,
+        timestamp,
+        nonce,
+    )
 }
 
-/// Construct message for voting
+/// Construct message for voting (see `ars_common::construct_vote_message`)
 pub fn construct_vote_message(
     agent_pubkey: &Pubkey,
     proposal_id: u64,
@@ -54,27 +55,52 @@ pub fn construct_vote_message(
     timestamp: i64,
     nonce: u64,
 ) -> Vec<u8> {
-    let mut message = Vec::new();
-    message.extend_from_slice(b"ARS_VOTE");
-    message.extend_from_slice(agent_pubkey.as_ref());
-    message.extend_from_slice(&proposal_id.to_le_bytes());
-    message.push(if prediction { 1 } else { 0 });
-    message.extend_from_slice(&stake_amount.to_le_bytes());
-    message.extend_from_slice(&timestamp.to_le_bytes());
-    message.extend_from_slice(&nonce.to_le_bytes());
-    message
+    ars_common::construct_vote_message(
+        &agent_pubkey.to_bytes(),
+        proposal_id,
+        prediction,
+        stake_amount,
+        timestamp,
+        nonce,
+    )
 }
 
-/// Validate timestamp is recent (within 5 minutes)
+/// Validate timestamp is recent (under MAX_SIGNATURE_AGE old) and not more
+/// than MAX_SIGNATURE_CLOCK_DRIFT ahead of the cluster clock
 pub fn validate_timestamp(timestamp: i64) -> Result<()> {
     let current_time = Clock::get()?.unix_timestamp;
-    let time_diff = (current_time - timestamp).abs();
+    check_timestamp_window(timestamp, current_time, MAX_SIGNATURE_AGE, MAX_SIGNATURE_CLOCK_DRIFT)
+}
+
+/// Accept `timestamp` if it is less than `max_age` seconds behind `now` and
+/// at most `max_drift` seconds ahead of it
+pub fn check_timestamp_window(timestamp: i64, now: i64, max_age: i64, max_drift: i64) -> Result<()> {
+    let age = now.saturating_sub(timestamp);
+    
+    require!(age < max_age, ICBError::SignatureExpired);
+    require!(age >= -max_drift, ICBError::SignatureExpired);
     
-    require!(
-        time_diff < 300, // 5 minutes
-        ICBError::SignatureExpired
-    );
+    Ok(())
+}
+
+/// Anti-replay check for a signed agent action. The signed message embeds
+/// the agent's current nonce and a timestamp: the nonce must match
+/// `agent_state.nonce` and the timestamp must be within `max_age` seconds
+/// of `now`. On success the nonce is consumed so the same message cannot
+/// be submitted again.
+pub fn verify_fresh_signed_action(
+    agent_state: &mut AgentState,
+    nonce: u64,
+    timestamp: i64,
+    now: i64,
+    max_age: i64,
+) -> Result<()> {
+    require!(nonce == agent_state.nonce, ICBError::InvalidNonce);
+    check_timestamp_window(timestamp, now, max_age, MAX_SIGNATURE_CLOCK_DRIFT)?;
     
+    agent_state.nonce = agent_state.nonce
+        .checked_add(1)
+        .ok_or(ICBError::MathOverflow)?;
     Ok(())
 }
 
@@ -123,4 +149,101 @@ mod tests {
         assert!(message.starts_with(b"ARS_VOTE"));
         assert!(message.len() > 8); // Prefix + data
     }
+    
+    #[test]
+    fn test_timestamp_window() {
+        let now = 1_700_000_000;
+        let check = |timestamp| {
+            check_timestamp_window(timestamp, now, MAX_SIGNATURE_AGE, MAX_SIGNATURE_CLOCK_DRIFT)
+        };
+        
+        // Past: up to just under 5 minutes
+        assert!(check(now).is_ok());
+        assert!(check(now - MAX_SIGNATURE_AGE + 1).is_ok());
+        assert_eq!(check(now - MAX_SIGNATURE_AGE).unwrap_err(), ICBError::SignatureExpired.into());
+        
+        // Future: only small clock skew, no pre-signing
+        assert!(check(now + MAX_SIGNATURE_CLOCK_DRIFT).is_ok());
+        assert!(check(now + MAX_SIGNATURE_CLOCK_DRIFT + 1).is_err());
+        assert_eq!(check(now + 60).unwrap_err(), ICBError::SignatureExpired.into());
+        
+        // No overflow at the extremes
+        assert!(check(i64::MIN).is_err());
+        assert!(check(i64::MAX).is_err());
+    }
+    
+    #[test]
+    fn test_messages_match_client_crate() {
+        let pubkey = Pubkey::new_unique();
+        
+        // Byte-identical to a client building the message from raw key bytes
+        let mut expected = b"ARS_CREATE_PROPOSAL".to_vec();
+        expected.extend_from_slice(pubkey.as_ref());
+        expected.push(1);
+        expected.extend_from_slice(&[1, 2, 3]);
+        expected.extend_from_slice(&1234567890i64.to_le_bytes());
+        expected.extend_from_slice(&42u64.to_le_bytes());
+        
+        assert_eq!(construct_proposal_message(&pubkey, 1, &[1, 2, 3], 1234567890, 42), expected);
+        assert_eq!(
+            ars_common::construct_proposal_message(&pubkey.to_bytes(), 1, &[1, 2, 3], 1234567890, 42),
+            expected
+        );
+        
+        let mut expected = b"ARS_VOTE".to_vec();
+        expected.extend_from_slice(pubkey.as_ref());
+        expected.extend_from_slice(&7u64.to_le_bytes());
+        expected.push(0);
+        expected.extend_from_slice(&1000u64.to_le_bytes());
+        expected.extend_from_slice(&1234567890i64.to_le_bytes());
+        expected.extend_from_slice(&42u64.to_le_bytes());
+        
+        assert_eq!(construct_vote_message(&pubkey, 7, false, 1000, 1234567890, 42), expected);
+        assert_eq!(
+            ars_common::construct_vote_message(&pubkey.to_bytes(), 7, false, 1000, 1234567890, 42),
+            expected
+        );
+    }
+    
+    fn agent_state(nonce: u64) -> AgentState {
+        AgentState {
+            agent_pubkey: Pubkey::new_unique(),
+            nonce,
+            last_action_timestamp: 0,
+            bump: 255,
+        }
+    }
+    
+    #[test]
+    fn test_verify_fresh_signed_action() {
+        let now = 1_700_000_000;
+        let max_age = 120;
+        
+        // Valid: current nonce, recent timestamp; the nonce is consumed
+        let mut state = agent_state(7);
+        verify_fresh_signed_action(&mut state, 7, now - 30, now, max_age).unwrap();
+        assert_eq!(state.nonce, 8);
+        
+        // Replaying the same message now fails on the nonce
+        assert_eq!(
+            verify_fresh_signed_action(&mut state, 7, now - 30, now, max_age).unwrap_err(),
+            ICBError::InvalidNonce.into()
+        );
+        
+        // Stale timestamp under the configured age
+        assert_eq!(
+            verify_fresh_signed_action(&mut state, 8, now - max_age, now, max_age).unwrap_err(),
+            ICBError::SignatureExpired.into()
+        );
+        assert_eq!(state.nonce, 8);
+        
+        // Wrong nonce, ahead or behind
+        for nonce in [0, 9, u64::MAX] {
+            assert_eq!(
+                verify_fresh_signed_action(&mut state, nonce, now, now, max_age).unwrap_err(),
+                ICBError::InvalidNonce.into()
+            );
+        }
+        assert_eq!(state.nonce, 8);
+    }
 }
@@ -37,4 +37,95 @@ pub enum ReserveError {
     
     #[msg("Invalid PDA derivation")]
     InvalidPDA,
+    
+    #[msg("No accrued fees to sweep")]
+    NoFeesToSweep,
+    
+    #[msg("Invalid treasury account")]
+    InvalidTreasury,
+    
+    #[msg("Invalid asset price")]
+    InvalidPrice,
+    
+    #[msg("Asset price is stale")]
+    StalePrice,
+    
+    #[msg("Deposit exceeds the per-transaction cap")]
+    DepositExceedsTxCap,
+    
+    #[msg("Deposit would exceed the vault cap")]
+    DepositExceedsVaultCap,
+    
+    #[msg("Invalid target VHR")]
+    InvalidVHRTarget,
+    
+    #[msg("Target weights must sum to 10000 bps")]
+    InvalidTargetWeights,
+    
+    #[msg("Swap deadline exceeded")]
+    DeadlineExceeded,
+    
+    #[msg("Duplicate request for this idempotency key")]
+    DuplicateRequest,
+    
+    #[msg("Idempotency record account missing for the supplied key")]
+    MissingIdempotencyRecord,
+    
+    #[msg("Idempotency record is still within its window")]
+    IdempotencyWindowActive,
+    
+    #[msg("Invalid price staleness window")]
+    InvalidPriceStaleness,
+    
+    #[msg("Invalid reserve asset configuration")]
+    InvalidAssetConfig,
+    
+    #[msg("Insurance share must be at most 10000 bps")]
+    InvalidInsuranceShare,
+    
+    #[msg("Insurance fund can only be drawn while undercollateralized")]
+    InsuranceDrawNotAllowed,
+    
+    #[msg("Vault already holds the maximum number of assets")]
+    TooManyAssets,
+    
+    #[msg("Token account mint does not match the vault mint")]
+    InvalidTokenMint,
+    
+    #[msg("Division by zero")]
+    DivisionByZero,
+    
+    #[msg("Withdrawal fee exceeds the maximum")]
+    InvalidWithdrawFee,
+    
+    #[msg("Vault token account is not owned by the vault PDA")]
+    VaultNotTokenOwner,
+    
+    #[msg("Account is not the ars-core global state")]
+    InvalidGovernanceState,
+    
+    #[msg("Invalid vault authority")]
+    InvalidAuthority,
+    
+    #[msg("Withdrawal recipient is already allowlisted")]
+    WithdrawRecipientAlreadyAllowed,
+    
+    #[msg("Withdrawal recipient allowlist is full")]
+    WithdrawRecipientAllowlistFull,
+    
+    #[msg("Withdrawal recipient is not allowlisted")]
+    WithdrawRecipientNotFound,
+    
+    #[msg("Recipient is not on the withdrawal allowlist")]
+    RecipientNotAllowlisted,
+    
+    #[msg("Account already migrated to the current version")]
+    AlreadyMigrated,
+}
+
+/// `error` carrying the mismatched keys, logged as Left (actual) / Right
+/// (expected), for key comparisons in `constraint = ...` that Anchor
+/// cannot annotate itself
+pub fn key_mismatch(error: ReserveError, actual: Pubkey, expected: Pubkey) -> anchor_lang::error::Error {
+    anchor_lang::error::Error::from(error).with_pubkeys((actual, expected))
 }
@@ -0,0 +1,294 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::state::*;
+use crate::errors::ReserveError;
+use crate::instructions::initialize_vault::{init_vault, VAULT_SEED};
+
+pub const ASSET_CONFIG_SEED: &[u8] = b"asset_config";
+
+/// Maximum number of assets registered by a single bootstrap_reserve, and
+/// the default vault asset cap
+pub const MAX_BOOTSTRAP_ASSETS: usize = 8;
+
+/// Highest asset cap governance can set
+pub const MAX_RESERVE_ASSETS: usize = 16;
+
+/// Initial configuration of one reserve asset
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct AssetParams {
+    pub mint: Pubkey,
+    pub target_weight_bps: u16,
+    pub min_weight_bps: u16,
+    pub max_weight_bps: u16,
+    pub volatility_threshold_bps: u16,
+    pub price_staleness_secs: i64,
+}
+
+impl AssetParams {
+    pub fn to_config(&self, bump: u8) -> AssetConfig {
+        AssetConfig {
+            mint: self.mint,
+            target_weight_bps: self.target_weight_bps,
+            min_weight_bps: self.min_weight_bps,
+            max_weight_bps: self.max_weight_bps,
+            volatility_threshold_bps: self.volatility_threshold_bps,
+            current_weight_bps: 0,
+            price_staleness_secs: self.price_staleness_secs,
+            accrued_fees: 0,
+            insurance_fund: 0,
+            price_usd: 0, // Recorded by update_vhr_from_prices
+            price_published_at: 0,
+            bump,
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct BootstrapReserve<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = ReserveVault::LEN,
+        seeds = [VAULT_SEED],
+        bump
+    )]
+    pub vault: Account<'info, ReserveVault>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Initialize the vault and create one AssetConfig per entry in `assets`.
+/// `remaining_accounts` holds the (uncreated) AssetConfig PDAs, seeded by
+/// [ASSET_CONFIG_SEED, mint], in the same order. Everything is validated
+/// before any account is written, and any failure reverts the whole bootstrap.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BootstrapReserve<'info>>,
+    rebalance_threshold_bps: u16,
+    assets: Vec<AssetParams>,
+) -> Result<()> {
+    validate_assets(&assets)?;
+    require!(
+        ctx.remaining_accounts.len() == assets.len(),
+        ReserveError::InvalidAssetConfig
+    );
+    
+    init_vault(
+        &mut ctx.accounts.vault,
+        ctx.accounts.authority.key(),
+        rebalance_threshold_bps,
+        ctx.bumps.vault,
+    )?;
+    ctx.accounts.vault.register_assets(assets.len())?;
+    
+    let rent = Rent::get()?.minimum_balance(AssetConfig::LEN);
+    
+    for (params, config_info) in assets.iter().zip(ctx.remaining_accounts) {
+        let (expected, bump) = Pubkey::find_program_address(
+            &[ASSET_CONFIG_SEED, params.mint.as_ref()],
+            ctx.program_id,
+        );
+        require_keys_eq!(config_info.key(), expected, ReserveError::InvalidPDA);
+        
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::CreateAccount {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: config_info.clone(),
+                },
+                &[&[ASSET_CONFIG_SEED, params.mint.as_ref(), &[bump]]],
+            ),
+            rent,
+            AssetConfig::LEN as u64,
+            ctx.program_id,
+        )?;
+        
+        let mut data = config_info.try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut data[..];
+        params.to_config(bump).try_serialize(&mut writer)?;
+        
+        msg!("Registered asset {} at {} bps", params.mint, params.target_weight_bps);
+    }
+    
+    msg!("Reserve bootstrapped with {} assets", assets.len());
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(params: AssetParams)]
+pub struct RegisterAsset<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        has_one = authority @ ReserveError::Unauthorized
+    )]
+    pub vault: Account<'info, ReserveVault>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = AssetConfig::LEN,
+        seeds = [ASSET_CONFIG_SEED, params.mint.as_ref()],
+        bump
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Add an asset to a bootstrapped reserve. It starts at zero target weight
+/// so the existing targets keep summing to 100%.
+pub fn register_asset(ctx: Context<RegisterAsset>, params: AssetParams) -> Result<()> {
+    validate_asset(&params)?;
+    require!(params.target_weight_bps == 0, ReserveError::InvalidTargetWeights);
+    
+    ctx.accounts.vault.register_assets(1)?;
+    ctx.accounts.asset_config.set_inner(params.to_config(ctx.bumps.asset_config));
+    
+    msg!("Registered asset {}", params.mint);
+    msg!("Assets: {} of {}", ctx.accounts.vault.asset_count, ctx.accounts.vault.max_assets);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetMaxAssets<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        has_one = authority @ ReserveError::Unauthorized
+    )]
+    pub vault: Account<'info, ReserveVault>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Cap the number of registered assets. The cap can never drop below the
+/// assets already registered.
+pub fn set_max_assets(ctx: Context<SetMaxAssets>, max_assets: u8) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    require!(
+        max_assets as usize <= MAX_RESERVE_ASSETS && max_assets >= vault.asset_count.max(1),
+        ReserveError::InvalidAssetConfig
+    );
+    
+    vault.max_assets = max_assets;
+    
+    msg!("Maximum reserve assets: {}", max_assets);
+    
+    Ok(())
+}
+
+/// Check one asset: min <= target <= max weight and a valid staleness window
+pub fn validate_asset(asset: &AssetParams) -> Result<()> {
+    require!(
+        asset.min_weight_bps <= asset.target_weight_bps
+            && asset.target_weight_bps <= asset.max_weight_bps
+            && asset.max_weight_bps <= 10000,
+        ReserveError::InvalidAssetConfig
+    );
+    require!(
+        (0..=MAX_CONFIGURABLE_PRICE_AGE).contains(&asset.price_staleness_secs),
+        ReserveError::InvalidPriceStaleness
+    );
+    Ok(())
+}
+
+/// Check the initial asset set: 1..=MAX_BOOTSTRAP_ASSETS distinct valid
+/// assets with target weights summing to 100%
+pub fn validate_assets(assets: &[AssetParams]) -> Result<()> {
+    require!(
+        !assets.is_empty() && assets.len() <= MAX_BOOTSTRAP_ASSETS,
+        ReserveError::InvalidAssetConfig
+    );
+    
+    for (i, asset) in assets.iter().enumerate() {
+        validate_asset(asset)?;
+        require!(
+            assets[..i].iter().all(|other| other.mint != asset.mint),
+            ReserveError::InvalidAssetConfig
+        );
+    }
+    
+    let total_weight: u32 = assets.iter().map(|a| a.target_weight_bps as u32).sum();
+    require!(total_weight == 10000, ReserveError::InvalidTargetWeights);
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn asset(target_weight_bps: u16) -> AssetParams {
+        AssetParams {
+            mint: Pubkey::new_unique(),
+            target_weight_bps,
+            min_weight_bps: 0,
+            max_weight_bps: 10000,
+            volatility_threshold_bps: 500,
+            price_staleness_secs: 0,
+        }
+    }
+    
+    #[test]
+    fn test_base_assets_accepted() {
+        let assets = [asset(4000), asset(4000), asset(2000)];
+        assert!(validate_assets(&assets).is_ok());
+        
+        let config = assets[0].to_config(254);
+        assert_eq!(config.mint, assets[0].mint);
+        assert_eq!(config.target_weight_bps, 4000);
+        assert_eq!(config.current_weight_bps, 0);
+        assert_eq!(config.bump, 254);
+    }
+    
+    #[test]
+    fn test_weight_sum_mismatch_reverts_bootstrap() {
+        for assets in [
+            vec![asset(4000), asset(4000), asset(1999)],
+            vec![asset(4000), asset(4000), asset(2001)],
+        ] {
+            assert_eq!(
+                validate_assets(&assets).unwrap_err(),
+                ReserveError::InvalidTargetWeights.into()
+            );
+        }
+    }
+    
+    #[test]
+    fn test_invalid_asset_sets_rejected() {
+        assert_eq!(validate_assets(&[]).unwrap_err(), ReserveError::InvalidAssetConfig.into());
+        
+        let too_many: Vec<AssetParams> = (0..=MAX_BOOTSTRAP_ASSETS).map(|_| asset(1000)).collect();
+        assert!(validate_assets(&too_many).is_err());
+        
+        // Duplicate mint
+        let first = asset(5000);
+        assert_eq!(
+            validate_assets(&[first, first]).unwrap_err(),
+            ReserveError::InvalidAssetConfig.into()
+        );
+        
+        // Target outside its own min/max band
+        let mut out_of_band = asset(10000);
+        out_of_band.max_weight_bps = 9000;
+        assert!(validate_assets(&[out_of_band]).is_err());
+        
+        let mut stale = asset(10000);
+        stale.price_staleness_secs = MAX_CONFIGURABLE_PRICE_AGE + 1;
+        assert_eq!(
+            validate_assets(&[stale]).unwrap_err(),
+            ReserveError::InvalidPriceStaleness.into()
+        );
+    }
+}
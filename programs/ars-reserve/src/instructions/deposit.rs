@@ -1,11 +1,41 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use crate::state::*;
-use crate::errors::ReserveError;
+use crate::errors::{key_mismatch, ReserveError};
 use crate::instructions::initialize_vault::VAULT_SEED;
-use crate::utils::ReentrancyGuard;
+use crate::instructions::idempotency::{claim_idempotency_key, IDEMPOTENCY_SEED};
+use crate::utils::{mint_matches, ReentrancyGuard};
 
 #[derive(Accounts)]
+pub struct SetDepositCaps<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        has_one = authority @ ReserveError::Unauthorized
+    )]
+    pub vault: Account<'info, ReserveVault>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn set_deposit_caps(
+    ctx: Context<SetDepositCaps>,
+    max_deposit_per_tx: u64,
+    max_total_value_usd: u64,
+) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    
+    vault.max_deposit_per_tx = max_deposit_per_tx;
+    vault.max_total_value_usd = max_total_value_usd;
+    
+    msg!("Deposit caps set: {} per tx, {} vault total", max_deposit_per_tx, max_total_value_usd);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, idempotency_key: Option<[u8; 32]>)]
 pub struct Deposit<'info> {
     #[account(
         mut,
@@ -16,29 +46,54 @@ pub struct Deposit<'info> {
     
     #[account(
         mut,
-        constraint = vault_token_account.owner == vault.key() @ ReserveError::InvalidAccountOwner
+        constraint = vault_token_account.owner == vault.key()
+            @ key_mismatch(ReserveError::VaultNotTokenOwner, vault_token_account.owner, vault.key())
     )]
     pub vault_token_account: Account<'info, TokenAccount>,
     
     #[account(
         mut,
-        constraint = depositor_token_account.mint == vault_token_account.mint @ ReserveError::InvalidAmount
+        constraint = mint_matches(&depositor_token_account, &vault_token_account) @ ReserveError::InvalidTokenMint
     )]
     pub depositor_token_account: Account<'info, TokenAccount>,
     
+    /// Present when the depositor supplies an idempotency key
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        space = IdempotencyRecord::LEN,
+        seeds = [IDEMPOTENCY_SEED, depositor.key().as_ref(), &idempotency_key.unwrap_or_default()],
+        bump
+    )]
+    pub idempotency_record: Option<Account<'info, IdempotencyRecord>>,
+    
+    #[account(mut)]
     pub depositor: Signer<'info>,
     
     pub token_program: Program<'info, Token>,
+    
+    pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+pub fn handler(ctx: Context<Deposit>, amount: u64, idempotency_key: Option<[u8; 32]>) -> Result<()> {
     require!(amount > 0, ReserveError::InvalidAmount);
     
+    // Reject a retried submission of the same deposit
+    claim_idempotency_key(
+        ctx.accounts.idempotency_record.as_deref_mut(),
+        idempotency_key,
+        ctx.accounts.depositor.key(),
+        Clock::get()?.unix_timestamp,
+    )?;
+    
     let vault = &mut ctx.accounts.vault;
     
     // Acquire reentrancy lock
     let _guard = ReentrancyGuard::acquire(&mut vault.locked)?;
     
+    // Limit how much a single deposit can skew the reserve
+    vault.check_deposit_caps(amount)?;
+    
     // Validate user has sufficient balance
     require!(
         ctx.accounts.depositor_token_account.amount >= amount,
@@ -0,0 +1,186 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::{key_mismatch, ReserveError};
+use crate::instructions::initialize_vault::VAULT_SEED;
+use crate::instructions::bootstrap_reserve::ASSET_CONFIG_SEED;
+use crate::utils::ReentrancyGuard;
+
+#[derive(Accounts)]
+pub struct SetTreasury<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        has_one = authority @ ReserveError::Unauthorized
+    )]
+    pub vault: Account<'info, ReserveVault>,
+    
+    pub treasury: Account<'info, TokenAccount>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Designate the owner of `treasury` as the fee destination. Each mint's
+/// fees are swept to that owner's token account for the mint.
+pub fn set_treasury(ctx: Context<SetTreasury>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    
+    vault.treasury = ctx.accounts.treasury.owner;
+    
+    msg!("Treasury set: {}", vault.treasury);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetInsuranceShare<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        has_one = authority @ ReserveError::Unauthorized
+    )]
+    pub vault: Account<'info, ReserveVault>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn set_insurance_share(ctx: Context<SetInsuranceShare>, insurance_share_bps: u16) -> Result<()> {
+    require!(insurance_share_bps <= 10000, ReserveError::InvalidInsuranceShare);
+    
+    ctx.accounts.vault.insurance_share_bps = insurance_share_bps;
+    
+    msg!("Insurance share of fees: {} bps", insurance_share_bps);
+    
+    Ok(())
+}
+
+/// Upper bound on the withdrawal fee (10%)
+pub const MAX_WITHDRAW_FEE_BPS: u16 = 1000;
+
+#[derive(Accounts)]
+pub struct SetWithdrawFee<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        has_one = authority @ ReserveError::Unauthorized
+    )]
+    pub vault: Account<'info, ReserveVault>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn set_withdraw_fee(ctx: Context<SetWithdrawFee>, withdraw_fee_bps: u16) -> Result<()> {
+    require!(withdraw_fee_bps <= MAX_WITHDRAW_FEE_BPS, ReserveError::InvalidWithdrawFee);
+    
+    ctx.accounts.vault.withdraw_fee_bps = withdraw_fee_bps;
+    
+    msg!("Withdrawal fee: {} bps", withdraw_fee_bps);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetRebalanceReward<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        has_one = authority @ ReserveError::Unauthorized
+    )]
+    pub vault: Account<'info, ReserveVault>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn set_rebalance_reward(ctx: Context<SetRebalanceReward>, rebalance_reward: u64) -> Result<()> {
+    ctx.accounts.vault.rebalance_reward = rebalance_reward;
+    
+    msg!("Rebalance keeper reward: {}", rebalance_reward);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SweepFees<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        has_one = authority @ ReserveError::Unauthorized
+    )]
+    pub vault: Account<'info, ReserveVault>,
+    
+    #[account(
+        mut,
+        constraint = vault_token_account.owner == vault.key()
+            @ key_mismatch(ReserveError::VaultNotTokenOwner, vault_token_account.owner, vault.key())
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    
+    /// Fees are taken from the accrual of the mint being swept
+    #[account(
+        mut,
+        seeds = [ASSET_CONFIG_SEED, vault_token_account.mint.as_ref()],
+        bump = asset_config.bump
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+    
+    #[account(
+        mut,
+        constraint = treasury_token_account.owner == vault.treasury
+            @ key_mismatch(ReserveError::InvalidTreasury, treasury_token_account.owner, vault.treasury),
+        constraint = treasury_token_account.mint == vault_token_account.mint @ ReserveError::InvalidTreasury
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+    
+    pub authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn sweep_fees(ctx: Context<SweepFees>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    
+    // Acquire reentrancy lock
+    let _guard = ReentrancyGuard::acquire(&mut vault.locked)?;
+    
+    let amount = ctx.accounts.asset_config.take_accrued_fees()?;
+    require!(
+        ctx.accounts.vault_token_account.amount >= amount,
+        ReserveError::InsufficientVaultBalance
+    );
+    
+    // Transfer fees from vault to treasury using PDA signer
+    let bump = vault.bump;
+    let seeds = &[VAULT_SEED, &[bump]];
+    let signer = &[&seeds[..]];
+    
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.vault_token_account.to_account_info(),
+        to: ctx.accounts.treasury_token_account.to_account_info(),
+        authority: vault.to_account_info(),
+    };
+    
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    
+    token::transfer(cpi_ctx, amount)?;
+    
+    let clock = Clock::get()?;
+    emit!(FeesSwept {
+        mint: ctx.accounts.asset_config.mint,
+        amount,
+        treasury: vault.treasury,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Swept {} {} in fees to treasury {}", amount, ctx.accounts.asset_config.mint, vault.treasury);
+    
+    // Release lock
+    ReentrancyGuard::release(&mut vault.locked);
+    
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ReserveError;
+
+pub const IDEMPOTENCY_SEED: &[u8] = b"idempotency";
+
+/// How long a used idempotency key blocks repeats (1 hour)
+pub const IDEMPOTENCY_WINDOW: i64 = 3600;
+
+#[derive(Accounts)]
+#[instruction(idempotency_key: [u8; 32])]
+pub struct CloseIdempotencyRecord<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [IDEMPOTENCY_SEED, owner.key().as_ref(), &idempotency_key],
+        bump
+    )]
+    pub idempotency_record: Account<'info, IdempotencyRecord>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+pub fn close_idempotency_record(ctx: Context<CloseIdempotencyRecord>, _idempotency_key: [u8; 32]) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        ctx.accounts.idempotency_record.is_expired(now, IDEMPOTENCY_WINDOW),
+        ReserveError::IdempotencyWindowActive
+    );
+    
+    msg!("Idempotency record closed for {}", ctx.accounts.owner.key());
+    
+    Ok(())
+}
+
+/// Claim `key` in `record` if the caller supplied one. Calls without a key
+/// are not deduplicated.
+pub fn claim_idempotency_key(
+    record: Option<&mut IdempotencyRecord>,
+    key: Option<[u8; 32]>,
+    owner: Pubkey,
+    now: i64,
+) -> Result<()> {
+    let Some(key) = key else {
+        return Ok(());
+    };
+    let record = record.ok_or(ReserveError::MissingIdempotencyRecord)?;
+    record.claim(owner, key, now, IDEMPOTENCY_WINDOW)
+}
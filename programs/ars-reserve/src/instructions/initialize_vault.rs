@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::errors::ReserveError;
+use crate::instructions::bootstrap_reserve::MAX_BOOTSTRAP_ASSETS;
 
 pub const VAULT_SEED: &[u8] = b"reserve_vault";
 
@@ -24,15 +25,29 @@ pub struct InitializeVault<'info> {
 pub fn handler(
     ctx: Context<InitializeVault>,
     rebalance_threshold_bps: u16,
+) -> Result<()> {
+    init_vault(
+        &mut ctx.accounts.vault,
+        ctx.accounts.authority.key(),
+        rebalance_threshold_bps,
+        ctx.bumps.vault,
+    )
+}
+
+/// Write a freshly created vault's initial state. Shared by initialize_vault
+/// and bootstrap_reserve.
+pub fn init_vault(
+    vault: &mut ReserveVault,
+    authority: Pubkey,
+    rebalance_threshold_bps: u16,
+    bump: u8,
 ) -> Result<()> {
     require!(
         rebalance_threshold_bps > 0 && rebalance_threshold_bps <= 10000,
         ReserveError::InvalidRebalanceThreshold
     );
     
-    let vault = &mut ctx.accounts.vault;
-    
-    vault.authority = ctx.accounts.authority.key();
+    vault.authority = authority;
     vault.usdc_vault = Pubkey::default(); // Set when token accounts created
     vault.sol_vault = Pubkey::default();
     vault.msol_vault = Pubkey::default();
@@ -41,7 +56,17 @@ pub fn handler(
     vault.vhr = 0;
     vault.last_rebalance = 0;
     vault.rebalance_threshold_bps = rebalance_threshold_bps;
-    vault.bump = ctx.bumps.vault;
+    vault.version = ReserveVault::CURRENT_VERSION;
+    vault.treasury = Pubkey::default(); // Set later via set_treasury
+    vault.max_deposit_per_tx = 0; // Uncapped until set_deposit_caps
+    vault.max_total_value_usd = 0;
+    vault.insurance_share_bps = 0; // All fees swept until set_insurance_share
+    vault.asset_count = 0;
+    vault.max_assets = MAX_BOOTSTRAP_ASSETS as u8;
+    vault.withdraw_fee_bps = 0; // Free withdrawals until set_withdraw_fee
+    vault.rebalance_reward = 0; // No keeper reward until set_rebalance_reward
+    vault.withdraw_recipients = Vec::new(); // Any recipient until governance adds one
+    vault.bump = bump;
     
     msg!("Reserve vault initialized");
     msg!("Authority: {}", vault.authority);
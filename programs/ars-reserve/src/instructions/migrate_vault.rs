@@ -0,0 +1,207 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+use crate::state::*;
+use crate::errors::ReserveError;
+use crate::instructions::bootstrap_reserve::MAX_BOOTSTRAP_ASSETS;
+use crate::instructions::initialize_vault::VAULT_SEED;
+
+/// Size of the original (unversioned) layout, which ended with
+/// rebalance_threshold_bps, locked and bump
+pub const LEGACY_VAULT_LEN: usize = 166;
+
+/// Offset of `ReserveVault::version`, directly after rebalance_threshold_bps
+const VERSION_OFFSET: usize = 164;
+
+#[derive(Accounts)]
+pub struct MigrateVault<'info> {
+    /// CHECK: Loaded manually - an old-layout vault is too small to
+    /// deserialize as the current ReserveVault until it has been reallocated
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump,
+        owner = crate::ID
+    )]
+    pub vault: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_vault(ctx: Context<MigrateVault>) -> Result<()> {
+    let vault_info = ctx.accounts.vault.to_account_info();
+    let new_len = ReserveVault::LEN;
+    
+    // The legacy layout is only recognisable by its size, so read the
+    // version before the realloc below
+    let from_version = stored_version(&vault_info.try_borrow_data()?);
+    
+    // Grow the account to the current layout, charging the extra rent to the authority
+    if vault_info.data_len() < new_len {
+        let rent = Rent::get()?;
+        let lamports_needed = rent
+            .minimum_balance(new_len)
+            .saturating_sub(vault_info.lamports());
+        
+        if lamports_needed > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: vault_info.clone(),
+                    },
+                ),
+                lamports_needed,
+            )?;
+        }
+        
+        vault_info.realloc(new_len, true)?;
+    }
+    
+    let mut data = vault_info.try_borrow_mut_data()?;
+    migrate_vault_data(&mut data[..], from_version, &ctx.accounts.authority.key())?;
+    
+    msg!("Reserve vault migrated from version {} to {}", from_version, ReserveVault::CURRENT_VERSION);
+    msg!("Account size: {} bytes", new_len);
+    
+    Ok(())
+}
+
+/// Layout version of stored vault data. The unversioned layout has
+/// locked where the version byte now sits, so it is told apart by size.
+pub fn stored_version(data: &[u8]) -> u8 {
+    if data.len() <= LEGACY_VAULT_LEN {
+        return 0;
+    }
+    data.get(VERSION_OFFSET).copied().unwrap_or(0)
+}
+
+/// Upgrade vault data stored in layout `from_version` (already sized to
+/// ReserveVault::LEN) to the current layout version
+pub fn migrate_vault_data(data: &mut [u8], from_version: u8, authority: &Pubkey) -> Result<()> {
+    // Each version can only be migrated once
+    require!(
+        from_version < ReserveVault::CURRENT_VERSION,
+        ReserveError::AlreadyMigrated
+    );
+    
+    let vault = deserialize_legacy(data)?;
+    require_keys_eq!(vault.authority, *authority, ReserveError::Unauthorized);
+    
+    data.fill(0);
+    let mut writer: &mut [u8] = data;
+    vault.try_serialize(&mut writer)?;
+    
+    Ok(())
+}
+
+/// Read vault data stored in the original layout. Fields added since then
+/// take the values initialize_vault gives a new vault.
+pub fn deserialize_legacy(data: &[u8]) -> Result<ReserveVault> {
+    require!(
+        data.len() >= LEGACY_VAULT_LEN && data[..8] == ReserveVault::DISCRIMINATOR,
+        ReserveError::InvalidAccountOwner
+    );
+    
+    let buf = &mut &data[8..];
+    Ok(ReserveVault {
+        authority: read(buf)?,
+        usdc_vault: read(buf)?,
+        sol_vault: read(buf)?,
+        msol_vault: read(buf)?,
+        total_value_usd: read(buf)?,
+        liabilities_usd: read(buf)?,
+        vhr: read(buf)?,
+        last_rebalance: read(buf)?,
+        rebalance_threshold_bps: read(buf)?,
+        locked: read(buf)?,
+        bump: read(buf)?,
+        version: ReserveVault::CURRENT_VERSION,
+        max_assets: MAX_BOOTSTRAP_ASSETS as u8,
+        ..Default::default()
+    })
+}
+
+fn read<T: AnchorDeserialize>(buf: &mut &[u8]) -> Result<T> {
+    Ok(T::deserialize(buf)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn legacy_account(authority: Pubkey, locked: bool) -> Vec<u8> {
+        let mut data = ReserveVault::DISCRIMINATOR.to_vec();
+        (
+            authority,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            5_000_000_000u64,
+            4_000_000_000u64,
+            12_500u16,
+            1_700_000_000i64,
+            1500u16,
+            locked,
+            254u8,
+        )
+            .serialize(&mut data)
+            .unwrap();
+        assert_eq!(data.len(), LEGACY_VAULT_LEN);
+        data
+    }
+    
+    #[test]
+    fn test_migrate_legacy_vault() {
+        let authority = Pubkey::new_unique();
+        let mut data = legacy_account(authority, true);
+        
+        // The legacy locked byte must not be read as a version
+        let from_version = stored_version(&data);
+        assert_eq!(from_version, 0);
+        
+        data.resize(ReserveVault::LEN, 0);
+        migrate_vault_data(&mut data, from_version, &authority).unwrap();
+        
+        let migrated = ReserveVault::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(migrated.version, ReserveVault::CURRENT_VERSION);
+        assert_eq!(migrated.treasury, Pubkey::default());
+        assert_eq!(migrated.max_deposit_per_tx, 0);
+        assert_eq!(migrated.asset_count, 0);
+        assert_eq!(migrated.max_assets as usize, MAX_BOOTSTRAP_ASSETS);
+        assert_eq!(migrated.withdraw_fee_bps, 0);
+        assert!(migrated.withdraw_recipients.is_empty());
+        
+        // Existing fields survive the migration
+        assert_eq!(migrated.authority, authority);
+        assert_eq!(migrated.total_value_usd, 5_000_000_000);
+        assert_eq!(migrated.liabilities_usd, 4_000_000_000);
+        assert_eq!(migrated.vhr, 12_500);
+        assert_eq!(migrated.rebalance_threshold_bps, 1500);
+        assert!(migrated.locked);
+        assert_eq!(migrated.bump, 254);
+        
+        // A migrated vault reports its version and cannot be migrated again
+        let version = stored_version(&data);
+        assert_eq!(version, ReserveVault::CURRENT_VERSION);
+        assert_eq!(
+            migrate_vault_data(&mut data, version, &authority).unwrap_err(),
+            ReserveError::AlreadyMigrated.into()
+        );
+    }
+    
+    #[test]
+    fn test_migrate_requires_vault_authority() {
+        let mut data = legacy_account(Pubkey::new_unique(), false);
+        data.resize(ReserveVault::LEN, 0);
+        
+        assert_eq!(
+            migrate_vault_data(&mut data, 0, &Pubkey::new_unique()).unwrap_err(),
+            ReserveError::Unauthorized.into()
+        );
+    }
+}
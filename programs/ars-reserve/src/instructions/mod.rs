@@ -1,11 +1,33 @@
 pub mod initialize_vault;
+pub mod bootstrap_reserve;
 pub mod deposit;
 pub mod withdraw;
 pub mod update_vhr;
+pub mod update_vhr_from_prices;
+pub mod query_max_mintable;
+pub mod query_vhr;
 pub mod rebalance;
+pub mod simulate_rebalance;
+pub mod query_reserve_composition;
+pub mod fees;
+pub mod idempotency;
+pub mod set_vault_authority;
+pub mod withdraw_recipients;
+pub mod migrate_vault;
 
 pub use initialize_vault::*;
+pub use bootstrap_reserve::*;
 pub use deposit::*;
 pub use withdraw::*;
 pub use update_vhr::*;
+pub use update_vhr_from_prices::*;
+pub use query_max_mintable::*;
+pub use query_vhr::*;
 pub use rebalance::*;
+pub use simulate_rebalance::*;
+pub use query_reserve_composition::*;
+pub use fees::*;
+pub use idempotency::*;
+pub use set_vault_authority::*;
+pub use withdraw_recipients::*;
+pub use migrate_vault::*;
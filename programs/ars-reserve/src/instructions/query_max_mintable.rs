@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ReserveError;
+use crate::instructions::initialize_vault::VAULT_SEED;
+
+#[derive(Accounts)]
+pub struct QueryMaxMintable<'info> {
+    #[account(
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, ReserveVault>,
+}
+
+pub fn handler(ctx: Context<QueryMaxMintable>, target_vhr_bps: u16) -> Result<u64> {
+    let vault = &ctx.accounts.vault;
+    
+    let mintable = max_mintable(vault.total_value_usd, vault.liabilities_usd, target_vhr_bps)?;
+    
+    msg!("Max mintable at {} bps VHR: {}", target_vhr_bps, mintable);
+    
+    Ok(mintable)
+}
+
+/// Largest additional liability that keeps `compute_vhr` at or above
+/// `target_vhr_bps`, i.e. floor(total * 10000 / target) - liabilities.
+/// Returns 0 when the vault is already at or below the target.
+pub fn max_mintable(total_value_usd: u64, liabilities_usd: u64, target_vhr_bps: u16) -> Result<u64> {
+    require!(target_vhr_bps > 0, ReserveError::InvalidVHRTarget);
+    
+    let max_liabilities = (total_value_usd as u128)
+        .checked_mul(10000)
+        .ok_or(ReserveError::ArithmeticOverflow)?
+        / target_vhr_bps as u128;
+    let mintable = max_liabilities.saturating_sub(liabilities_usd as u128);
+    
+    Ok(mintable.min(u64::MAX as u128) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::update_vhr::compute_vhr;
+    
+    #[test]
+    fn test_max_mintable_keeps_vhr_at_target() {
+        // $1.5M reserve, $500k liabilities, 150% target: up to $1M total liabilities
+        assert_eq!(max_mintable(1_500_000, 500_000, 15000).unwrap(), 500_000);
+        
+        // Across reserve states, minting the result lands exactly on or above
+        // the target and one more unit drops below it
+        for (total, liabilities, target) in [
+            (1_500_000u64, 500_000u64, 15000u16),
+            (1_000_001, 0, 15000),
+            (7_777_777, 1_234_567, 11000),
+            (10_000_000_000, 3_000_000_000, 20000),
+        ] {
+            let mintable = max_mintable(total, liabilities, target).unwrap();
+            assert!(compute_vhr(total, liabilities + mintable).unwrap() >= target);
+            assert!(compute_vhr(total, liabilities + mintable + 1).unwrap() < target);
+        }
+    }
+    
+    #[test]
+    fn test_max_mintable_at_or_below_target() {
+        // Exactly at 150%: nothing more can be minted
+        assert_eq!(max_mintable(1_500_000, 1_000_000, 15000).unwrap(), 0);
+        
+        // Under-collateralized or empty vault
+        assert_eq!(max_mintable(1_000_000, 1_000_000, 15000).unwrap(), 0);
+        assert_eq!(max_mintable(0, 0, 15000).unwrap(), 0);
+    }
+    
+    #[test]
+    fn test_max_mintable_rejects_zero_target() {
+        assert_eq!(
+            max_mintable(1_000_000, 0, 0).unwrap_err(),
+            ReserveError::InvalidVHRTarget.into()
+        );
+    }
+    
+    #[test]
+    fn test_max_mintable_saturates() {
+        assert_eq!(max_mintable(u64::MAX, 0, 1).unwrap(), u64::MAX);
+    }
+}
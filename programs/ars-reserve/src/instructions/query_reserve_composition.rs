@@ -0,0 +1,245 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use crate::state::*;
+use crate::errors::ReserveError;
+use crate::instructions::initialize_vault::VAULT_SEED;
+use crate::instructions::update_vhr_from_prices::{asset_value_usd, validate_price_age};
+
+/// Accounts passed per asset in `remaining_accounts`: config, vault token account, mint
+pub const ACCOUNTS_PER_ASSET: usize = 3;
+
+/// One registered reserve asset as seen by treasury dashboards
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct AssetComposition {
+    pub mint: Pubkey,
+    pub balance: u64,               // Token base units held by the vault
+    pub value_usd: Option<u64>,     // Scaled by 1e6, None without a usable price
+    pub current_weight_bps: Option<u16>, // Share of the priced portfolio
+    pub target_weight_bps: u16,
+}
+
+/// Holding of one asset and its price, if one is known
+pub struct AssetHolding {
+    pub mint: Pubkey,
+    pub balance: u64,
+    pub decimals: u8,
+    pub price: Option<AssetPrice>,
+    pub max_price_age: i64,
+    pub target_weight_bps: u16,
+}
+
+#[derive(Accounts)]
+pub struct QueryReserveComposition<'info> {
+    #[account(
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, ReserveVault>,
+}
+
+/// `remaining_accounts` holds one (AssetConfig, vault token account, mint)
+/// triple per asset, with `prices` in the same order
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, QueryReserveComposition<'info>>,
+    prices: Vec<Option<AssetPrice>>,
+) -> Result<Vec<AssetComposition>> {
+    let clock = Clock::get()?;
+    let holdings = load_holdings(ctx.accounts.vault.key(), ctx.remaining_accounts, prices)?;
+    let composition = reserve_composition(&holdings, clock.unix_timestamp)?;
+    
+    for asset in &composition {
+        match (asset.value_usd, asset.current_weight_bps) {
+            (Some(value), Some(weight)) => msg!(
+                "{}: {} units, ${}, {} / {} bps",
+                asset.mint, asset.balance, value, weight, asset.target_weight_bps
+            ),
+            _ => msg!("{}: {} units, no price", asset.mint, asset.balance),
+        }
+    }
+    
+    Ok(composition)
+}
+
+/// Read one (AssetConfig, vault token account, mint) triple per price from
+/// `remaining`, checking each token account belongs to the vault, and
+/// value them at the caller's `prices`
+pub fn load_holdings<'info>(
+    vault_key: Pubkey,
+    remaining: &'info [AccountInfo<'info>],
+    prices: Vec<Option<AssetPrice>>,
+) -> Result<Vec<AssetHolding>> {
+    require!(
+        remaining.len() / ACCOUNTS_PER_ASSET == prices.len(),
+        ReserveError::InvalidAccountOwner
+    );
+    
+    let mut holdings = load_recorded_holdings(vault_key, remaining)?;
+    for (holding, price) in holdings.iter_mut().zip(prices) {
+        holding.price = price;
+    }
+    
+    Ok(holdings)
+}
+
+/// Read one (AssetConfig, vault token account, mint) triple per asset from
+/// `remaining`, checking each token account belongs to the vault, and
+/// value them at the prices last recorded on their configs by
+/// update_vhr_from_prices
+pub fn load_recorded_holdings<'info>(
+    vault_key: Pubkey,
+    remaining: &'info [AccountInfo<'info>],
+) -> Result<Vec<AssetHolding>> {
+    require!(
+        remaining.chunks_exact(ACCOUNTS_PER_ASSET).remainder().is_empty(),
+        ReserveError::InvalidAccountOwner
+    );
+    
+    let mut holdings = Vec::with_capacity(remaining.len() / ACCOUNTS_PER_ASSET);
+    for accounts in remaining.chunks(ACCOUNTS_PER_ASSET) {
+        let config = Account::<AssetConfig>::try_from(&accounts[0])?;
+        let token_account = Account::<TokenAccount>::try_from(&accounts[1])?;
+        let mint = Account::<Mint>::try_from(&accounts[2])?;
+        
+        require!(
+            token_account.owner == vault_key
+                && token_account.mint == config.mint
+                && mint.key() == config.mint,
+            ReserveError::InvalidAccountOwner
+        );
+        
+        holdings.push(AssetHolding {
+            mint: config.mint,
+            balance: token_account.amount,
+            decimals: mint.decimals,
+            price: config.recorded_price(),
+            max_price_age: config.max_price_age(),
+            target_weight_bps: config.target_weight_bps,
+        });
+    }
+    
+    Ok(holdings)
+}
+
+/// Value each holding and weight it against the total of the priced ones.
+/// Holdings with a missing, zero or stale (per the asset's own window) price
+/// are still listed, without a value or weight, rather than failing the
+/// whole query.
+pub fn reserve_composition(holdings: &[AssetHolding], now: i64) -> Result<Vec<AssetComposition>> {
+    let mut values = Vec::with_capacity(holdings.len());
+    for holding in holdings {
+        let value = match holding.price {
+            Some(price) if validate_price_age(&price, now, holding.max_price_age).is_ok() => {
+                Some(asset_value_usd(holding.balance, holding.decimals, price.price_usd)?)
+            }
+            _ => None,
+        };
+        values.push(value);
+    }
+    
+    let total_value_usd = values
+        .iter()
+        .flatten()
+        .try_fold(0u64, |total, value| total.checked_add(*value))
+        .ok_or(ReserveError::ArithmeticOverflow)?;
+    
+    Ok(holdings
+        .iter()
+        .zip(values)
+        .map(|(holding, value_usd)| AssetComposition {
+            mint: holding.mint,
+            balance: holding.balance,
+            value_usd,
+            current_weight_bps: value_usd
+                .filter(|_| total_value_usd > 0)
+                .map(|value| ((value as u128 * 10000) / total_value_usd as u128) as u16),
+            target_weight_bps: holding.target_weight_bps,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const NOW: i64 = 1_700_000_000;
+    
+    fn holding(balance: u64, decimals: u8, price_usd: Option<u64>, target_weight_bps: u16) -> AssetHolding {
+        AssetHolding {
+            mint: Pubkey::new_unique(),
+            balance,
+            decimals,
+            price: price_usd.map(|price_usd| AssetPrice { price_usd, published_at: NOW - 60 }),
+            max_price_age: MAX_PRICE_AGE,
+            target_weight_bps,
+        }
+    }
+    
+    #[test]
+    fn test_multi_asset_weights_sum_to_full() {
+        // $1M total: 500,000 USDC, 3,000 SOL @ $100, 1,000 mSOL @ $200
+        let holdings = [
+            holding(500_000_000_000, 6, Some(1_000_000), 4000),
+            holding(3_000_000_000_000, 9, Some(100_000_000), 4000),
+            holding(1_000_000_000_000, 9, Some(200_000_000), 2000),
+        ];
+        
+        let composition = reserve_composition(&holdings, NOW).unwrap();
+        
+        let values: Vec<Option<u64>> = composition.iter().map(|a| a.value_usd).collect();
+        assert_eq!(values, vec![Some(500_000_000_000), Some(300_000_000_000), Some(200_000_000_000)]);
+        
+        let weights: Vec<u16> = composition.iter().map(|a| a.current_weight_bps.unwrap()).collect();
+        assert_eq!(weights, vec![5000, 3000, 2000]);
+        assert_eq!(weights.iter().map(|w| *w as u32).sum::<u32>(), 10000);
+        
+        let targets: Vec<u16> = composition.iter().map(|a| a.target_weight_bps).collect();
+        assert_eq!(targets, vec![4000, 4000, 2000]);
+    }
+    
+    #[test]
+    fn test_uneven_weights_round_down() {
+        let holdings = [
+            holding(1_000_000, 6, Some(1_000_000), 3334),
+            holding(1_000_000, 6, Some(1_000_000), 3333),
+            holding(1_000_000, 6, Some(1_000_000), 3333),
+        ];
+        
+        let composition = reserve_composition(&holdings, NOW).unwrap();
+        let total: u32 = composition.iter().map(|a| a.current_weight_bps.unwrap() as u32).sum();
+        
+        // Each weight rounds down, losing at most 1 bps per asset
+        assert!(total <= 10000 && total >= 10000 - composition.len() as u32);
+    }
+    
+    #[test]
+    fn test_missing_price_is_listed_without_weight() {
+        let mut stale = holding(2_000_000_000, 9, Some(100_000_000), 2000);
+        stale.price = Some(AssetPrice { price_usd: 100_000_000, published_at: NOW - 3_600 });
+        let holdings = [
+            holding(600_000_000, 6, Some(1_000_000), 4000),
+            holding(4_000_000_000, 9, Some(100_000_000), 4000),
+            holding(5_000_000_000, 9, None, 1000),
+            stale,
+        ];
+        
+        let composition = reserve_composition(&holdings, NOW).unwrap();
+        
+        assert_eq!(composition.len(), 4);
+        assert_eq!(composition[0].current_weight_bps, Some(6000));
+        assert_eq!(composition[1].current_weight_bps, Some(4000));
+        
+        // Unpriced assets keep their balance but have no value or weight
+        for asset in &composition[2..] {
+            assert_eq!(asset.value_usd, None);
+            assert_eq!(asset.current_weight_bps, None);
+        }
+        assert_eq!(composition[3].balance, 2_000_000_000);
+    }
+    
+    #[test]
+    fn test_no_prices_yields_no_weights() {
+        let holdings = [holding(1_000_000, 6, None, 10000)];
+        let composition = reserve_composition(&holdings, NOW).unwrap();
+        assert_eq!(composition[0].current_weight_bps, None);
+    }
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::instructions::initialize_vault::VAULT_SEED;
+use crate::instructions::update_vhr::try_compute_vhr;
+
+#[derive(Accounts)]
+pub struct QueryVHR<'info> {
+    #[account(
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, ReserveVault>,
+}
+
+/// Recompute VHR from the stored totals rather than trusting `vault.vhr`,
+/// so dashboards see "undefined" instead of a sentinel value
+pub fn handler(ctx: Context<QueryVHR>) -> Result<Option<u16>> {
+    let vault = &ctx.accounts.vault;
+    
+    let vhr = try_compute_vhr(vault.total_value_usd, vault.liabilities_usd)?;
+    
+    match vhr {
+        Some(vhr) => msg!("VHR: {} bps", vhr),
+        None => msg!("VHR: undefined (no liabilities)"),
+    }
+    
+    Ok(vhr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::update_vhr::compute_vhr;
+    
+    #[test]
+    fn test_zero_liabilities_is_undefined() {
+        assert_eq!(try_compute_vhr(1_000_000, 0).unwrap(), None);
+        assert_eq!(try_compute_vhr(0, 0).unwrap(), None);
+        
+        // The write path keeps its saturated sentinel
+        assert_eq!(compute_vhr(1_000_000, 0).unwrap(), u16::MAX);
+    }
+    
+    #[test]
+    fn test_overflow_prone_inputs_saturate() {
+        // u64::MAX * 10000 only fits because the product is taken in u128
+        assert_eq!(try_compute_vhr(u64::MAX, 1).unwrap(), Some(u16::MAX));
+        assert_eq!(try_compute_vhr(u64::MAX, u64::MAX).unwrap(), Some(10000));
+        assert_eq!(try_compute_vhr(0, u64::MAX).unwrap(), Some(0));
+        
+        // Just above u16::MAX bps saturates instead of wrapping
+        assert_eq!(try_compute_vhr(65_536, 10_000).unwrap(), Some(u16::MAX));
+    }
+    
+    #[test]
+    fn test_regular_ratio() {
+        assert_eq!(try_compute_vhr(1_500_000, 1_000_000).unwrap(), Some(15000));
+        assert_eq!(try_compute_vhr(999_999, 1_000_000).unwrap(), Some(9999));
+    }
+}
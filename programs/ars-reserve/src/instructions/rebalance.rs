@@ -1,27 +1,70 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use crate::state::*;
-use crate::errors::ReserveError;
+use crate::errors::{key_mismatch, ReserveError};
 use crate::instructions::initialize_vault::VAULT_SEED;
-use crate::utils::ReentrancyGuard;
+use crate::instructions::bootstrap_reserve::ASSET_CONFIG_SEED;
+use crate::instructions::query_reserve_composition::{
+    load_recorded_holdings, reserve_composition, AssetComposition, AssetHolding,
+};
+use crate::utils::{check_swap_deadline, ReentrancyGuard};
+
+/// Minimum time between rebalances (1 hour)
+pub const MIN_REBALANCE_INTERVAL: i64 = 3600;
 
 #[derive(Accounts)]
 pub struct Rebalance<'info> {
     #[account(
         mut,
         seeds = [VAULT_SEED],
-        bump = vault.bump,
-        constraint = vault.authority == authority.key() @ ReserveError::Unauthorized
+        bump = vault.bump
     )]
     pub vault: Account<'info, ReserveVault>,
     
-    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        constraint = vault_token_account.owner == vault.key()
+            @ key_mismatch(ReserveError::VaultNotTokenOwner, vault_token_account.owner, vault.key())
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+    
+    /// The keeper reward is paid from this mint's accrued fees
+    #[account(
+        mut,
+        seeds = [ASSET_CONFIG_SEED, vault_token_account.mint.as_ref()],
+        bump = asset_config.bump
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+    
+    #[account(
+        mut,
+        constraint = keeper_token_account.mint == vault_token_account.mint @ ReserveError::InvalidTokenMint
+    )]
+    pub keeper_token_account: Account<'info, TokenAccount>,
+    
+    /// Anyone may rebalance; the reward only follows a measured drift cut
+    pub keeper: Signer<'info>,
     
     /// CHECK: Jupiter program for swap execution
     /// This will be validated during CPI call
     pub jupiter_program: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
 }
 
-pub fn handler(ctx: Context<Rebalance>) -> Result<()> {
+/// `remaining_accounts` holds one (AssetConfig, vault token account, mint)
+/// triple for every registered asset. They are valued at the prices
+/// update_vhr_from_prices last recorded, never at caller-supplied ones, and
+/// drift is measured before and after the swaps to decide whether the
+/// keeper reward is paid.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Rebalance<'info>>,
+    min_amount_out: u64,
+    deadline_ts: i64,
+) -> Result<()> {
+    let vault_key = ctx.accounts.vault.key();
+    let holdings = load_recorded_holdings(vault_key, ctx.remaining_accounts)?;
+    check_all_assets(&holdings, ctx.accounts.vault.asset_count)?;
     let vault = &mut ctx.accounts.vault;
     
     // Acquire reentrancy lock
@@ -29,35 +72,168 @@ pub fn handler(ctx: Context<Rebalance>) -> Result<()> {
     
     let clock = Clock::get()?;
     
-    // Validate authority owns the vault
-    require!(
-        vault.authority == ctx.accounts.authority.key(),
-        ReserveError::Unauthorized
-    );
+    // A delayed or front-run rebalance reverts instead of swapping at a bad price
+    check_swap_deadline(deadline_ts, clock.unix_timestamp)?;
+    require!(min_amount_out > 0, ReserveError::InvalidAmount);
     
     // Check minimum time between rebalances (prevent spam)
-    let min_rebalance_interval = 3600; // 1 hour
     require!(
-        clock.unix_timestamp >= vault.last_rebalance + min_rebalance_interval,
+        clock.unix_timestamp >= vault.last_rebalance + MIN_REBALANCE_INTERVAL,
         ReserveError::RebalanceTooFrequent
     );
     
+    let drift_before_bps = max_weight_drift_bps(&reserve_composition(&holdings, clock.unix_timestamp)?);
+    
     vault.last_rebalance = clock.unix_timestamp;
     
     msg!("Vault rebalanced at: {}", clock.unix_timestamp);
     msg!("Current VHR: {} bps", vault.vhr);
+    msg!("Largest weight drift: {} bps", drift_before_bps);
     
     // TODO: Implement actual rebalancing logic with CPI to Jupiter
     // This would involve:
     // 1. Calculate current asset weights
     // 2. Compare with target weights (40% SOL, 30% USDC, 20% mSOL, 10% JitoSOL)
-    // 3. Calculate required swaps with slippage protection
-    // 4. Execute swaps via Jupiter CPI with invoke_signed
+    // 3. Calculate required swaps with slippage protection, building
+    //    JupiterSwapParams with min_amount_out and deadline_ts. Since any
+    //    keeper may call this, min_amount_out must also be floored at the
+    //    quote implied by the recorded prices.
+    // 4. Execute swaps via Jupiter CPI with invoke_signed, passing
+    //    min_amount_out to the route and calling check_output on the result
     // 5. Update vault composition
     // 6. Verify VHR remains above threshold
     
+    // Re-read balances after the swaps; the keeper is paid only when the
+    // drift was over the threshold and the swaps measurably reduced it, so
+    // a call that moves nothing earns nothing
+    let holdings = load_recorded_holdings(vault_key, ctx.remaining_accounts)?;
+    let drift_after_bps = max_weight_drift_bps(&reserve_composition(&holdings, clock.unix_timestamp)?);
+    msg!("Largest weight drift after swaps: {} bps", drift_after_bps);
+    
+    let reward = vault.take_rebalance_reward(&mut ctx.accounts.asset_config, drift_before_bps, drift_after_bps);
+    if reward > 0 {
+        ctx.accounts.vault_token_account.reload()?;
+        require!(
+            ctx.accounts.vault_token_account.amount >= reward,
+            ReserveError::InsufficientVaultBalance
+        );
+        
+        let bump = vault.bump;
+        let seeds = &[VAULT_SEED, &[bump]];
+        let signer = &[&seeds[..]];
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.vault_token_account.to_account_info(),
+            to: ctx.accounts.keeper_token_account.to_account_info(),
+            authority: vault.to_account_info(),
+        };
+        
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        
+        token::transfer(cpi_ctx, reward)?;
+        
+        emit!(RebalanceRewardPaid {
+            keeper: ctx.accounts.keeper.key(),
+            amount: reward,
+            drift_before_bps,
+            drift_after_bps,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        msg!("Keeper reward: {}", reward);
+    }
+    
     // Release lock
     ReentrancyGuard::release(&mut vault.locked);
     
     Ok(())
 }
+
+/// Require one holding per registered asset, each mint once, so drift is
+/// measured over the whole reserve rather than a subset picked by the caller
+pub fn check_all_assets(holdings: &[AssetHolding], asset_count: u8) -> Result<()> {
+    let mut mints: Vec<Pubkey> = holdings.iter().map(|holding| holding.mint).collect();
+    mints.sort();
+    mints.dedup();
+    require!(
+        mints.len() == holdings.len() && holdings.len() == asset_count as usize,
+        ReserveError::InvalidAssetConfig
+    );
+    Ok(())
+}
+
+/// Largest gap between an asset's current and target weight. Assets
+/// without a usable price have no weight and are not counted.
+pub fn max_weight_drift_bps(composition: &[AssetComposition]) -> u16 {
+    composition
+        .iter()
+        .filter_map(|asset| {
+            asset
+                .current_weight_bps
+                .map(|weight| weight.abs_diff(asset.target_weight_bps))
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn asset(current_weight_bps: Option<u16>, target_weight_bps: u16) -> AssetComposition {
+        AssetComposition {
+            mint: Pubkey::new_unique(),
+            balance: 0,
+            value_usd: current_weight_bps.map(|_| 0),
+            current_weight_bps,
+            target_weight_bps,
+        }
+    }
+    
+    #[test]
+    fn test_max_weight_drift() {
+        // On target: nothing to rebalance
+        assert_eq!(max_weight_drift_bps(&[asset(Some(4000), 4000), asset(Some(6000), 6000)]), 0);
+        
+        // Over- and under-weight both count
+        assert_eq!(
+            max_weight_drift_bps(&[asset(Some(7000), 4000), asset(Some(2000), 4000), asset(Some(1000), 2000)]),
+            3000
+        );
+        
+        // Unpriced assets are ignored
+        assert_eq!(max_weight_drift_bps(&[asset(None, 5000), asset(Some(5100), 5000)]), 100);
+        assert_eq!(max_weight_drift_bps(&[]), 0);
+    }
+    
+    fn holding(mint: Pubkey) -> AssetHolding {
+        AssetHolding {
+            mint,
+            balance: 0,
+            decimals: 6,
+            price: None,
+            max_price_age: MAX_PRICE_AGE,
+            target_weight_bps: 0,
+        }
+    }
+    
+    #[test]
+    fn test_rebalance_covers_every_asset_once() {
+        let usdc = Pubkey::new_unique();
+        let sol = Pubkey::new_unique();
+        assert!(check_all_assets(&[holding(usdc), holding(sol)], 2).is_ok());
+        
+        // A subset would let the caller choose the drift it is paid for
+        assert_eq!(
+            check_all_assets(&[holding(usdc)], 2).unwrap_err(),
+            ReserveError::InvalidAssetConfig.into()
+        );
+        
+        // So would counting one asset twice
+        assert_eq!(
+            check_all_assets(&[holding(usdc), holding(usdc)], 2).unwrap_err(),
+            ReserveError::InvalidAssetConfig.into()
+        );
+    }
+}
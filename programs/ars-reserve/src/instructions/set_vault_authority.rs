@@ -0,0 +1,157 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::ReserveError;
+use crate::instructions::initialize_vault::VAULT_SEED;
+
+/// ars-core program, whose GlobalState authority governs the reserve
+pub const ARS_CORE_PROGRAM_ID: Pubkey = Pubkey::new_from_array(ars_common::ARS_CORE_PROGRAM_ID);
+
+/// Seed of the ars-core GlobalState PDA
+pub const CORE_GLOBAL_STATE_SEED: &[u8] = b"global_state";
+
+#[derive(Accounts)]
+pub struct SetVaultAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, ReserveVault>,
+    
+    /// CHECK: ars-core GlobalState, parsed by read_core_authority
+    #[account(
+        seeds = [CORE_GLOBAL_STATE_SEED],
+        bump,
+        seeds::program = ARS_CORE_PROGRAM_ID,
+        owner = ARS_CORE_PROGRAM_ID @ ReserveError::InvalidGovernanceState
+    )]
+    pub core_global_state: UncheckedAccount<'info>,
+    
+    /// The ars-core authority, not the vault's own authority
+    pub governance_authority: Signer<'info>,
+}
+
+/// Read the authority from raw ars-core GlobalState data, where it is the
+/// first field after the discriminator
+pub fn read_core_authority(data: &[u8]) -> Result<Pubkey> {
+    let discriminator = anchor_lang::solana_program::hash::hash(b"account:GlobalState");
+    require!(
+        data.len() >= 8 + 32 && data[..8] == discriminator.to_bytes()[..8],
+        ReserveError::InvalidGovernanceState
+    );
+    Ok(Pubkey::new_from_array(data[8..40].try_into().unwrap()))
+}
+
+/// Require `signer` to be the ars-core authority recorded in `core_global_state`
+pub fn check_core_authority(core_global_state: &[u8], signer: &Pubkey) -> Result<()> {
+    require_keys_eq!(read_core_authority(core_global_state)?, *signer, ReserveError::Unauthorized);
+    Ok(())
+}
+
+/// Hand the vault to `new_authority` if `signer` is the ars-core authority
+/// recorded in `core_global_state`. This is the recovery path for a lost
+/// vault key, so the vault's current authority plays no part in it.
+pub fn rotate_vault_authority(
+    vault: &mut ReserveVault,
+    core_global_state: &[u8],
+    signer: &Pubkey,
+    new_authority: Pubkey,
+) -> Result<()> {
+    check_core_authority(core_global_state, signer)?;
+    require!(new_authority != Pubkey::default(), ReserveError::InvalidAuthority);
+    vault.authority = new_authority;
+    Ok(())
+}
+
+pub fn handler(ctx: Context<SetVaultAuthority>, new_authority: Pubkey) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let previous_authority = vault.authority;
+    
+    rotate_vault_authority(
+        vault,
+        &ctx.accounts.core_global_state.try_borrow_data()?,
+        &ctx.accounts.governance_authority.key(),
+        new_authority,
+    )?;
+    
+    emit!(VaultAuthorityChanged {
+        previous_authority,
+        new_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Vault authority rotated by governance");
+    msg!("Previous: {}", previous_authority);
+    msg!("New: {}", new_authority);
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn core_global_state(authority: Pubkey) -> Vec<u8> {
+        let discriminator = anchor_lang::solana_program::hash::hash(b"account:GlobalState");
+        let mut data = discriminator.to_bytes()[..8].to_vec();
+        data.extend_from_slice(authority.as_ref());
+        data.extend_from_slice(&[0u8; 64]);
+        data
+    }
+    
+    fn vault(authority: Pubkey) -> ReserveVault {
+        ReserveVault {
+            authority,
+            rebalance_threshold_bps: 1500,
+            max_assets: 3,
+            bump: 255,
+            ..Default::default()
+        }
+    }
+    
+    #[test]
+    fn test_shared_program_id_matches_declared() {
+        assert_eq!(Pubkey::new_from_array(ars_common::ARS_RESERVE_PROGRAM_ID), crate::ID);
+    }
+    
+    #[test]
+    fn test_core_authority_rotates_vault_authority() {
+        let core_authority = Pubkey::new_unique();
+        let lost_key = Pubkey::new_unique();
+        let replacement = Pubkey::new_unique();
+        let core_state = core_global_state(core_authority);
+        let mut vault = vault(lost_key);
+        
+        rotate_vault_authority(&mut vault, &core_state, &core_authority, replacement).unwrap();
+        assert_eq!(vault.authority, replacement);
+    }
+    
+    #[test]
+    fn test_vault_authority_rotation_rejected() {
+        let core_authority = Pubkey::new_unique();
+        let vault_authority = Pubkey::new_unique();
+        let core_state = core_global_state(core_authority);
+        let mut vault = vault(vault_authority);
+        
+        // The operational key cannot use the governance path
+        assert_eq!(
+            rotate_vault_authority(&mut vault, &core_state, &vault_authority, Pubkey::new_unique()).unwrap_err(),
+            ReserveError::Unauthorized.into()
+        );
+        
+        // Nor can the vault be handed to the default key
+        assert_eq!(
+            rotate_vault_authority(&mut vault, &core_state, &core_authority, Pubkey::default()).unwrap_err(),
+            ReserveError::InvalidAuthority.into()
+        );
+        
+        // Some other account type in place of GlobalState
+        let mut forged = core_state.clone();
+        forged[0] ^= 1;
+        assert_eq!(
+            rotate_vault_authority(&mut vault, &forged, &core_authority, Pubkey::new_unique()).unwrap_err(),
+            ReserveError::InvalidGovernanceState.into()
+        );
+        assert_eq!(vault.authority, vault_authority);
+    }
+}
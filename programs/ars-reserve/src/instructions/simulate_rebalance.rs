@@ -0,0 +1,247 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use crate::state::*;
+use crate::errors::ReserveError;
+use crate::instructions::initialize_vault::VAULT_SEED;
+use crate::instructions::update_vhr_from_prices::{asset_value_usd, portfolio_value_usd};
+
+/// Direction of a simulated rebalance trade
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
+/// One leg a keeper has to execute to move an asset to its target weight
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct RebalanceTrade {
+    pub mint: Pubkey,
+    pub side: TradeSide,
+    pub value_usd: u64,             // Scaled by 1e6
+    pub amount: u64,                // Token base units at the supplied price
+}
+
+/// Current holding of a reserve asset and its configured target weight
+pub struct AssetPosition {
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub decimals: u8,
+    pub price: AssetPrice,
+    pub max_price_age: i64,
+    pub target_weight_bps: u16,
+}
+
+#[derive(Accounts)]
+pub struct SimulateRebalance<'info> {
+    #[account(
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, ReserveVault>,
+    
+    #[account(address = vault.usdc_vault @ ReserveError::InvalidAccountOwner)]
+    pub usdc_vault: Account<'info, TokenAccount>,
+    
+    #[account(address = vault.sol_vault @ ReserveError::InvalidAccountOwner)]
+    pub sol_vault: Account<'info, TokenAccount>,
+    
+    #[account(address = vault.msol_vault @ ReserveError::InvalidAccountOwner)]
+    pub msol_vault: Account<'info, TokenAccount>,
+    
+    #[account(address = usdc_vault.mint)]
+    pub usdc_mint: Account<'info, Mint>,
+    
+    #[account(address = sol_vault.mint)]
+    pub sol_mint: Account<'info, Mint>,
+    
+    #[account(address = msol_vault.mint)]
+    pub msol_mint: Account<'info, Mint>,
+    
+    #[account(constraint = usdc_config.mint == usdc_vault.mint @ ReserveError::InvalidAccountOwner)]
+    pub usdc_config: Account<'info, AssetConfig>,
+    
+    #[account(constraint = sol_config.mint == sol_vault.mint @ ReserveError::InvalidAccountOwner)]
+    pub sol_config: Account<'info, AssetConfig>,
+    
+    #[account(constraint = msol_config.mint == msol_vault.mint @ ReserveError::InvalidAccountOwner)]
+    pub msol_config: Account<'info, AssetConfig>,
+}
+
+pub fn handler(
+    ctx: Context<SimulateRebalance>,
+    usdc_price: AssetPrice,
+    sol_price: AssetPrice,
+    msol_price: AssetPrice,
+) -> Result<Vec<RebalanceTrade>> {
+    let clock = Clock::get()?;
+    let accounts = &ctx.accounts;
+    
+    let position = |vault: &TokenAccount, mint: &Mint, price: AssetPrice, config: &AssetConfig| AssetPosition {
+        mint: vault.mint,
+        amount: vault.amount,
+        decimals: mint.decimals,
+        price,
+        max_price_age: config.max_price_age(),
+        target_weight_bps: config.target_weight_bps,
+    };
+    let positions = [
+        position(&accounts.usdc_vault, &accounts.usdc_mint, usdc_price, &accounts.usdc_config),
+        position(&accounts.sol_vault, &accounts.sol_mint, sol_price, &accounts.sol_config),
+        position(&accounts.msol_vault, &accounts.msol_mint, msol_price, &accounts.msol_config),
+    ];
+    
+    let trades = simulate_trades(&positions, clock.unix_timestamp)?;
+    
+    for trade in &trades {
+        msg!("{:?} {} of {} (${})", trade.side, trade.amount, trade.mint, trade.value_usd);
+    }
+    
+    Ok(trades)
+}
+
+/// Buy/sell legs that move each position from its current USD value to
+/// `target_weight_bps` of the portfolio. Assets already on target are
+/// omitted. Target weights must sum to 100%.
+pub fn simulate_trades(positions: &[AssetPosition], now: i64) -> Result<Vec<RebalanceTrade>> {
+    let total_weight: u32 = positions.iter().map(|p| p.target_weight_bps as u32).sum();
+    require!(total_weight == 10000, ReserveError::InvalidTargetWeights);
+    
+    let holdings: Vec<(u64, u8, AssetPrice, i64)> = positions
+        .iter()
+        .map(|p| (p.amount, p.decimals, p.price, p.max_price_age))
+        .collect();
+    let total_value_usd = portfolio_value_usd(&holdings, now)?;
+    
+    let mut trades = Vec::new();
+    for position in positions {
+        let current_value = asset_value_usd(position.amount, position.decimals, position.price.price_usd)?;
+        let target_value = ((total_value_usd as u128 * position.target_weight_bps as u128) / 10000) as u64;
+        
+        let (side, value_usd) = if target_value > current_value {
+            (TradeSide::Buy, target_value - current_value)
+        } else if current_value > target_value {
+            (TradeSide::Sell, current_value - target_value)
+        } else {
+            continue;
+        };
+        
+        trades.push(RebalanceTrade {
+            mint: position.mint,
+            side,
+            value_usd,
+            amount: usd_to_amount(value_usd, position.decimals, position.price.price_usd)?,
+        });
+    }
+    
+    Ok(trades)
+}
+
+/// Token base units worth `value_usd` at `price_usd`, rounded down
+pub fn usd_to_amount(value_usd: u64, decimals: u8, price_usd: u64) -> Result<u64> {
+    let scale = 10u128
+        .checked_pow(decimals as u32)
+        .ok_or(ReserveError::ArithmeticOverflow)?;
+    let amount = (value_usd as u128)
+        .checked_mul(scale)
+        .ok_or(ReserveError::ArithmeticOverflow)?
+        .checked_div(price_usd as u128)
+        .ok_or(ReserveError::InvalidPrice)?;
+    
+    u64::try_from(amount).map_err(|_| error!(ReserveError::ArithmeticOverflow))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const NOW: i64 = 1_700_000_000;
+    
+    fn position(mint: Pubkey, amount: u64, decimals: u8, price_usd: u64, target_weight_bps: u16) -> AssetPosition {
+        AssetPosition {
+            mint,
+            amount,
+            decimals,
+            price: AssetPrice { price_usd, published_at: NOW - 60 },
+            max_price_age: MAX_PRICE_AGE,
+            target_weight_bps,
+        }
+    }
+    
+    #[test]
+    fn test_skewed_portfolio_trades() {
+        let (usdc, sol, msol) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        
+        // $1M total: 70% USDC, 20% SOL, 10% mSOL against a 40/40/20 target
+        let positions = [
+            position(usdc, 700_000_000_000, 6, 1_000_000, 4000),      // 700,000 USDC @ $1
+            position(sol, 2_000_000_000_000, 9, 100_000_000, 4000),   // 2,000 SOL @ $100
+            position(msol, 500_000_000_000, 9, 200_000_000, 2000),    // 500 mSOL @ $200
+        ];
+        
+        let trades = simulate_trades(&positions, NOW).unwrap();
+        assert_eq!(
+            trades,
+            vec![
+                // Sell $300k of USDC
+                RebalanceTrade { mint: usdc, side: TradeSide::Sell, value_usd: 300_000_000_000, amount: 300_000_000_000 },
+                // Buy $200k of SOL = 2,000 SOL
+                RebalanceTrade { mint: sol, side: TradeSide::Buy, value_usd: 200_000_000_000, amount: 2_000_000_000_000 },
+                // Buy $100k of mSOL = 500 mSOL
+                RebalanceTrade { mint: msol, side: TradeSide::Buy, value_usd: 100_000_000_000, amount: 500_000_000_000 },
+            ]
+        );
+        
+        // Buys and sells net out
+        let net: i128 = trades
+            .iter()
+            .map(|t| match t.side {
+                TradeSide::Buy => t.value_usd as i128,
+                TradeSide::Sell => -(t.value_usd as i128),
+            })
+            .sum();
+        assert_eq!(net, 0);
+    }
+    
+    #[test]
+    fn test_balanced_portfolio_has_no_trades() {
+        let positions = [
+            position(Pubkey::new_unique(), 500_000_000, 6, 1_000_000, 5000),
+            position(Pubkey::new_unique(), 5_000_000_000, 9, 100_000_000, 5000),
+        ];
+        assert!(simulate_trades(&positions, NOW).unwrap().is_empty());
+    }
+    
+    #[test]
+    fn test_target_weights_must_sum_to_full() {
+        let positions = [
+            position(Pubkey::new_unique(), 500_000_000, 6, 1_000_000, 5000),
+            position(Pubkey::new_unique(), 5_000_000_000, 9, 100_000_000, 4000),
+        ];
+        assert_eq!(
+            simulate_trades(&positions, NOW).unwrap_err(),
+            ReserveError::InvalidTargetWeights.into()
+        );
+    }
+    
+    #[test]
+    fn test_one_stale_feed_blocks_simulation() {
+        let mut usdc = position(Pubkey::new_unique(), 500_000_000, 6, 1_000_000, 5000);
+        let mut sol = position(Pubkey::new_unique(), 5_000_000_000, 9, 100_000_000, 5000);
+        usdc.price.published_at = NOW - 1_800;
+        sol.price.published_at = NOW - 1_800;
+        
+        // USDC tolerates the age, SOL does not
+        usdc.max_price_age = 3_600;
+        assert_eq!(
+            simulate_trades(&[usdc, sol], NOW).unwrap_err(),
+            ReserveError::StalePrice.into()
+        );
+    }
+    
+    #[test]
+    fn test_usd_to_amount_rounds_down() {
+        // $100 of SOL at $150 = 0.666666666 SOL
+        assert_eq!(usd_to_amount(100_000_000, 9, 150_000_000).unwrap(), 666_666_666);
+        assert!(usd_to_amount(1, 6, 0).is_err());
+    }
+}
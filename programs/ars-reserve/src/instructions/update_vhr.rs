@@ -9,7 +9,7 @@ pub struct UpdateVHR<'info> {
         mut,
         seeds = [VAULT_SEED],
         bump = vault.bump,
-        constraint = vault.authority == authority.key() @ ReserveError::Unauthorized
+        has_one = authority @ ReserveError::Unauthorized
     )]
     pub vault: Account<'info, ReserveVault>,
     
@@ -28,15 +28,10 @@ pub fn handler(
     
     // Calculate VHR = (reserves / liabilities) * 10000
     // VHR is in basis points (15000 = 150%)
+    let vhr = compute_vhr(total_value_usd, liabilities_usd)?;
+    vault.vhr = vhr;
+    
     if liabilities_usd > 0 {
-        let vhr = (total_value_usd as u128)
-            .checked_mul(10000)
-            .ok_or(ReserveError::ArithmeticOverflow)?
-            .checked_div(liabilities_usd as u128)
-            .ok_or(ReserveError::ArithmeticOverflow)? as u16;
-        
-        vault.vhr = vhr;
-        
         msg!("VHR updated to: {} bps", vhr);
         msg!("Total value: ${}", total_value_usd);
         msg!("Liabilities: ${}", liabilities_usd);
@@ -46,9 +41,31 @@ pub fn handler(
             msg!("WARNING: VHR below 150% threshold!");
         }
     } else {
-        vault.vhr = u16::MAX; // Infinite VHR when no liabilities
         msg!("VHR: Infinite (no liabilities)");
     }
     
     Ok(())
 }
+
+/// VHR in basis points, saturating at u16::MAX (also used for no liabilities)
+pub fn compute_vhr(total_value_usd: u64, liabilities_usd: u64) -> Result<u16> {
+    // Infinite VHR when no liabilities
+    Ok(try_compute_vhr(total_value_usd, liabilities_usd)?.unwrap_or(u16::MAX))
+}
+
+/// VHR in basis points, saturating at u16::MAX, or None when it is undefined
+/// because there are no liabilities. Never panics; arithmetic failures are
+/// returned as errors.
+pub fn try_compute_vhr(total_value_usd: u64, liabilities_usd: u64) -> Result<Option<u16>> {
+    if liabilities_usd == 0 {
+        return Ok(None);
+    }
+    
+    let vhr = (total_value_usd as u128)
+        .checked_mul(10000)
+        .ok_or(ReserveError::ArithmeticOverflow)?
+        .checked_div(liabilities_usd as u128)
+        .ok_or(ReserveError::ArithmeticOverflow)?;
+    
+    Ok(Some(vhr.min(u16::MAX as u128) as u16))
+}
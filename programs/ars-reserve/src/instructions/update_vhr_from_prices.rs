@@ -0,0 +1,221 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use crate::state::*;
+use crate::errors::ReserveError;
+use crate::instructions::initialize_vault::VAULT_SEED;
+use crate::instructions::update_vhr::compute_vhr;
+
+#[derive(Accounts)]
+pub struct UpdateVHRFromPrices<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        has_one = authority @ ReserveError::Unauthorized
+    )]
+    pub vault: Account<'info, ReserveVault>,
+    
+    #[account(address = vault.usdc_vault @ ReserveError::InvalidAccountOwner)]
+    pub usdc_vault: Account<'info, TokenAccount>,
+    
+    #[account(address = vault.sol_vault @ ReserveError::InvalidAccountOwner)]
+    pub sol_vault: Account<'info, TokenAccount>,
+    
+    #[account(address = vault.msol_vault @ ReserveError::InvalidAccountOwner)]
+    pub msol_vault: Account<'info, TokenAccount>,
+    
+    #[account(address = usdc_vault.mint)]
+    pub usdc_mint: Account<'info, Mint>,
+    
+    #[account(address = sol_vault.mint)]
+    pub sol_mint: Account<'info, Mint>,
+    
+    #[account(address = msol_vault.mint)]
+    pub msol_mint: Account<'info, Mint>,
+    
+    #[account(mut, constraint = usdc_config.mint == usdc_vault.mint @ ReserveError::InvalidAccountOwner)]
+    pub usdc_config: Account<'info, AssetConfig>,
+    
+    #[account(mut, constraint = sol_config.mint == sol_vault.mint @ ReserveError::InvalidAccountOwner)]
+    pub sol_config: Account<'info, AssetConfig>,
+    
+    #[account(mut, constraint = msol_config.mint == msol_vault.mint @ ReserveError::InvalidAccountOwner)]
+    pub msol_config: Account<'info, AssetConfig>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPriceStaleness<'info> {
+    #[account(
+        seeds = [VAULT_SEED],
+        bump = vault.bump,
+        has_one = authority @ ReserveError::Unauthorized
+    )]
+    pub vault: Account<'info, ReserveVault>,
+    
+    #[account(mut)]
+    pub asset_config: Account<'info, AssetConfig>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Set how old a price may be when valuing this asset. Stablecoins can
+/// tolerate older prices than volatile assets; 0 restores MAX_PRICE_AGE.
+pub fn set_price_staleness(ctx: Context<SetPriceStaleness>, price_staleness_secs: i64) -> Result<()> {
+    require!(
+        (0..=MAX_CONFIGURABLE_PRICE_AGE).contains(&price_staleness_secs),
+        ReserveError::InvalidPriceStaleness
+    );
+    
+    let asset_config = &mut ctx.accounts.asset_config;
+    asset_config.price_staleness_secs = price_staleness_secs;
+    
+    msg!("Price staleness for {}: {} seconds", asset_config.mint, asset_config.max_price_age());
+    
+    Ok(())
+}
+
+pub fn handler(
+    ctx: Context<UpdateVHRFromPrices>,
+    usdc_price: AssetPrice,
+    sol_price: AssetPrice,
+    msol_price: AssetPrice,
+    liabilities_usd: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let accounts = &ctx.accounts;
+    
+    let holdings = [
+        (accounts.usdc_vault.amount, accounts.usdc_mint.decimals, usdc_price, accounts.usdc_config.max_price_age()),
+        (accounts.sol_vault.amount, accounts.sol_mint.decimals, sol_price, accounts.sol_config.max_price_age()),
+        (accounts.msol_vault.amount, accounts.msol_mint.decimals, msol_price, accounts.msol_config.max_price_age()),
+    ];
+    let total_value_usd = portfolio_value_usd(&holdings, clock.unix_timestamp)?;
+    let vhr = compute_vhr(total_value_usd, liabilities_usd)?;
+    
+    // The prices are now validated and authority-signed; keep them for
+    // permissionless instructions such as rebalance
+    ctx.accounts.usdc_config.record_price(usdc_price);
+    ctx.accounts.sol_config.record_price(sol_price);
+    ctx.accounts.msol_config.record_price(msol_price);
+    
+    let vault = &mut ctx.accounts.vault;
+    vault.total_value_usd = total_value_usd;
+    vault.liabilities_usd = liabilities_usd;
+    vault.vhr = vhr;
+    
+    msg!("VHR computed from prices: {} bps", vhr);
+    msg!("Total value: ${}", total_value_usd);
+    msg!("Liabilities: ${}", liabilities_usd);
+    
+    Ok(())
+}
+
+/// Reject zero prices and prices older than MAX_PRICE_AGE
+pub fn validate_price(price: &AssetPrice, now: i64) -> Result<()> {
+    validate_price_age(price, now, MAX_PRICE_AGE)
+}
+
+/// Reject zero prices and prices older than `max_age` seconds
+pub fn validate_price_age(price: &AssetPrice, now: i64, max_age: i64) -> Result<()> {
+    require!(price.price_usd > 0, ReserveError::InvalidPrice);
+    require!(
+        price.published_at <= now && now - price.published_at <= max_age,
+        ReserveError::StalePrice
+    );
+    Ok(())
+}
+
+/// USD value (scaled by 1e6) of `amount` base units of a token with `decimals`
+pub fn asset_value_usd(amount: u64, decimals: u8, price_usd: u64) -> Result<u64> {
+    let scale = 10u128
+        .checked_pow(decimals as u32)
+        .ok_or(ReserveError::ArithmeticOverflow)?;
+    let value = (amount as u128)
+        .checked_mul(price_usd as u128)
+        .ok_or(ReserveError::ArithmeticOverflow)?
+        .checked_div(scale)
+        .ok_or(ReserveError::ArithmeticOverflow)?;
+    
+    u64::try_from(value).map_err(|_| error!(ReserveError::ArithmeticOverflow))
+}
+
+/// Total USD value of `(amount, decimals, price, max_price_age)` holdings,
+/// validating each price against its own staleness window
+pub fn portfolio_value_usd(holdings: &[(u64, u8, AssetPrice, i64)], now: i64) -> Result<u64> {
+    holdings.iter().try_fold(0u64, |total, (amount, decimals, price, max_age)| {
+        validate_price_age(price, now, *max_age)?;
+        total
+            .checked_add(asset_value_usd(*amount, *decimals, price.price_usd)?)
+            .ok_or(error!(ReserveError::ArithmeticOverflow))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const NOW: i64 = 1_700_000_000;
+    
+    fn price(price_usd: u64) -> AssetPrice {
+        AssetPrice { price_usd, published_at: NOW - 60 }
+    }
+    
+    #[test]
+    fn test_mixed_portfolio_vhr() {
+        let holdings = [
+            (1_000_000_000_000, 6, price(1_000_000), MAX_PRICE_AGE),   // 1,000,000 USDC @ $1
+            (5_000_000_000_000, 9, price(150_000_000), MAX_PRICE_AGE), // 5,000 SOL @ $150
+            (2_000_000_000_000, 9, price(165_000_000), MAX_PRICE_AGE), // 2,000 mSOL @ $165
+        ];
+        
+        // $1,000,000 + $750,000 + $330,000
+        let total = portfolio_value_usd(&holdings, NOW).unwrap();
+        assert_eq!(total, 2_080_000_000_000);
+        
+        // $2.08M against $1.6M of liabilities = 130%
+        assert_eq!(compute_vhr(total, 1_600_000_000_000).unwrap(), 13_000);
+        assert_eq!(compute_vhr(total, 0).unwrap(), u16::MAX);
+    }
+    
+    #[test]
+    fn test_asset_value_rounds_down() {
+        // 1.5 SOL at $150.333333
+        assert_eq!(asset_value_usd(1_500_000_000, 9, 150_333_333).unwrap(), 225_499_999);
+        assert_eq!(asset_value_usd(0, 9, 150_000_000).unwrap(), 0);
+    }
+    
+    #[test]
+    fn test_invalid_prices_rejected() {
+        let zero = [(1_000_000, 6, price(0), MAX_PRICE_AGE)];
+        assert_eq!(
+            portfolio_value_usd(&zero, NOW).unwrap_err(),
+            ReserveError::InvalidPrice.into()
+        );
+        
+        let stale = [(1_000_000, 6, AssetPrice { price_usd: 1_000_000, published_at: NOW - MAX_PRICE_AGE - 1 }, MAX_PRICE_AGE)];
+        assert_eq!(
+            portfolio_value_usd(&stale, NOW).unwrap_err(),
+            ReserveError::StalePrice.into()
+        );
+        
+        let future = [(1_000_000, 6, AssetPrice { price_usd: 1_000_000, published_at: NOW + 1 }, MAX_PRICE_AGE)];
+        assert!(portfolio_value_usd(&future, NOW).is_err());
+    }
+    
+    #[test]
+    fn test_per_asset_staleness() {
+        // A 20 minute old USDC price is fine under a 1 hour window
+        let usdc = (1_000_000_000, 6, AssetPrice { price_usd: 1_000_000, published_at: NOW - 1_200 }, 3_600);
+        let sol = (1_000_000_000, 9, price(150_000_000), MAX_PRICE_AGE);
+        assert_eq!(portfolio_value_usd(&[usdc, sol], NOW).unwrap(), 1_150_000_000);
+        
+        // The same age on SOL's 5 minute window blocks the whole valuation
+        let stale_sol = (1_000_000_000, 9, AssetPrice { price_usd: 150_000_000, published_at: NOW - 1_200 }, MAX_PRICE_AGE);
+        assert_eq!(
+            portfolio_value_usd(&[usdc, stale_sol], NOW).unwrap_err(),
+            ReserveError::StalePrice.into()
+        );
+    }
+}
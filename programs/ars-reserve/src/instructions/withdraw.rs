@@ -1,36 +1,75 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use crate::state::*;
-use crate::errors::ReserveError;
+use crate::errors::{key_mismatch, ReserveError};
 use crate::instructions::initialize_vault::VAULT_SEED;
-use crate::utils::ReentrancyGuard;
+use crate::instructions::bootstrap_reserve::ASSET_CONFIG_SEED;
+use crate::instructions::idempotency::{claim_idempotency_key, IDEMPOTENCY_SEED};
+use crate::instructions::update_vhr::try_compute_vhr;
+use crate::utils::{mint_matches, ReentrancyGuard};
 
 #[derive(Accounts)]
+#[instruction(amount: u64, idempotency_key: Option<[u8; 32]>)]
 pub struct Withdraw<'info> {
     #[account(
         mut,
         seeds = [VAULT_SEED],
         bump = vault.bump,
-        constraint = vault.authority == authority.key() @ ReserveError::Unauthorized
+        has_one = authority @ ReserveError::Unauthorized
     )]
     pub vault: Account<'info, ReserveVault>,
     
     #[account(
         mut,
-        constraint = vault_token_account.owner == vault.key() @ ReserveError::InvalidAccountOwner
+        constraint = vault_token_account.owner == vault.key()
+            @ key_mismatch(ReserveError::VaultNotTokenOwner, vault_token_account.owner, vault.key())
     )]
     pub vault_token_account: Account<'info, TokenAccount>,
     
-    #[account(mut)]
+    /// The withdrawal fee accrues against the mint being withdrawn
+    #[account(
+        mut,
+        seeds = [ASSET_CONFIG_SEED, vault_token_account.mint.as_ref()],
+        bump = asset_config.bump
+    )]
+    pub asset_config: Account<'info, AssetConfig>,
+    
+    #[account(
+        mut,
+        constraint = mint_matches(&recipient_token_account, &vault_token_account) @ ReserveError::InvalidTokenMint,
+        constraint = vault.allows_withdraw_recipient(&recipient_token_account.key()) @ ReserveError::RecipientNotAllowlisted
+    )]
     pub recipient_token_account: Account<'info, TokenAccount>,
     
+    /// Present when the caller supplies an idempotency key
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = IdempotencyRecord::LEN,
+        seeds = [IDEMPOTENCY_SEED, authority.key().as_ref(), &idempotency_key.unwrap_or_default()],
+        bump
+    )]
+    pub idempotency_record: Option<Account<'info, IdempotencyRecord>>,
+    
+    #[account(mut)]
     pub authority: Signer<'info>,
     
     pub token_program: Program<'info, Token>,
+    
+    pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+pub fn handler(ctx: Context<Withdraw>, amount: u64, idempotency_key: Option<[u8; 32]>) -> Result<()> {
     require!(amount > 0, ReserveError::InvalidAmount);
+    
+    // Reject a retried submission of the same withdrawal
+    claim_idempotency_key(
+        ctx.accounts.idempotency_record.as_deref_mut(),
+        idempotency_key,
+        ctx.accounts.authority.key(),
+        Clock::get()?.unix_timestamp,
+    )?;
+    
     require!(
         ctx.accounts.vault_token_account.amount >= amount,
         ReserveError::InsufficientVaultBalance
@@ -41,17 +80,17 @@ pub fn handler(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
     // Acquire reentrancy lock
     let _guard = ReentrancyGuard::acquire(&mut vault.locked)?;
     
+    // The fee stays in the vault; only the net amount leaves it
+    let (fee, net_amount) = vault.split_withdrawal(amount)?;
+    
     // Check VHR after withdrawal would still be above threshold
     let new_total_value = vault.total_value_usd
         .checked_sub(amount)
         .ok_or(ReserveError::ArithmeticUnderflow)?;
     
-    // Calculate new VHR (simplified)
-    let new_vhr = if vault.liabilities_usd > 0 {
-        ((new_total_value as u128 * 10000) / vault.liabilities_usd as u128) as u16
-    } else {
-        10000 // 100% if no liabilities
-    };
+    // Calculate new VHR, saturating rather than wrapping for large reserves
+    let new_vhr = try_compute_vhr(new_total_value, vault.liabilities_usd)?
+        .unwrap_or(10000); // 100% if no liabilities
     
     require!(
         new_vhr >= vault.rebalance_threshold_bps,
@@ -72,13 +111,23 @@ pub fn handler(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
     let cpi_program = ctx.accounts.token_program.to_account_info();
     let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
     
-    token::transfer(cpi_ctx, amount)?;
+    token::transfer(cpi_ctx, net_amount)?;
     
     // Update vault state
     vault.total_value_usd = new_total_value;
     vault.vhr = new_vhr;
+    vault.accrue_fee(&mut ctx.accounts.asset_config, fee)?;
+    
+    emit!(Withdrawn {
+        mint: ctx.accounts.asset_config.mint,
+        amount,
+        fee,
+        net_amount,
+        recipient: ctx.accounts.recipient_token_account.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
     
-    msg!("Withdrawn {} tokens from vault", amount);
+    msg!("Withdrawn {} {} from vault ({} fee retained)", net_amount, ctx.accounts.asset_config.mint, fee);
     msg!("New vault total value: {} USD", vault.total_value_usd);
     msg!("New VHR: {} bps", vault.vhr);
     
@@ -87,3 +136,145 @@ pub fn handler(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
     
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+    use anchor_lang::solana_program::program_pack::Pack;
+    use anchor_spl::token::spl_token::state::{Account as SplAccount, AccountState};
+    
+    /// Backing storage for one AccountInfo handed to try_accounts
+    struct TestAccount {
+        key: Pubkey,
+        owner: Pubkey,
+        lamports: u64,
+        data: Vec<u8>,
+        is_signer: bool,
+        executable: bool,
+    }
+    
+    impl TestAccount {
+        fn new(key: Pubkey, owner: Pubkey, data: Vec<u8>) -> Self {
+            TestAccount { key, owner, lamports: 1_000_000_000, data, is_signer: false, executable: false }
+        }
+        
+        fn program(key: Pubkey) -> Self {
+            TestAccount { executable: true, ..Self::new(key, Pubkey::default(), Vec::new()) }
+        }
+        
+        fn info(&mut self) -> AccountInfo<'_> {
+            AccountInfo::new(
+                &self.key,
+                self.is_signer,
+                true,
+                &mut self.lamports,
+                &mut self.data,
+                &self.owner,
+                self.executable,
+                0,
+            )
+        }
+    }
+    
+    /// try_accounts reads the Rent sysvar for init_if_needed, which has no
+    /// default off-chain implementation
+    struct RentStub;
+    
+    impl anchor_lang::solana_program::program_stubs::SyscallStubs for RentStub {
+        fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+            unsafe { *(var_addr as *mut Rent) = Rent::default() };
+            anchor_lang::solana_program::entrypoint::SUCCESS
+        }
+    }
+    
+    fn token_account(mint: Pubkey, owner: Pubkey) -> TestAccount {
+        let account = SplAccount {
+            mint,
+            owner,
+            amount: 1_000_000,
+            state: AccountState::Initialized,
+            ..Default::default()
+        };
+        let mut data = vec![0u8; SplAccount::LEN];
+        SplAccount::pack(account, &mut data).unwrap();
+        TestAccount::new(Pubkey::new_unique(), Token::id(), data)
+    }
+    
+    /// Run Withdraw's account constraints, optionally passing a vault token
+    /// account held by someone else or the AssetConfig of another mint
+    fn check_accounts(vault_owned: bool, config_mint_matches: bool) -> Result<()> {
+        static STUBS: std::sync::Once = std::sync::Once::new();
+        STUBS.call_once(|| {
+            anchor_lang::solana_program::program_stubs::set_syscall_stubs(Box::new(RentStub));
+        });
+        
+        let (vault_key, bump) = Pubkey::find_program_address(&[VAULT_SEED], &crate::ID);
+        let authority = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let vault_token_owner = if vault_owned { vault_key } else { Pubkey::new_unique() };
+        let config_mint = if config_mint_matches { mint } else { Pubkey::new_unique() };
+        let (config_key, config_bump) =
+            Pubkey::find_program_address(&[ASSET_CONFIG_SEED, config_mint.as_ref()], &crate::ID);
+        
+        let vault = ReserveVault { authority, bump, ..Default::default() };
+        let mut vault_data = Vec::new();
+        vault.try_serialize(&mut vault_data).unwrap();
+        let config = AssetConfig { mint: config_mint, bump: config_bump, ..Default::default() };
+        let mut config_data = Vec::new();
+        config.try_serialize(&mut config_data).unwrap();
+        
+        let mut accounts = [
+            TestAccount::new(vault_key, crate::ID, vault_data),
+            token_account(mint, vault_token_owner),
+            TestAccount::new(config_key, crate::ID, config_data),
+            token_account(mint, Pubkey::new_unique()),
+            TestAccount::program(crate::ID), // no idempotency record
+            TestAccount { is_signer: true, ..TestAccount::new(authority, Pubkey::default(), Vec::new()) },
+            TestAccount::program(Token::id()),
+            TestAccount::program(System::id()),
+        ];
+        let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+        let ix_data = (1_000u64, None::<[u8; 32]>).try_to_vec().unwrap();
+        
+        Withdraw::try_accounts(
+            &crate::ID,
+            &mut &infos[..],
+            &ix_data,
+            &mut WithdrawBumps::default(),
+            &mut BTreeSet::new(),
+        )
+        .map(|_| ())
+    }
+    
+    #[test]
+    fn test_vault_token_account_must_be_vault_owned() {
+        assert!(check_accounts(true, true).is_ok());
+        
+        // Right mint, but held by someone else: the vault seeds could not sign for it
+        let err = check_accounts(false, true).unwrap_err();
+        assert_eq!(err, ReserveError::VaultNotTokenOwner.into());
+        
+        // The log names the actual holder and the vault it should have been
+        let (vault_key, _) = Pubkey::find_program_address(&[VAULT_SEED], &crate::ID);
+        match err {
+            Error::AnchorError(e) => match e.compared_values {
+                Some(anchor_lang::error::ComparedValues::Pubkeys((left, right))) => {
+                    assert_ne!(left, vault_key);
+                    assert_eq!(right, vault_key);
+                }
+                _ => panic!("expected both keys in the error"),
+            },
+            other => panic!("expected an AnchorError, got {:?}", other),
+        }
+    }
+    
+    #[test]
+    fn test_fee_accrues_against_withdrawn_mint() {
+        // Another mint's config would book the fee in the wrong units
+        assert_eq!(
+            check_accounts(true, false).unwrap_err(),
+            anchor_lang::error::ErrorCode::ConstraintSeeds.into()
+        );
+    }
+}
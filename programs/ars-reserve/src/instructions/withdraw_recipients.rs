@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::instructions::initialize_vault::VAULT_SEED;
+use crate::instructions::set_vault_authority::{check_core_authority, ARS_CORE_PROGRAM_ID, CORE_GLOBAL_STATE_SEED};
+use crate::errors::ReserveError;
+
+/// The withdrawal allowlist is held by governance rather than the vault
+/// authority, so a compromised vault key cannot redirect withdrawals
+#[derive(Accounts)]
+pub struct ManageWithdrawRecipients<'info> {
+    #[account(
+        mut,
+        seeds = [VAULT_SEED],
+        bump = vault.bump
+    )]
+    pub vault: Account<'info, ReserveVault>,
+    
+    /// CHECK: ars-core GlobalState, parsed by check_core_authority
+    #[account(
+        seeds = [CORE_GLOBAL_STATE_SEED],
+        bump,
+        seeds::program = ARS_CORE_PROGRAM_ID,
+        owner = ARS_CORE_PROGRAM_ID @ ReserveError::InvalidGovernanceState
+    )]
+    pub core_global_state: UncheckedAccount<'info>,
+    
+    /// The ars-core authority, not the vault's own authority
+    pub governance_authority: Signer<'info>,
+}
+
+pub fn add_withdraw_recipient(ctx: Context<ManageWithdrawRecipients>, recipient: Pubkey) -> Result<()> {
+    check_core_authority(
+        &ctx.accounts.core_global_state.try_borrow_data()?,
+        &ctx.accounts.governance_authority.key(),
+    )?;
+    
+    let vault = &mut ctx.accounts.vault;
+    vault.add_withdraw_recipient(recipient)?;
+    
+    msg!("Withdrawal recipient added: {}", recipient);
+    msg!("Withdrawal recipients: {}", vault.withdraw_recipients.len());
+    
+    Ok(())
+}
+
+pub fn remove_withdraw_recipient(ctx: Context<ManageWithdrawRecipients>, recipient: Pubkey) -> Result<()> {
+    check_core_authority(
+        &ctx.accounts.core_global_state.try_borrow_data()?,
+        &ctx.accounts.governance_authority.key(),
+    )?;
+    
+    let vault = &mut ctx.accounts.vault;
+    vault.remove_withdraw_recipient(&recipient)?;
+    
+    msg!("Withdrawal recipient removed: {}", recipient);
+    msg!("Withdrawal recipients: {}", vault.withdraw_recipients.len());
+    
+    Ok(())
+}
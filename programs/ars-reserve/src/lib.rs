@@ -23,20 +23,58 @@ pub mod ars_reserve {
         instructions::initialize_vault::handler(ctx, rebalance_threshold_bps)
     }
 
+    /// Initialize the vault and register its base assets in one transaction
+    pub fn bootstrap_reserve<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BootstrapReserve<'info>>,
+        rebalance_threshold_bps: u16,
+        assets: Vec<AssetParams>,
+    ) -> Result<()> {
+        instructions::bootstrap_reserve::handler(ctx, rebalance_threshold_bps, assets)
+    }
+
+    /// Add an asset to the reserve at zero target weight, up to the asset cap
+    pub fn register_asset(ctx: Context<RegisterAsset>, params: AssetParams) -> Result<()> {
+        instructions::bootstrap_reserve::register_asset(ctx, params)
+    }
+
+    /// Set the maximum number of reserve assets
+    pub fn set_max_assets(ctx: Context<SetMaxAssets>, max_assets: u8) -> Result<()> {
+        instructions::bootstrap_reserve::set_max_assets(ctx, max_assets)
+    }
+
     /// Deposit assets into the vault
     pub fn deposit(
         ctx: Context<Deposit>,
         amount: u64,
+        idempotency_key: Option<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::deposit::handler(ctx, amount, idempotency_key)
+    }
+
+    /// Set the per-transaction and total vault deposit caps (0 = uncapped)
+    pub fn set_deposit_caps(
+        ctx: Context<SetDepositCaps>,
+        max_deposit_per_tx: u64,
+        max_total_value_usd: u64,
     ) -> Result<()> {
-        instructions::deposit::handler(ctx, amount)
+        instructions::deposit::set_deposit_caps(ctx, max_deposit_per_tx, max_total_value_usd)
     }
 
     /// Withdraw assets from the vault
     pub fn withdraw(
         ctx: Context<Withdraw>,
         amount: u64,
+        idempotency_key: Option<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::withdraw::handler(ctx, amount, idempotency_key)
+    }
+
+    /// Close an idempotency record whose window has passed and reclaim its rent
+    pub fn close_idempotency_record(
+        ctx: Context<CloseIdempotencyRecord>,
+        idempotency_key: [u8; 32],
     ) -> Result<()> {
-        instructions::withdraw::handler(ctx, amount)
+        instructions::idempotency::close_idempotency_record(ctx, idempotency_key)
     }
 
     /// Calculate and update VHR
@@ -48,10 +86,108 @@ pub mod ars_reserve {
         instructions::update_vhr::handler(ctx, total_value_usd, liabilities_usd)
     }
 
-    /// Rebalance the vault
-    pub fn rebalance(
-        ctx: Context<Rebalance>,
+    /// Compute and store VHR from vault balances and asset prices
+    pub fn update_vhr_from_prices(
+        ctx: Context<UpdateVHRFromPrices>,
+        usdc_price: AssetPrice,
+        sol_price: AssetPrice,
+        msol_price: AssetPrice,
+        liabilities_usd: u64,
+    ) -> Result<()> {
+        instructions::update_vhr_from_prices::handler(ctx, usdc_price, sol_price, msol_price, liabilities_usd)
+    }
+
+    /// Set the maximum price age accepted when valuing an asset
+    pub fn set_price_staleness(
+        ctx: Context<SetPriceStaleness>,
+        price_staleness_secs: i64,
     ) -> Result<()> {
-        instructions::rebalance::handler(ctx)
+        instructions::update_vhr_from_prices::set_price_staleness(ctx, price_staleness_secs)
+    }
+
+    /// Return the most ICU that can be minted while keeping VHR at or above a target
+    pub fn query_max_mintable(
+        ctx: Context<QueryMaxMintable>,
+        target_vhr_bps: u16,
+    ) -> Result<u64> {
+        instructions::query_max_mintable::handler(ctx, target_vhr_bps)
+    }
+
+    /// Return the vault's current VHR, or None when it has no liabilities
+    pub fn query_vhr(ctx: Context<QueryVHR>) -> Result<Option<u16>> {
+        instructions::query_vhr::handler(ctx)
+    }
+
+    /// Rebalance the vault, reverting past `deadline_ts` or below `min_amount_out`;
+    /// permissionless, paying the keeper reward when the swaps cut a drift past the threshold
+    pub fn rebalance<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Rebalance<'info>>,
+        min_amount_out: u64,
+        deadline_ts: i64,
+    ) -> Result<()> {
+        instructions::rebalance::handler(ctx, min_amount_out, deadline_ts)
+    }
+
+    /// Compute the per-asset trades that would bring the vault to its target weights
+    pub fn simulate_rebalance(
+        ctx: Context<SimulateRebalance>,
+        usdc_price: AssetPrice,
+        sol_price: AssetPrice,
+        msol_price: AssetPrice,
+    ) -> Result<Vec<RebalanceTrade>> {
+        instructions::simulate_rebalance::handler(ctx, usdc_price, sol_price, msol_price)
+    }
+
+    /// Return each registered asset's balance, USD value and current vs target weight
+    pub fn query_reserve_composition<'info>(
+        ctx: Context<'_, '_, 'info, 'info, QueryReserveComposition<'info>>,
+        prices: Vec<Option<AssetPrice>>,
+    ) -> Result<Vec<AssetComposition>> {
+        instructions::query_reserve_composition::handler(ctx, prices)
+    }
+
+    /// Designate the owner of the given token account as the treasury that receives swept fees
+    pub fn set_treasury(ctx: Context<SetTreasury>) -> Result<()> {
+        instructions::fees::set_treasury(ctx)
+    }
+
+    /// Set the share of accrued fees routed to the insurance fund
+    pub fn set_insurance_share(ctx: Context<SetInsuranceShare>, insurance_share_bps: u16) -> Result<()> {
+        instructions::fees::set_insurance_share(ctx, insurance_share_bps)
+    }
+
+    /// Set the fee retained by the vault on each withdrawal
+    pub fn set_withdraw_fee(ctx: Context<SetWithdrawFee>, withdraw_fee_bps: u16) -> Result<()> {
+        instructions::fees::set_withdraw_fee(ctx, withdraw_fee_bps)
+    }
+
+    /// Set the reward paid from accrued fees for a needed rebalance (0 = off)
+    pub fn set_rebalance_reward(ctx: Context<SetRebalanceReward>, rebalance_reward: u64) -> Result<()> {
+        instructions::fees::set_rebalance_reward(ctx, rebalance_reward)
+    }
+
+    /// Reassign the vault authority; signed by the ars-core authority, for recovering a lost vault key
+    pub fn set_vault_authority(ctx: Context<SetVaultAuthority>, new_authority: Pubkey) -> Result<()> {
+        instructions::set_vault_authority::handler(ctx, new_authority)
+    }
+
+    /// Allow withdrawals to a token account; signed by the ars-core authority
+    pub fn add_withdraw_recipient(ctx: Context<ManageWithdrawRecipients>, recipient: Pubkey) -> Result<()> {
+        instructions::withdraw_recipients::add_withdraw_recipient(ctx, recipient)
+    }
+
+    /// Remove a token account from the withdrawal allowlist; signed by the ars-core authority
+    pub fn remove_withdraw_recipient(ctx: Context<ManageWithdrawRecipients>, recipient: Pubkey) -> Result<()> {
+        instructions::withdraw_recipients::remove_withdraw_recipient(ctx, recipient)
+    }
+
+    /// Sweep one mint's accrued fees to the treasury
+    pub fn sweep_fees(ctx: Context<SweepFees>) -> Result<()> {
+        instructions::fees::sweep_fees(ctx)
+    }
+
+    /// Migrate the reserve vault to the current layout version
+    pub fn migrate_vault(ctx: Context<MigrateVault>) -> Result<()> {
+        instructions::migrate_vault::migrate_vault(ctx)
     }
 }
@@ -1,7 +1,23 @@
 use anchor_lang::prelude::*;
+use crate::errors::ReserveError;
+use crate::utils::mul_div_floor;
+
+/// Default maximum age of a price used to value an asset (5 minutes)
+pub const MAX_PRICE_AGE: i64 = 300;
+
+/// Upper bound for a per-asset `price_staleness_secs` (1 day)
+pub const MAX_CONFIGURABLE_PRICE_AGE: i64 = 86400;
+
+/// USD price for one whole token of an asset
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct AssetPrice {
+    pub price_usd: u64,             // Scaled by 1e6
+    pub published_at: i64,
+}
 
 /// Reserve vault state
 #[account]
+#[derive(Default)]
 pub struct ReserveVault {
     pub authority: Pubkey,
     pub usdc_vault: Pubkey,
@@ -12,11 +28,29 @@ pub struct ReserveVault {
     pub vhr: u16,                   // Basis points (15000 = 150%)
     pub last_rebalance: i64,
     pub rebalance_threshold_bps: u16, // 1500 = 15%
+    pub version: u8,                // Layout version, see migrate_vault
+    pub treasury: Pubkey,           // Governance-designated owner of the fee destination accounts
+    pub max_deposit_per_tx: u64,    // 0 = uncapped
+    pub max_total_value_usd: u64,   // 0 = uncapped, scaled by 1e6
+    pub insurance_share_bps: u16,   // Share of accrued fees routed to the insurance fund
+    pub asset_count: u8,            // AssetConfig accounts registered for this vault
+    pub max_assets: u8,             // Cap on asset_count, bounding rebalance loops
+    pub withdraw_fee_bps: u16,      // Retained from each withdrawal, 0 = no fee
+    pub rebalance_reward: u64,      // Paid from the asset's accrued fees to the caller of a needed rebalance, 0 = off
+    pub withdraw_recipients: Vec<Pubkey>, // Token accounts withdraw may pay; empty = any recipient
     pub locked: bool,               // Reentrancy guard
     pub bump: u8,
 }
 
 impl ReserveVault {
+    pub const MAX_WITHDRAW_RECIPIENTS: usize = 8;
+    /// Smallest cut in the largest weight drift that earns the keeper reward
+    pub const MIN_REWARDED_DRIFT_REDUCTION_BPS: u16 = 100;
+    
+    /// Layout version written by this program. The original layout, with
+    /// only the fields up to rebalance_threshold_bps plus locked and bump,
+    /// is version 0.
+    pub const CURRENT_VERSION: u8 = 1;
     pub const LEN: usize = 8 + // discriminator
         32 + // authority
         32 + // usdc_vault
@@ -27,12 +61,145 @@ impl ReserveVault {
         2 +  // vhr
         8 +  // last_rebalance
         2 +  // rebalance_threshold_bps
+        1 +  // version
+        32 + // treasury
+        8 +  // max_deposit_per_tx
+        8 +  // max_total_value_usd
+        2 +  // insurance_share_bps
+        1 +  // asset_count
+        1 +  // max_assets
+        2 +  // withdraw_fee_bps
+        8 +  // rebalance_reward
+        4 + Self::MAX_WITHDRAW_RECIPIENTS * 32 + // withdraw_recipients (vec)
         1 +  // locked
         1;   // bump
+    
+    /// Record fees retained by the vault in `asset`'s mint, routing
+    /// insurance_share_bps of them to the insurance fund and the rest to a
+    /// later sweep of that mint
+    pub fn accrue_fee(&mut self, asset: &mut AssetConfig, amount: u64) -> Result<()> {
+        let insured = ((amount as u128 * self.insurance_share_bps as u128) / 10000) as u64;
+        asset.fund_insurance(insured)?;
+        asset.accrued_fees = asset.accrued_fees
+            .checked_add(amount - insured)
+            .ok_or(ReserveError::ArithmeticOverflow)?;
+        Ok(())
+    }
+    
+    /// Count `count` newly registered assets, rejecting registration beyond
+    /// max_assets
+    pub fn register_assets(&mut self, count: usize) -> Result<()> {
+        let new_count = (self.asset_count as usize)
+            .checked_add(count)
+            .filter(|total| *total <= self.max_assets as usize)
+            .ok_or(ReserveError::TooManyAssets)?;
+        self.asset_count = new_count as u8;
+        Ok(())
+    }
+    
+    /// Reserves are worth less than the liabilities they back
+    pub fn is_undercollateralized(&self) -> bool {
+        self.liabilities_usd > 0 && self.vhr < 10000
+    }
+    
+    /// Absorb a `loss` of `asset`'s mint, in its base units, while
+    /// undercollateralized, drawing on that mint's insurance fund first and
+    /// on the main reserve only for the remainder. Returns (drawn from
+    /// insurance, drawn from the main reserve).
+    pub fn draw_insurance(&self, asset: &mut AssetConfig, loss: u64) -> Result<(u64, u64)> {
+        require!(self.is_undercollateralized(), ReserveError::InsuranceDrawNotAllowed);
+        
+        let from_insurance = loss.min(asset.insurance_fund);
+        asset.insurance_fund -= from_insurance;
+        
+        Ok((from_insurance, loss - from_insurance))
+    }
+    
+    /// Reject a deposit above the per-transaction cap or one that would push
+    /// total_value_usd past the vault cap. A cap of 0 disables that check.
+    pub fn check_deposit_caps(&self, amount: u64) -> Result<()> {
+        if self.max_deposit_per_tx > 0 {
+            require!(
+                amount <= self.max_deposit_per_tx,
+                ReserveError::DepositExceedsTxCap
+            );
+        }
+        if self.max_total_value_usd > 0 {
+            let new_total = self.total_value_usd
+                .checked_add(amount)
+                .ok_or(ReserveError::ArithmeticOverflow)?;
+            require!(
+                new_total <= self.max_total_value_usd,
+                ReserveError::DepositExceedsVaultCap
+            );
+        }
+        Ok(())
+    }
+    
+    /// Split a gross withdrawal into (fee, net amount paid out). The fee
+    /// rounds down so a zero fee rate pays out the full amount.
+    pub fn split_withdrawal(&self, amount: u64) -> Result<(u64, u64)> {
+        let fee = mul_div_floor(amount, self.withdraw_fee_bps as u64, 10000)?;
+        Ok((fee, amount - fee))
+    }
+    
+    /// Whether withdraw may pay `recipient`; any account is allowed while
+    /// the allowlist is empty
+    pub fn allows_withdraw_recipient(&self, recipient: &Pubkey) -> bool {
+        self.withdraw_recipients.is_empty() || self.withdraw_recipients.contains(recipient)
+    }
+    
+    /// Allow withdraw to pay the token account `recipient`
+    pub fn add_withdraw_recipient(&mut self, recipient: Pubkey) -> Result<()> {
+        require!(
+            !self.withdraw_recipients.contains(&recipient),
+            ReserveError::WithdrawRecipientAlreadyAllowed
+        );
+        require!(
+            self.withdraw_recipients.len() < Self::MAX_WITHDRAW_RECIPIENTS,
+            ReserveError::WithdrawRecipientAllowlistFull
+        );
+        self.withdraw_recipients.push(recipient);
+        Ok(())
+    }
+    
+    /// Stop withdraw from paying `recipient`. Removing the last entry
+    /// deactivates the allowlist.
+    pub fn remove_withdraw_recipient(&mut self, recipient: &Pubkey) -> Result<()> {
+        let index = self.withdraw_recipients
+            .iter()
+            .position(|key| key == recipient)
+            .ok_or(ReserveError::WithdrawRecipientNotFound)?;
+        self.withdraw_recipients.swap_remove(index);
+        Ok(())
+    }
+    
+    /// Take the keeper reward, in `asset`'s mint, for a rebalance that
+    /// moved the largest weight drift from `drift_before_bps` to
+    /// `drift_after_bps`. Nothing is paid unless the drift exceeded
+    /// rebalance_threshold_bps and the swaps cut it by at least
+    /// MIN_REWARDED_DRIFT_REDUCTION_BPS, and the reward is capped at the
+    /// fees accrued in that mint so it never draws on reserve backing.
+    pub fn take_rebalance_reward(
+        &self,
+        asset: &mut AssetConfig,
+        drift_before_bps: u16,
+        drift_after_bps: u16,
+    ) -> u64 {
+        if drift_before_bps <= self.rebalance_threshold_bps
+            || drift_before_bps.saturating_sub(drift_after_bps) < Self::MIN_REWARDED_DRIFT_REDUCTION_BPS
+        {
+            return 0;
+        }
+        let reward = self.rebalance_reward.min(asset.accrued_fees);
+        asset.accrued_fees -= reward;
+        reward
+    }
 }
 
 /// Asset configuration
 #[account]
+#[derive(Default)]
 pub struct AssetConfig {
     pub mint: Pubkey,
     pub target_weight_bps: u16,     // 3333 = 33.33%
@@ -40,6 +207,11 @@ pub struct AssetConfig {
     pub max_weight_bps: u16,
     pub volatility_threshold_bps: u16,
     pub current_weight_bps: u16,
+    pub price_staleness_secs: i64,  // Max price age for this asset, 0 = MAX_PRICE_AGE
+    pub accrued_fees: u64,          // Fees in this mint's base units held by the vault awaiting sweep
+    pub insurance_fund: u64,        // Loss-absorbing carve-out in this mint's base units, never swept
+    pub price_usd: u64,             // Last price validated by update_vhr_from_prices, scaled by 1e6, 0 = none
+    pub price_published_at: i64,
     pub bump: u8,
 }
 
@@ -51,5 +223,427 @@ impl AssetConfig {
         2 +  // max_weight_bps
         2 +  // volatility_threshold_bps
         2 +  // current_weight_bps
+        8 +  // price_staleness_secs
+        8 +  // accrued_fees
+        8 +  // insurance_fund
+        8 +  // price_usd
+        8 +  // price_published_at
         1;   // bump
+    
+    /// Oldest price accepted when valuing this asset
+    pub fn max_price_age(&self) -> i64 {
+        if self.price_staleness_secs > 0 {
+            self.price_staleness_secs
+        } else {
+            MAX_PRICE_AGE
+        }
+    }
+    
+    /// Keep a price validated under the vault authority's signature, for
+    /// permissionless instructions that must not trust caller-supplied prices
+    pub fn record_price(&mut self, price: AssetPrice) {
+        self.price_usd = price.price_usd;
+        self.price_published_at = price.published_at;
+    }
+    
+    /// The last recorded price, if any. Callers still check its age.
+    pub fn recorded_price(&self) -> Option<AssetPrice> {
+        (self.price_usd > 0).then_some(AssetPrice {
+            price_usd: self.price_usd,
+            published_at: self.price_published_at,
+        })
+    }
+    
+    /// Add `amount` of this mint to its insurance fund
+    pub fn fund_insurance(&mut self, amount: u64) -> Result<()> {
+        self.insurance_fund = self.insurance_fund
+            .checked_add(amount)
+            .ok_or(ReserveError::ArithmeticOverflow)?;
+        Ok(())
+    }
+    
+    /// Take this mint's full accrued fee balance for sweeping, resetting the counter
+    pub fn take_accrued_fees(&mut self) -> Result<u64> {
+        let amount = self.accrued_fees;
+        require!(amount > 0, ReserveError::NoFeesToSweep);
+        self.accrued_fees = 0;
+        Ok(amount)
+    }
+}
+
+/// Marks a client-supplied idempotency key as used by `owner`
+#[account]
+pub struct IdempotencyRecord {
+    pub owner: Pubkey,
+    pub key: [u8; 32],
+    pub created_at: i64,            // 0 until first claimed
+}
+
+impl IdempotencyRecord {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // owner
+        32 + // key
+        8;   // created_at
+    
+    /// Record a use of `key`, rejecting a repeat within `window` seconds
+    pub fn claim(&mut self, owner: Pubkey, key: [u8; 32], now: i64, window: i64) -> Result<()> {
+        if self.created_at != 0 {
+            require!(
+                now.saturating_sub(self.created_at) >= window,
+                ReserveError::DuplicateRequest
+            );
+        }
+        self.owner = owner;
+        self.key = key;
+        self.created_at = now;
+        Ok(())
+    }
+    
+    /// Whether the record is past its window and can be closed
+    pub fn is_expired(&self, now: i64, window: i64) -> bool {
+        now.saturating_sub(self.created_at) >= window
+    }
+}
+
+/// Emitted on each withdrawal, with the fee retained by the vault in the
+/// withdrawn mint
+#[event]
+pub struct Withdrawn {
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+    pub net_amount: u64,
+    pub recipient: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when governance reassigns the vault authority
+#[event]
+pub struct VaultAuthorityChanged {
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a keeper is paid for a rebalance that reduced drift
+#[event]
+pub struct RebalanceRewardPaid {
+    pub keeper: Pubkey,
+    pub amount: u64,
+    pub drift_before_bps: u16,
+    pub drift_after_bps: u16,
+    pub timestamp: i64,
+}
+
+/// Emitted when one mint's accrued fees are swept to the treasury
+#[event]
+pub struct FeesSwept {
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub treasury: Pubkey,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn vault() -> ReserveVault {
+        ReserveVault {
+            authority: Pubkey::new_unique(),
+            rebalance_threshold_bps: 1500,
+            treasury: Pubkey::new_unique(),
+            max_assets: 3,
+            bump: 255,
+            ..Default::default()
+        }
+    }
+    
+    fn asset() -> AssetConfig {
+        AssetConfig { mint: Pubkey::new_unique(), ..Default::default() }
+    }
+    
+    #[test]
+    fn test_sweep_takes_exact_accrued_amount() {
+        let mut vault = vault();
+        let mut usdc = asset();
+        vault.accrue_fee(&mut usdc, 1_250).unwrap();
+        vault.accrue_fee(&mut usdc, 750).unwrap();
+        
+        assert_eq!(usdc.take_accrued_fees().unwrap(), 2_000);
+        assert_eq!(usdc.accrued_fees, 0);
+        
+        // Nothing left to sweep
+        assert_eq!(
+            usdc.take_accrued_fees().unwrap_err(),
+            ReserveError::NoFeesToSweep.into()
+        );
+    }
+    
+    #[test]
+    fn test_fees_tracked_per_mint() {
+        let mut vault = vault();
+        let mut usdc = asset();
+        let mut sol = asset();
+        
+        // 5 USDC (6 decimals) and 0.1 SOL (9 decimals) are never summed
+        vault.accrue_fee(&mut usdc, 5_000_000).unwrap();
+        vault.accrue_fee(&mut sol, 100_000_000).unwrap();
+        
+        assert_eq!(sol.take_accrued_fees().unwrap(), 100_000_000);
+        assert_eq!(usdc.accrued_fees, 5_000_000);
+        assert_eq!(usdc.take_accrued_fees().unwrap(), 5_000_000);
+    }
+    
+    #[test]
+    fn test_withdraw_fee_retained_by_vault() {
+        let mut vault = vault();
+        
+        // No fee by default
+        assert_eq!(vault.split_withdrawal(10_000).unwrap(), (0, 10_000));
+        
+        vault.withdraw_fee_bps = 30;
+        vault.insurance_share_bps = 5000;
+        let gross = 1_000_000;
+        let (fee, net) = vault.split_withdrawal(gross).unwrap();
+        assert_eq!(fee, 3_000);
+        assert_eq!(net, gross - fee);
+        
+        // The fee stays with the vault, split between sweepable fees and
+        // the insurance fund
+        let mut usdc = asset();
+        vault.accrue_fee(&mut usdc, fee).unwrap();
+        assert_eq!(usdc.accrued_fees + usdc.insurance_fund, fee);
+        
+        // Dust withdrawals round the fee down to zero
+        assert_eq!(vault.split_withdrawal(333).unwrap(), (0, 333));
+    }
+    
+    #[test]
+    fn test_accrue_fee_overflow() {
+        let mut vault = vault();
+        let mut usdc = asset();
+        vault.accrue_fee(&mut usdc, u64::MAX).unwrap();
+        assert!(vault.accrue_fee(&mut usdc, 1).is_err());
+    }
+    
+    #[test]
+    fn test_fee_accrual_grows_insurance_fund() {
+        let mut vault = vault();
+        vault.insurance_share_bps = 2500;
+        
+        let mut usdc = asset();
+        vault.accrue_fee(&mut usdc, 1_000).unwrap();
+        vault.accrue_fee(&mut usdc, 3).unwrap();
+        
+        // 25% of each fee, rounded down, is carved out; the rest is swept
+        assert_eq!(usdc.insurance_fund, 250);
+        assert_eq!(usdc.accrued_fees, 753);
+        assert_eq!(usdc.take_accrued_fees().unwrap(), 753);
+        assert_eq!(usdc.insurance_fund, 250);
+        
+        // Each mint's fund grows only from fees in that mint
+        let mut sol = asset();
+        vault.accrue_fee(&mut sol, 4_000_000_000).unwrap();
+        assert_eq!(sol.insurance_fund, 1_000_000_000);
+        assert_eq!(usdc.insurance_fund, 250);
+    }
+    
+    #[test]
+    fn test_withdraw_recipient_allowlist() {
+        let mut vault = vault();
+        let treasury = Pubkey::new_unique();
+        let attacker = Pubkey::new_unique();
+        
+        // Inactive while empty
+        assert!(vault.allows_withdraw_recipient(&attacker));
+        
+        // Once active, only allowlisted accounts may receive withdrawals
+        vault.add_withdraw_recipient(treasury).unwrap();
+        assert!(vault.allows_withdraw_recipient(&treasury));
+        assert!(!vault.allows_withdraw_recipient(&attacker));
+        assert_eq!(
+            vault.add_withdraw_recipient(treasury).unwrap_err(),
+            ReserveError::WithdrawRecipientAlreadyAllowed.into()
+        );
+        
+        // Removing the last entry opens it up again
+        vault.remove_withdraw_recipient(&treasury).unwrap();
+        assert!(vault.allows_withdraw_recipient(&attacker));
+        assert_eq!(
+            vault.remove_withdraw_recipient(&treasury).unwrap_err(),
+            ReserveError::WithdrawRecipientNotFound.into()
+        );
+    }
+    
+    #[test]
+    fn test_withdraw_recipient_allowlist_bounded() {
+        let mut vault = vault();
+        for _ in 0..ReserveVault::MAX_WITHDRAW_RECIPIENTS {
+            vault.add_withdraw_recipient(Pubkey::new_unique()).unwrap();
+        }
+        assert_eq!(
+            vault.add_withdraw_recipient(Pubkey::new_unique()).unwrap_err(),
+            ReserveError::WithdrawRecipientAllowlistFull.into()
+        );
+    }
+    
+    #[test]
+    fn test_recorded_price() {
+        let mut usdc = asset();
+        assert!(usdc.recorded_price().is_none());
+        
+        usdc.record_price(AssetPrice { price_usd: 1_000_000, published_at: 1_700_000_000 });
+        let price = usdc.recorded_price().unwrap();
+        assert_eq!((price.price_usd, price.published_at), (1_000_000, 1_700_000_000));
+    }
+    
+    #[test]
+    fn test_rebalance_reward_only_when_needed() {
+        let mut vault = vault();
+        vault.rebalance_reward = 500;
+        let mut usdc = asset();
+        vault.accrue_fee(&mut usdc, 2_000).unwrap();
+        
+        // Drift within the 15% threshold: a no-op rebalance pays nothing
+        assert_eq!(vault.take_rebalance_reward(&mut usdc, 0, 0), 0);
+        assert_eq!(vault.take_rebalance_reward(&mut usdc, 1500, 0), 0);
+        assert_eq!(usdc.accrued_fees, 2_000);
+        
+        // Drift was high, but the swaps left it (nearly) where it was
+        assert_eq!(vault.take_rebalance_reward(&mut usdc, 3000, 3000), 0);
+        assert_eq!(vault.take_rebalance_reward(&mut usdc, 3000, 2901), 0);
+        assert_eq!(vault.take_rebalance_reward(&mut usdc, 3000, 3500), 0);
+        assert_eq!(usdc.accrued_fees, 2_000);
+        
+        // A needed rebalance that cut drift pays the reward out of accrued fees
+        assert_eq!(vault.take_rebalance_reward(&mut usdc, 1501, 1401), 500);
+        assert_eq!(usdc.accrued_fees, 1_500);
+        
+        // Capped at what the mint's fee pool holds
+        vault.rebalance_reward = 10_000;
+        assert_eq!(vault.take_rebalance_reward(&mut usdc, 3000, 500), 1_500);
+        assert_eq!(usdc.accrued_fees, 0);
+        assert_eq!(vault.take_rebalance_reward(&mut usdc, 3000, 500), 0);
+    }
+    
+    #[test]
+    fn test_insurance_drawn_before_main_reserve() {
+        let mut vault = vault();
+        vault.total_value_usd = 900_000;
+        vault.liabilities_usd = 1_000_000;
+        vault.vhr = 9000;
+        let mut usdc = asset();
+        let mut sol = asset();
+        usdc.fund_insurance(50_000).unwrap();
+        
+        // Covered entirely by the fund
+        assert_eq!(vault.draw_insurance(&mut usdc, 30_000).unwrap(), (30_000, 0));
+        assert_eq!(usdc.insurance_fund, 20_000);
+        
+        // Fund exhausted, the rest comes out of the main reserve
+        assert_eq!(vault.draw_insurance(&mut usdc, 45_000).unwrap(), (20_000, 25_000));
+        assert_eq!(usdc.insurance_fund, 0);
+        
+        // One mint's fund never covers a loss in another
+        usdc.fund_insurance(50_000).unwrap();
+        assert_eq!(vault.draw_insurance(&mut sol, 10_000).unwrap(), (0, 10_000));
+        assert_eq!(usdc.insurance_fund, 50_000);
+    }
+    
+    #[test]
+    fn test_insurance_draw_requires_undercollateralization() {
+        let mut vault = vault();
+        let mut usdc = asset();
+        usdc.fund_insurance(50_000).unwrap();
+        vault.total_value_usd = 1_500_000;
+        vault.liabilities_usd = 1_000_000;
+        vault.vhr = 15000;
+        
+        assert_eq!(
+            vault.draw_insurance(&mut usdc, 1).unwrap_err(),
+            ReserveError::InsuranceDrawNotAllowed.into()
+        );
+        assert_eq!(usdc.insurance_fund, 50_000);
+    }
+    
+    #[test]
+    fn test_asset_registration_capped() {
+        let mut vault = vault();
+        
+        vault.register_assets(2).unwrap();
+        vault.register_assets(1).unwrap();
+        assert_eq!(vault.asset_count, 3);
+        
+        // At the cap, nothing more can be registered
+        assert_eq!(
+            vault.register_assets(1).unwrap_err(),
+            ReserveError::TooManyAssets.into()
+        );
+        assert_eq!(vault.asset_count, 3);
+        
+        // Raising the cap makes room again
+        vault.max_assets = 4;
+        vault.register_assets(1).unwrap();
+        assert!(vault.register_assets(1).is_err());
+        assert!(vault.register_assets(usize::MAX).is_err());
+    }
+    
+    #[test]
+    fn test_deposit_per_tx_cap() {
+        let mut vault = vault();
+        
+        // Uncapped by default
+        assert!(vault.check_deposit_caps(u64::MAX).is_ok());
+        
+        vault.max_deposit_per_tx = 1_000_000;
+        assert!(vault.check_deposit_caps(1_000_000).is_ok());
+        assert_eq!(
+            vault.check_deposit_caps(1_000_001).unwrap_err(),
+            ReserveError::DepositExceedsTxCap.into()
+        );
+    }
+    
+    #[test]
+    fn test_deposit_vault_cap() {
+        let mut vault = vault();
+        vault.total_value_usd = 9_000_000;
+        vault.max_total_value_usd = 10_000_000;
+        
+        // Exactly filling the vault is allowed
+        assert!(vault.check_deposit_caps(1_000_000).is_ok());
+        assert_eq!(
+            vault.check_deposit_caps(1_000_001).unwrap_err(),
+            ReserveError::DepositExceedsVaultCap.into()
+        );
+        
+        // Overflowing the running total is rejected rather than wrapping
+        vault.total_value_usd = u64::MAX;
+        assert_eq!(
+            vault.check_deposit_caps(1).unwrap_err(),
+            ReserveError::ArithmeticOverflow.into()
+        );
+    }
+    
+    #[test]
+    fn test_idempotency_key_rejects_repeat() {
+        let owner = Pubkey::new_unique();
+        let window = 3_600;
+        let mut record = IdempotencyRecord { owner: Pubkey::default(), key: [0; 32], created_at: 0 };
+        
+        // First use of a fresh key succeeds
+        record.claim(owner, [7; 32], 1_000, window).unwrap();
+        assert_eq!(record.created_at, 1_000);
+        
+        // A retry inside the window is a duplicate
+        assert_eq!(
+            record.claim(owner, [7; 32], 1_000 + window - 1, window).unwrap_err(),
+            ReserveError::DuplicateRequest.into()
+        );
+        assert!(!record.is_expired(1_000 + window - 1, window));
+        
+        // Once the window passes the record can be reused or closed
+        assert!(record.is_expired(1_000 + window, window));
+        assert!(record.claim(owner, [7; 32], 1_000 + window, window).is_ok());
+    }
 }
@@ -40,6 +40,26 @@ pub struct JupiterSwapParams {
     pub output_mint: Pubkey,
     pub amount: u64,
     pub slippage_config: SlippageConfig,
+    /// Last unix timestamp at which the swap may execute
+    pub deadline_ts: i64,
+}
+
+impl JupiterSwapParams {
+    /// Reject the swap before the CPI if the deadline has passed
+    pub fn check_deadline(&self, now: i64) -> Result<()> {
+        check_swap_deadline(self.deadline_ts, now)
+    }
+    
+    /// Reject the swap after the CPI if it returned less than min_output_amount
+    pub fn check_output(&self, actual_output: u64) -> Result<()> {
+        self.slippage_config.validate_output(actual_output)
+    }
+}
+
+/// A delayed swap must revert rather than execute at a stale price
+pub fn check_swap_deadline(deadline_ts: i64, now: i64) -> Result<()> {
+    require!(now <= deadline_ts, ReserveError::DeadlineExceeded);
+    Ok(())
 }
 
 /// Validate CPI accounts before invocation
@@ -125,4 +145,30 @@ mod tests {
         // Should fail below min
         assert!(config.validate_output(994_999).is_err());
     }
+    
+    #[test]
+    fn test_swap_deadline_and_min_output() {
+        let now = 1_700_000_000;
+        let params = JupiterSwapParams {
+            input_mint: Pubkey::new_unique(),
+            output_mint: Pubkey::new_unique(),
+            amount: 1_000_000,
+            slippage_config: SlippageConfig { max_slippage_bps: 50, min_output_amount: 995_000 },
+            deadline_ts: now,
+        };
+        
+        // Up to and including the deadline
+        assert!(params.check_deadline(now).is_ok());
+        assert_eq!(
+            params.check_deadline(now + 1).unwrap_err(),
+            ReserveError::DeadlineExceeded.into()
+        );
+        
+        // Output at or above min_amount_out
+        assert!(params.check_output(995_000).is_ok());
+        assert_eq!(
+            params.check_output(994_999).unwrap_err(),
+            ReserveError::SlippageExceeded.into()
+        );
+    }
 }
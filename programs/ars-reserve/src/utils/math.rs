@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ReserveError;
+
+/// `a * b / denominator` rounded down, computed in u128 so the intermediate
+/// product cannot overflow.
+pub fn mul_div_floor(a: u64, b: u64, denominator: u64) -> Result<u64> {
+    require!(denominator > 0, ReserveError::DivisionByZero);
+    let quotient = (a as u128) * (b as u128) / denominator as u128;
+    u64::try_from(quotient).map_err(|_| error!(ReserveError::ArithmeticOverflow))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_mul_div_floor() {
+        assert_eq!(mul_div_floor(999, 30, 10000).unwrap(), 2);
+        assert_eq!(mul_div_floor(u64::MAX, 10000, 10000).unwrap(), u64::MAX);
+        assert!(mul_div_floor(u64::MAX, 2, 1).is_err());
+        assert_eq!(
+            mul_div_floor(1, 1, 0).unwrap_err(),
+            ReserveError::DivisionByZero.into()
+        );
+    }
+}
@@ -1,5 +1,7 @@
 pub mod security;
 pub mod cpi_helpers;
+pub mod math;
 
 pub use security::*;
 pub use cpi_helpers::*;
+pub use math::*;
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
 
 use crate::errors::ReserveError;
 
@@ -50,6 +51,13 @@ pub fn validate_account_owner(
     Ok(())
 }
 
+/// Whether a user token account holds the same mint as the vault's.
+/// Checked up front so a mismatch fails with InvalidTokenMint rather than
+/// an opaque SPL transfer error.
+pub fn mint_matches(token_account: &TokenAccount, vault_token_account: &TokenAccount) -> bool {
+    token_account.mint == vault_token_account.mint
+}
+
 /// Validate PDA derivation matches expected seeds
 /// Critical for preventing PDA spoofing attacks
 pub fn validate_pda(
@@ -91,4 +99,29 @@ mod tests {
         // Lock should be released
         assert!(!locked);
     }
+    
+    fn token_account(mint: Pubkey) -> TokenAccount {
+        use anchor_lang::solana_program::program_pack::Pack;
+        use anchor_spl::token::spl_token::state::{Account, AccountState};
+        
+        let account = Account {
+            mint,
+            owner: Pubkey::new_unique(),
+            amount: 1_000,
+            state: AccountState::Initialized,
+            ..Default::default()
+        };
+        let mut data = vec![0u8; Account::LEN];
+        Account::pack(account, &mut data).unwrap();
+        TokenAccount::try_deserialize(&mut &data[..]).unwrap()
+    }
+    
+    #[test]
+    fn test_mismatched_mint_rejected() {
+        let vault_mint = Pubkey::new_unique();
+        let vault_token_account = token_account(vault_mint);
+        
+        assert!(mint_matches(&token_account(vault_mint), &vault_token_account));
+        assert!(!mint_matches(&token_account(Pubkey::new_unique()), &vault_token_account));
+    }
 }
@@ -28,4 +28,7 @@ pub enum TokenError {
     
     #[msg("Arithmetic underflow")]
     ArithmeticUnderflow,
+    
+    #[msg("Account already migrated to the current version")]
+    AlreadyMigrated,
 }
@@ -49,6 +49,8 @@ pub fn handler(
     token_state.epoch_burned = 0;
     token_state.total_supply_at_epoch_start = ctx.accounts.mint.supply;
     token_state.circuit_breaker_active = false;
+    token_state.version = TokenState::CURRENT_VERSION;
+    token_state.pending_mint_burn_cap_bps = None;
     token_state.bump = ctx.bumps.token_state;
     
     msg!("ARU token initialized");
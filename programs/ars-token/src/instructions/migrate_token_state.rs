@@ -0,0 +1,203 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+use crate::state::*;
+use crate::errors::TokenError;
+use crate::instructions::initialize_mint::TOKEN_STATE_SEED;
+
+/// Size of the original (unversioned) layout, which ended with
+/// circuit_breaker_active and bump
+pub const LEGACY_TOKEN_STATE_LEN: usize = 126;
+
+/// Offset of `TokenState::version`, directly after circuit_breaker_active
+const VERSION_OFFSET: usize = 125;
+
+#[derive(Accounts)]
+pub struct MigrateTokenState<'info> {
+    /// CHECK: Loaded manually - an old-layout account is too small to
+    /// deserialize as the current TokenState until it has been reallocated
+    #[account(
+        mut,
+        seeds = [TOKEN_STATE_SEED],
+        bump,
+        owner = crate::ID
+    )]
+    pub token_state: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_token_state(ctx: Context<MigrateTokenState>) -> Result<()> {
+    let token_state_info = ctx.accounts.token_state.to_account_info();
+    let new_len = TokenState::LEN;
+    
+    // The legacy layout is only recognisable by its size, so read the
+    // version before the realloc below
+    let from_version = stored_version(&token_state_info.try_borrow_data()?);
+    
+    // Grow the account to the current layout, charging the extra rent to the authority
+    if token_state_info.data_len() < new_len {
+        let rent = Rent::get()?;
+        let lamports_needed = rent
+            .minimum_balance(new_len)
+            .saturating_sub(token_state_info.lamports());
+        
+        if lamports_needed > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: token_state_info.clone(),
+                    },
+                ),
+                lamports_needed,
+            )?;
+        }
+        
+        token_state_info.realloc(new_len, true)?;
+    }
+    
+    let mut data = token_state_info.try_borrow_mut_data()?;
+    migrate_token_state_data(&mut data[..], from_version, &ctx.accounts.authority.key())?;
+    
+    msg!("TokenState migrated from version {} to {}", from_version, TokenState::CURRENT_VERSION);
+    msg!("Account size: {} bytes", new_len);
+    
+    Ok(())
+}
+
+/// Layout version of stored TokenState data. The unversioned layout has
+/// bump where the version byte now sits, so it is told apart by size.
+pub fn stored_version(data: &[u8]) -> u8 {
+    if data.len() <= LEGACY_TOKEN_STATE_LEN {
+        return 0;
+    }
+    data.get(VERSION_OFFSET).copied().unwrap_or(0)
+}
+
+/// Upgrade TokenState data stored in layout `from_version` (already sized
+/// to TokenState::LEN) to the current layout version
+pub fn migrate_token_state_data(data: &mut [u8], from_version: u8, authority: &Pubkey) -> Result<()> {
+    // Each version can only be migrated once
+    require!(
+        from_version < TokenState::CURRENT_VERSION,
+        TokenError::AlreadyMigrated
+    );
+    
+    let token_state = deserialize_legacy(data)?;
+    require_keys_eq!(token_state.authority, *authority, TokenError::Unauthorized);
+    
+    data.fill(0);
+    let mut writer: &mut [u8] = data;
+    token_state.try_serialize(&mut writer)?;
+    
+    Ok(())
+}
+
+/// Read TokenState data stored in the original layout. A legacy account
+/// has no cap change scheduled.
+pub fn deserialize_legacy(data: &[u8]) -> Result<TokenState> {
+    require!(
+        data.len() >= LEGACY_TOKEN_STATE_LEN && data[..8] == TokenState::DISCRIMINATOR,
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+    
+    let buf = &mut &data[8..];
+    Ok(TokenState {
+        authority: read(buf)?,
+        mint: read(buf)?,
+        epoch_duration: read(buf)?,
+        mint_burn_cap_bps: read(buf)?,
+        stability_fee_bps: read(buf)?,
+        current_epoch: read(buf)?,
+        epoch_start_time: read(buf)?,
+        epoch_minted: read(buf)?,
+        epoch_burned: read(buf)?,
+        total_supply_at_epoch_start: read(buf)?,
+        circuit_breaker_active: read(buf)?,
+        version: TokenState::CURRENT_VERSION,
+        pending_mint_burn_cap_bps: None,
+        bump: read(buf)?,
+    })
+}
+
+fn read<T: AnchorDeserialize>(buf: &mut &[u8]) -> Result<T> {
+    Ok(T::deserialize(buf)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn legacy_account(authority: Pubkey) -> Vec<u8> {
+        let mut data = TokenState::DISCRIMINATOR.to_vec();
+        (
+            authority,
+            Pubkey::new_unique(),
+            86_400i64,
+            200u16,
+            10u16,
+            3u64,
+            1_000i64,
+            500u64,
+            200u64,
+            1_000_000u64,
+            true,
+            254u8,
+        )
+            .serialize(&mut data)
+            .unwrap();
+        assert_eq!(data.len(), LEGACY_TOKEN_STATE_LEN);
+        data
+    }
+    
+    #[test]
+    fn test_migrate_legacy_token_state() {
+        let authority = Pubkey::new_unique();
+        let mut data = legacy_account(authority);
+        
+        // The legacy bump must not be read as a version
+        let from_version = stored_version(&data);
+        assert_eq!(from_version, 0);
+        
+        data.resize(TokenState::LEN, 0);
+        migrate_token_state_data(&mut data, from_version, &authority).unwrap();
+        
+        let migrated = TokenState::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(migrated.version, TokenState::CURRENT_VERSION);
+        assert_eq!(migrated.pending_mint_burn_cap_bps, None);
+        
+        // Existing fields survive the migration
+        assert_eq!(migrated.authority, authority);
+        assert_eq!(migrated.epoch_duration, 86_400);
+        assert_eq!(migrated.mint_burn_cap_bps, 200);
+        assert_eq!(migrated.current_epoch, 3);
+        assert_eq!((migrated.epoch_minted, migrated.epoch_burned), (500, 200));
+        assert_eq!(migrated.total_supply_at_epoch_start, 1_000_000);
+        assert!(migrated.circuit_breaker_active);
+        assert_eq!(migrated.bump, 254);
+        
+        // A migrated account reports its version and cannot be migrated again
+        let version = stored_version(&data);
+        assert_eq!(version, TokenState::CURRENT_VERSION);
+        assert_eq!(
+            migrate_token_state_data(&mut data, version, &authority).unwrap_err(),
+            TokenError::AlreadyMigrated.into()
+        );
+    }
+    
+    #[test]
+    fn test_migrate_requires_token_authority() {
+        let mut data = legacy_account(Pubkey::new_unique());
+        data.resize(TokenState::LEN, 0);
+        
+        assert_eq!(
+            migrate_token_state_data(&mut data, 0, &Pubkey::new_unique()).unwrap_err(),
+            TokenError::Unauthorized.into()
+        );
+    }
+}
@@ -10,14 +10,14 @@ pub struct MintICU<'info> {
         mut,
         seeds = [TOKEN_STATE_SEED],
         bump = token_state.bump,
-        constraint = token_state.authority == authority.key() @ TokenError::Unauthorized,
+        has_one = authority @ TokenError::Unauthorized,
         constraint = !token_state.circuit_breaker_active @ TokenError::CircuitBreakerActive
     )]
     pub token_state: Account<'info, TokenState>,
     
     #[account(
         mut,
-        constraint = mint.key() == token_state.mint @ TokenError::Unauthorized
+        address = token_state.mint @ TokenError::Unauthorized
     )]
     pub mint: Account<'info, Mint>,
     
@@ -40,21 +40,10 @@ pub fn handler(
     
     // Check if we need to start a new epoch
     let clock = Clock::get()?;
-    if clock.unix_timestamp >= token_state.epoch_start_time + token_state.epoch_duration {
-        // New epoch - reset counters
-        token_state.current_epoch += 1;
-        token_state.epoch_start_time = clock.unix_timestamp;
-        token_state.epoch_minted = 0;
-        token_state.epoch_burned = 0;
-        token_state.total_supply_at_epoch_start = ctx.accounts.mint.supply;
-    }
+    token_state.advance_epoch_if_due(clock.unix_timestamp, ctx.accounts.mint.supply)?;
     
     // Calculate mint cap for this epoch (±2% of supply at epoch start)
-    let mint_cap = (token_state.total_supply_at_epoch_start as u128)
-        .checked_mul(token_state.mint_burn_cap_bps as u128)
-        .ok_or(TokenError::ArithmeticOverflow)?
-        .checked_div(10000)
-        .ok_or(TokenError::ArithmeticOverflow)? as u64;
+    let mint_cap = token_state.epoch_cap()?;
     
     // Check if minting this amount would exceed cap
     let new_minted = token_state.epoch_minted
@@ -2,8 +2,12 @@ pub mod initialize_mint;
 pub mod mint_icu;
 pub mod burn_icu;
 pub mod start_new_epoch;
+pub mod set_mint_burn_cap;
+pub mod migrate_token_state;
 
 pub use initialize_mint::*;
 pub use mint_icu::*;
 pub use burn_icu::*;
 pub use start_new_epoch::*;
+pub use set_mint_burn_cap::*;
+pub use migrate_token_state::*;
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::TokenError;
+use crate::instructions::initialize_mint::TOKEN_STATE_SEED;
+
+#[derive(Accounts)]
+pub struct SetMintBurnCap<'info> {
+    #[account(
+        mut,
+        seeds = [TOKEN_STATE_SEED],
+        bump = token_state.bump,
+        has_one = authority @ TokenError::Unauthorized
+    )]
+    pub token_state: Account<'info, TokenState>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetMintBurnCap>, mint_burn_cap_bps: u16) -> Result<()> {
+    let token_state = &mut ctx.accounts.token_state;
+    
+    // The running epoch keeps its cap; see TokenState::schedule_mint_burn_cap
+    token_state.schedule_mint_burn_cap(mint_burn_cap_bps)?;
+    
+    msg!("Mint/burn cap: {} bps (current epoch {})", token_state.mint_burn_cap_bps, token_state.current_epoch);
+    msg!("Scheduled from next epoch: {} bps", mint_burn_cap_bps);
+    
+    Ok(())
+}
@@ -20,13 +20,7 @@ pub fn handler(ctx: Context<StartNewEpoch>) -> Result<()> {
     let clock = Clock::get()?;
     
     // Check if epoch has ended
-    if clock.unix_timestamp >= token_state.epoch_start_time + token_state.epoch_duration {
-        token_state.current_epoch += 1;
-        token_state.epoch_start_time = clock.unix_timestamp;
-        token_state.epoch_minted = 0;
-        token_state.epoch_burned = 0;
-        token_state.total_supply_at_epoch_start = ctx.accounts.mint.supply;
-        
+    if token_state.advance_epoch_if_due(clock.unix_timestamp, ctx.accounts.mint.supply)? {
         msg!("New epoch started: {}", token_state.current_epoch);
         msg!("Supply at epoch start: {}", token_state.total_supply_at_epoch_start);
     } else {
@@ -46,4 +46,14 @@ pub mod ars_token {
     pub fn start_new_epoch(ctx: Context<StartNewEpoch>) -> Result<()> {
         instructions::start_new_epoch::handler(ctx)
     }
+
+    /// Schedule a new mint/burn cap for the next epoch
+    pub fn set_mint_burn_cap(ctx: Context<SetMintBurnCap>, mint_burn_cap_bps: u16) -> Result<()> {
+        instructions::set_mint_burn_cap::handler(ctx, mint_burn_cap_bps)
+    }
+
+    /// Migrate the token state to the current layout version
+    pub fn migrate_token_state(ctx: Context<MigrateTokenState>) -> Result<()> {
+        instructions::migrate_token_state::migrate_token_state(ctx)
+    }
 }
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::errors::TokenError;
 
 /// Token state for ARU
 #[account]
@@ -14,10 +15,16 @@ pub struct TokenState {
     pub epoch_burned: u64,
     pub total_supply_at_epoch_start: u64,
     pub circuit_breaker_active: bool,
+    pub version: u8,                // Layout version, see migrate_token_state
+    pub pending_mint_burn_cap_bps: Option<u16>, // Applied at the next epoch rollover
     pub bump: u8,
 }
 
 impl TokenState {
+    /// Layout version written by this program. The original layout, which
+    /// ended with circuit_breaker_active and bump, is version 0.
+    pub const CURRENT_VERSION: u8 = 1;
+    
     pub const LEN: usize = 8 + // discriminator
         32 + // authority
         32 + // mint
@@ -30,7 +37,58 @@ impl TokenState {
         8 +  // epoch_burned
         8 +  // total_supply_at_epoch_start
         1 +  // circuit_breaker_active
+        1 +  // version
+        3 +  // pending_mint_burn_cap_bps
         1;   // bump
+    
+    /// When the current epoch ends. Re-checks the epoch duration and rejects
+    /// i64 overflow at extreme start times.
+    pub fn epoch_end(&self) -> Result<i64> {
+        require!(self.epoch_duration > 0, TokenError::InvalidEpochDuration);
+        self.epoch_start_time
+            .checked_add(self.epoch_duration)
+            .ok_or(error!(TokenError::ArithmeticOverflow))
+    }
+    
+    /// Start a new epoch at `now` if the current one has ended, resetting the
+    /// mint/burn counters against `supply`. Returns whether it rolled over.
+    pub fn advance_epoch_if_due(&mut self, now: i64, supply: u64) -> Result<bool> {
+        if now < self.epoch_end()? {
+            return Ok(false);
+        }
+        
+        self.current_epoch = self.current_epoch
+            .checked_add(1)
+            .ok_or(TokenError::ArithmeticOverflow)?;
+        self.epoch_start_time = now;
+        self.epoch_minted = 0;
+        self.epoch_burned = 0;
+        self.total_supply_at_epoch_start = supply;
+        if let Some(cap_bps) = self.pending_mint_burn_cap_bps.take() {
+            self.mint_burn_cap_bps = cap_bps;
+        }
+        Ok(true)
+    }
+    
+    /// Mint (or burn) allowance for the current epoch, as a share of the
+    /// supply at epoch start.
+    pub fn epoch_cap(&self) -> Result<u64> {
+        Ok((self.total_supply_at_epoch_start as u128)
+            .checked_mul(self.mint_burn_cap_bps as u128)
+            .ok_or(TokenError::ArithmeticOverflow)?
+            .checked_div(10000)
+            .ok_or(TokenError::ArithmeticOverflow)? as u64)
+    }
+    
+    /// Schedule a new mint/burn cap. The counters for the running epoch were
+    /// accumulated under the current cap, so the change only takes effect
+    /// when the next epoch starts; a reduction never retroactively puts
+    /// already-executed mints or burns over the limit.
+    pub fn schedule_mint_burn_cap(&mut self, cap_bps: u16) -> Result<()> {
+        require!(cap_bps <= 10000, TokenError::InvalidMintBurnCap);
+        self.pending_mint_burn_cap_bps = Some(cap_bps);
+        Ok(())
+    }
 }
 
 /// Mint/burn event for logging
@@ -42,3 +100,95 @@ pub struct MintBurnEvent {
     pub timestamp: i64,
     pub epoch: u64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn token_state(epoch_start_time: i64, epoch_duration: i64) -> TokenState {
+        TokenState {
+            authority: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            epoch_duration,
+            mint_burn_cap_bps: 200,
+            stability_fee_bps: 10,
+            current_epoch: 3,
+            epoch_start_time,
+            epoch_minted: 500,
+            epoch_burned: 200,
+            total_supply_at_epoch_start: 1_000_000,
+            circuit_breaker_active: false,
+            version: TokenState::CURRENT_VERSION,
+            pending_mint_burn_cap_bps: None,
+            bump: 255,
+        }
+    }
+    
+    #[test]
+    fn test_epoch_end_overflow() {
+        assert_eq!(token_state(1_000, 86_400).epoch_end().unwrap(), 87_400);
+        assert_eq!(token_state(i64::MAX - 86_400, 86_400).epoch_end().unwrap(), i64::MAX);
+        
+        // Near-i64::MAX starts fail cleanly instead of wrapping
+        assert_eq!(
+            token_state(i64::MAX - 86_399, 86_400).epoch_end().unwrap_err(),
+            TokenError::ArithmeticOverflow.into()
+        );
+        let mut state = token_state(i64::MAX, 1);
+        assert!(state.advance_epoch_if_due(i64::MAX, 2_000_000).is_err());
+        assert_eq!(state.current_epoch, 3);
+        
+        // A zero duration is rejected rather than rolling over every call
+        assert_eq!(
+            token_state(1_000, 0).epoch_end().unwrap_err(),
+            TokenError::InvalidEpochDuration.into()
+        );
+    }
+    
+    #[test]
+    fn test_advance_epoch_if_due() {
+        let mut state = token_state(1_000, 86_400);
+        
+        assert!(!state.advance_epoch_if_due(87_399, 2_000_000).unwrap());
+        assert_eq!(state.current_epoch, 3);
+        
+        assert!(state.advance_epoch_if_due(87_400, 2_000_000).unwrap());
+        assert_eq!(state.current_epoch, 4);
+        assert_eq!(state.epoch_start_time, 87_400);
+        assert_eq!((state.epoch_minted, state.epoch_burned), (0, 0));
+        assert_eq!(state.total_supply_at_epoch_start, 2_000_000);
+    }
+    
+    #[test]
+    fn test_cap_change_applies_from_next_epoch() {
+        // 2% of 1,000,000 with 500 already minted this epoch
+        let mut state = token_state(1_000, 86_400);
+        assert_eq!(state.epoch_cap().unwrap(), 20_000);
+        
+        // Cutting the cap below what was already minted leaves the
+        // running epoch untouched
+        state.schedule_mint_burn_cap(1).unwrap();
+        assert_eq!(state.mint_burn_cap_bps, 200);
+        assert_eq!(state.epoch_cap().unwrap(), 20_000);
+        assert!(state.epoch_minted <= state.epoch_cap().unwrap());
+        assert!(!state.advance_epoch_if_due(87_399, 1_000_500).unwrap());
+        assert_eq!(state.pending_mint_burn_cap_bps, Some(1));
+        
+        // Rollover picks up the new cap against fresh counters
+        assert!(state.advance_epoch_if_due(87_400, 1_000_500).unwrap());
+        assert_eq!(state.mint_burn_cap_bps, 1);
+        assert_eq!(state.pending_mint_burn_cap_bps, None);
+        assert_eq!(state.epoch_cap().unwrap(), 100);
+        assert_eq!(state.epoch_minted, 0);
+        
+        // A later rollover with nothing scheduled keeps it
+        assert!(state.advance_epoch_if_due(173_800, 1_000_500).unwrap());
+        assert_eq!(state.mint_burn_cap_bps, 1);
+        
+        assert_eq!(
+            state.schedule_mint_burn_cap(10_001).unwrap_err(),
+            TokenError::InvalidMintBurnCap.into()
+        );
+        assert_eq!(state.pending_mint_burn_cap_bps, None);
+    }
+}